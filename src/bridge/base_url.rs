@@ -0,0 +1,83 @@
+use crate::{Error, Result};
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
+
+/// Returns the base URL of a bridge with the given IP address.
+///
+/// IPv6 addresses are enclosed in brackets, e.g. `http://[fe80::1]`.
+pub(crate) fn from_ip(ip_address: IpAddr) -> String {
+    match ip_address {
+        IpAddr::V4(v) => format!("http://{}", v),
+        IpAddr::V6(v) => format!("http://[{}]", v),
+    }
+}
+
+/// Validates a base URL and returns it without trailing slashes, together with the IP address of
+/// the host.
+///
+/// If the host is a name, it is resolved and the first IP address is returned.
+pub(crate) fn parse(base_url: &str) -> Result<(String, IpAddr)> {
+    let invalid = || Error::InvalidBaseUrl(base_url.to_owned());
+    let base_url = base_url.trim_end_matches('/');
+    let (rest, default_port) = match base_url.strip_prefix("http://") {
+        Some(v) => (v, 80),
+        None => (base_url.strip_prefix("https://").ok_or_else(invalid)?, 443),
+    };
+    let authority = rest.split('/').next().unwrap_or_default();
+    if authority.is_empty() || authority.contains(|c: char| c.is_whitespace() || c == '@') {
+        return Err(invalid());
+    }
+    let ip_address = match authority.parse::<SocketAddr>() {
+        Ok(v) => v.ip(),
+        Err(_) => match authority.trim_matches(&['[', ']'][..]).parse() {
+            Ok(v) => v,
+            Err(_) => resolve(authority, default_port).ok_or_else(invalid)?,
+        },
+    };
+    Ok((base_url.to_owned(), ip_address))
+}
+
+/// Resolves the host of an authority like `example.com:8080` to an IP address.
+fn resolve(authority: &str, default_port: u16) -> Option<IpAddr> {
+    let addresses = match authority.contains(':') {
+        true => authority.to_socket_addrs(),
+        false => (authority, default_port).to_socket_addrs(),
+    };
+    addresses.ok()?.next().map(|v| v.ip())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn from_ip() {
+        let ip_address = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2));
+        assert_eq!(super::from_ip(ip_address), "http://192.168.1.2");
+        let ip_address = IpAddr::V6(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1));
+        assert_eq!(super::from_ip(ip_address), "http://[fe80::1]");
+    }
+
+    #[test]
+    fn parse() {
+        let ip_address = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2));
+        assert_eq!(
+            super::parse("http://192.168.1.2:8080/").unwrap(),
+            ("http://192.168.1.2:8080".to_owned(), ip_address)
+        );
+        let ip_address = IpAddr::V6(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1));
+        assert_eq!(
+            super::parse("http://[fe80::1]").unwrap(),
+            ("http://[fe80::1]".to_owned(), ip_address)
+        );
+        let (base_url, ip_address) = super::parse("https://localhost:8443/hue").unwrap();
+        assert_eq!(base_url, "https://localhost:8443/hue");
+        assert!(ip_address.is_loopback());
+        assert!(super::parse("http://localhost").unwrap().1.is_loopback());
+        assert!(matches!(
+            super::parse("ftp://192.168.1.2"),
+            Err(Error::InvalidBaseUrl(_))
+        ));
+        assert!(super::parse("http:///api").is_err());
+    }
+}
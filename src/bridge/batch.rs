@@ -0,0 +1,194 @@
+use super::Bridge;
+use crate::resource::Modifier;
+use crate::{response::Modified, Response, Result};
+use derive_setters::Setters;
+use serde_json::Value as JsonValue;
+use std::time::Duration;
+
+/// A list of modifications that are sent to the bridge together.
+///
+/// Every command consists of the identifier of a resource and a modifier. Modifiers of different
+/// types can be mixed in the same batch. The batch is executed with [`Bridge::execute_batch`].
+///
+/// # Examples
+///
+/// Turn on two lights and rename a group, waiting 100 milliseconds between requests:
+/// ```no_run
+/// use huelib2::bridge::Batch;
+/// use huelib2::resource::{group, light};
+/// use std::time::Duration;
+///
+/// # fn main() -> huelib2::Result<()> {
+/// # use huelib2::Bridge;
+/// # use std::net::{IpAddr, Ipv4Addr};
+/// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+/// // let bridge = Bridge::new(...);
+/// let on = light::StateModifier::new().with_on(true);
+/// let mut batch = Batch::new().with_pacing(Duration::from_millis(100));
+/// batch.push("1".to_owned(), &on)?;
+/// batch.push("2".to_owned(), &on)?;
+/// batch.push(
+///     "1".to_owned(),
+///     &group::AttributeModifier::new().with_name("Living room".into()),
+/// )?;
+/// for result in bridge.execute_batch(&batch) {
+///     if let Err(e) = result {
+///         println!("Command failed: {}", e);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Setters)]
+#[setters(prefix = "with_")]
+pub struct Batch {
+    /// Commands as URL suffix and serialized modifier.
+    #[setters(skip)]
+    commands: Vec<(String, JsonValue)>,
+    /// Time to wait between two consecutive requests of the same thread.
+    pacing: Duration,
+    /// Maximum number of threads used to send the requests.
+    ///
+    /// Commands are sent in order if this is at most one (the default). Otherwise, commands that
+    /// modify the same resource are still sent in order.
+    threads: usize,
+}
+
+impl Batch {
+    /// Creates a new, empty batch.
+    ///
+    /// Commands are sent one after another without pacing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a command that modifies the resource with the given identifier.
+    ///
+    /// The modifier is serialized immediately, so later changes to it do not affect the batch.
+    pub fn push<M: Modifier>(&mut self, id: M::Id, modifier: &M) -> Result<()> {
+        self.commands
+            .push((M::url_suffix(id), serde_json::to_value(modifier)?));
+        Ok(())
+    }
+
+    /// Returns the number of commands in the batch.
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Returns whether the batch contains no commands.
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+}
+
+pub(super) fn execute(bridge: &Bridge, batch: &Batch) -> Vec<Result<Vec<Response<Modified>>>> {
+    let targets = batch
+        .commands
+        .iter()
+        .enumerate()
+        .map(|(i, (url_suffix, body))| (i, url_suffix.clone(), body))
+        .collect();
+    let mut results = bridge.put_many(targets, batch.threads, batch.pacing);
+    (0..batch.commands.len())
+        .map(|i| results.remove(&i).expect("missing result of command"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::{group, light, Adjust};
+    use serde_json::json;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    #[test]
+    fn execute() {
+        let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "user").with_dry_run();
+        let mut batch = Batch::new();
+        batch
+            .push("1".to_owned(), &light::StateModifier::new().with_on(true))
+            .unwrap();
+        batch
+            .push(
+                "2".to_owned(),
+                &group::AttributeModifier::new().with_name("test".into()),
+            )
+            .unwrap();
+        assert_eq!(batch.len(), 2);
+
+        let results = bridge.execute_batch(&batch);
+        assert_eq!(
+            results.into_iter().map(|v| v.unwrap()).collect::<Vec<_>>(),
+            vec![
+                vec![Response::Success(Modified {
+                    address: "/lights/1/state/on".into(),
+                    value: json!(true),
+                })],
+                vec![Response::Success(Modified {
+                    address: "/groups/2/name".into(),
+                    value: json!("test"),
+                })],
+            ]
+        );
+        let paths: Vec<String> = bridge
+            .take_recorded_requests()
+            .into_iter()
+            .map(|v| v.path)
+            .collect();
+        assert_eq!(paths, vec!["lights/1/state", "groups/2"]);
+
+        let results = bridge.execute_batch(&batch.with_threads(2));
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(Result::is_ok));
+    }
+
+    #[test]
+    fn execute_parallel_in_order_per_resource() {
+        let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "user").with_dry_run();
+        let mut batch = Batch::new()
+            .with_threads(4)
+            .with_pacing(Duration::from_millis(1));
+        for i in 0..10 {
+            let modifier = light::StateModifier::new().with_brightness(Adjust::Override(i));
+            batch.push(((i % 2) + 1).to_string(), &modifier).unwrap();
+        }
+        batch
+            .push(
+                "1".to_owned(),
+                &light::AttributeModifier::new().with_name("last".into()),
+            )
+            .unwrap();
+        assert!(bridge.execute_batch(&batch).iter().all(Result::is_ok));
+
+        let requests = bridge.take_recorded_requests();
+        let bodies = |light: &str| -> Vec<JsonValue> {
+            requests
+                .iter()
+                .filter(|v| v.path.starts_with(&format!("lights/{}", light)))
+                .map(|v| v.body.clone().unwrap())
+                .collect()
+        };
+        assert_eq!(
+            bodies("1"),
+            vec![
+                json!({"bri": 0}),
+                json!({"bri": 2}),
+                json!({"bri": 4}),
+                json!({"bri": 6}),
+                json!({"bri": 8}),
+                json!({"name": "last"}),
+            ]
+        );
+        assert_eq!(
+            bodies("2"),
+            vec![
+                json!({"bri": 1}),
+                json!({"bri": 3}),
+                json!({"bri": 5}),
+                json!({"bri": 7}),
+                json!({"bri": 9}),
+            ]
+        );
+    }
+}
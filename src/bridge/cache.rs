@@ -0,0 +1,151 @@
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Cache for responses of `GET` requests.
+///
+/// Clones of a cache share the same entries. Two caches are equal if they have the same time to
+/// live.
+#[derive(Clone, Debug)]
+pub(crate) struct Cache {
+    entries: Arc<Mutex<HashMap<String, (Instant, JsonValue)>>>,
+    ttl: Duration,
+}
+
+impl Cache {
+    /// Creates a new cache where entries expire after `ttl`.
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(HashMap::new())),
+            ttl,
+        }
+    }
+
+    /// Returns the time to live of entries.
+    pub(crate) fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Returns the cached response for the URL suffix if it has not expired.
+    pub(crate) fn get(&self, url_suffix: &str) -> Option<JsonValue> {
+        let mut entries = self.entries.lock().expect("cache mutex is poisoned");
+        match entries.get(url_suffix) {
+            Some((inserted, value)) if inserted.elapsed() < self.ttl => Some(value.clone()),
+            Some(_) => {
+                entries.remove(url_suffix);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Stores the response for the URL suffix.
+    ///
+    /// Responses that contain errors are not stored.
+    pub(crate) fn insert(&self, url_suffix: &str, value: JsonValue) {
+        if contains_error(&value) {
+            return;
+        }
+        self.entries
+            .lock()
+            .expect("cache mutex is poisoned")
+            .insert(url_suffix.to_owned(), (Instant::now(), value));
+    }
+
+    /// Removes all entries that could be affected by a modification of the URL suffix.
+    pub(crate) fn invalidate(&self, url_suffix: &str) {
+        self.entries
+            .lock()
+            .expect("cache mutex is poisoned")
+            .retain(|cached, _| !is_affected(url_suffix, cached));
+    }
+
+    /// Removes all entries.
+    pub(crate) fn clear(&self) {
+        self.entries
+            .lock()
+            .expect("cache mutex is poisoned")
+            .clear();
+    }
+}
+
+impl PartialEq for Cache {
+    fn eq(&self, other: &Self) -> bool {
+        self.ttl == other.ttl
+    }
+}
+
+impl Eq for Cache {}
+
+impl Hash for Cache {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.ttl.hash(state);
+    }
+}
+
+/// Returns whether a cached response could be affected by a modification of `modified`.
+///
+/// A modification affects all cached responses of the same resource kind and the full state.
+/// Modifying a group also affects the lights because the state of a group is applied to its
+/// lights.
+fn is_affected(modified: &str, cached: &str) -> bool {
+    let kind = |v: &str| v.split('/').next().unwrap_or_default().to_owned();
+    let (modified_kind, cached_kind) = (kind(modified), kind(cached));
+    cached.is_empty()
+        || modified_kind == cached_kind
+        || (modified_kind == "groups" && cached_kind == "lights")
+}
+
+/// Returns whether a response contains an error entry, e.g. `[{"error": {...}}]`.
+fn contains_error(value: &JsonValue) -> bool {
+    value
+        .as_array()
+        .into_iter()
+        .flatten()
+        .any(|v| v.get("error").is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn get_and_expire() {
+        let cache = Cache::new(Duration::from_secs(60));
+        assert_eq!(cache.get("lights"), None);
+        cache.insert("lights", json!({}));
+        assert_eq!(cache.get("lights"), Some(json!({})));
+
+        let cache = Cache::new(Duration::from_secs(0));
+        cache.insert("lights", json!({}));
+        assert_eq!(cache.get("lights"), None);
+    }
+
+    #[test]
+    fn skip_errors() {
+        let cache = Cache::new(Duration::from_secs(60));
+        let error = json!([{"error": {"type": 3, "address": "/lights/1", "description": ""}}]);
+        cache.insert("lights/1", error);
+        assert_eq!(cache.get("lights/1"), None);
+
+        cache.insert("lights", json!([]));
+        assert_eq!(cache.get("lights"), Some(json!([])));
+    }
+
+    #[test]
+    fn invalidate() {
+        let cache = Cache::new(Duration::from_secs(60));
+        for url_suffix in &["", "lights", "lights/1", "groups/1", "sensors"] {
+            cache.insert(url_suffix, json!({}));
+        }
+        cache.invalidate("groups/1/action");
+        assert_eq!(cache.get(""), None);
+        assert_eq!(cache.get("lights"), None);
+        assert_eq!(cache.get("lights/1"), None);
+        assert_eq!(cache.get("groups/1"), None);
+        assert_eq!(cache.get("sensors"), Some(json!({})));
+    }
+}
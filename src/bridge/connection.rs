@@ -0,0 +1,66 @@
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use ureq::{Agent, AgentBuilder};
+
+/// HTTP connections of a bridge.
+///
+/// Clones of a connection share the same agent and thus the same pool of idle connections. Two
+/// connections are equal if they have the same keep-alive setting.
+#[derive(Clone, Debug)]
+pub(crate) struct Connection {
+    agent: Arc<Mutex<Agent>>,
+    keep_alive: bool,
+}
+
+impl Connection {
+    /// Creates a new connection.
+    ///
+    /// If `keep_alive` is false, connections are closed after every request.
+    pub(crate) fn new(keep_alive: bool) -> Self {
+        Self {
+            agent: Arc::new(Mutex::new(build_agent(keep_alive))),
+            keep_alive,
+        }
+    }
+
+    /// Returns whether connections are reused for multiple requests.
+    pub(crate) fn keep_alive(&self) -> bool {
+        self.keep_alive
+    }
+
+    /// Returns the agent that is used to send requests.
+    pub(crate) fn agent(&self) -> Agent {
+        self.agent
+            .lock()
+            .expect("connection mutex is poisoned")
+            .clone()
+    }
+
+    /// Closes all idle connections by replacing the agent.
+    pub(crate) fn close_idle(&self) {
+        *self.agent.lock().expect("connection mutex is poisoned") = build_agent(self.keep_alive);
+    }
+}
+
+impl PartialEq for Connection {
+    fn eq(&self, other: &Self) -> bool {
+        self.keep_alive == other.keep_alive
+    }
+}
+
+impl Eq for Connection {}
+
+impl Hash for Connection {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.keep_alive.hash(state);
+    }
+}
+
+fn build_agent(keep_alive: bool) -> Agent {
+    let builder = AgentBuilder::new();
+    if keep_alive {
+        builder.build()
+    } else {
+        builder.max_idle_connections(0).build()
+    }
+}
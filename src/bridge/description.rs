@@ -30,7 +30,7 @@ impl Description {
     /// This method sends a HTTP GET request to `http://<bridge_ip_address>/description.xml` to get
     /// the descriptor file.
     pub fn get(ip_address: IpAddr) -> crate::Result<Self> {
-        let url = format!("http://{}/description.xml", ip_address);
+        let url = format!("{}/description.xml", super::base_url::from_ip(ip_address));
         let http_response = ureq::get(&url).call()?;
         Ok(serde_xml_rs::from_reader(http_response.into_reader())?)
     }
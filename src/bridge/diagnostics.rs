@@ -0,0 +1,288 @@
+use super::Bridge;
+use crate::resource::RequestMethod;
+use crate::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::{Map, Value as JsonValue};
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use ureq::Error as UreqError;
+
+/// Maximum number of requests that are kept in the request log of a bridge.
+const REQUEST_LOG_SIZE: usize = 50;
+
+/// Information about a bridge that can be attached to bug reports.
+///
+/// The bundle contains the configuration and the capabilities of the bridge as returned by the
+/// bridge, the number of errors and the most recent requests that were sent to the bridge.
+/// Usernames are replaced, because they grant access to the bridge. Request and response bodies
+/// are not included.
+///
+/// See [`Bridge::diagnostics_bundle`] for more information.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DiagnosticsBundle {
+    /// Time when the bundle was created.
+    pub created: DateTime<Utc>,
+    /// Version of this crate.
+    pub crate_version: String,
+    /// Software version of the bridge.
+    pub software_version: Option<String>,
+    /// Version of the Philips Hue API.
+    pub api_version: Option<String>,
+    /// Configuration of the bridge with redacted usernames.
+    pub config: JsonValue,
+    /// Capabilities of the bridge.
+    pub capabilities: JsonValue,
+    /// Number of errors that occurred since the bridge was created.
+    pub errors: ErrorCounters,
+    /// Most recent requests that were sent before the bundle was created, oldest first.
+    pub requests: Vec<LoggedRequest>,
+}
+
+/// Number of errors that occurred while sending requests to a bridge.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Serialize)]
+pub struct ErrorCounters {
+    /// Requests that failed before a response was received or whose response was not valid
+    /// JSON.
+    pub transport: u64,
+    /// Responses with an HTTP status code that indicates an error.
+    pub http_status: u64,
+    /// Errors that were returned by the Philips Hue API in a successful response.
+    pub api: u64,
+}
+
+/// Request that was sent to a bridge, as included in a [`DiagnosticsBundle`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize)]
+pub struct LoggedRequest {
+    /// Time when the request was sent.
+    pub time: DateTime<Utc>,
+    /// HTTP method of the request, e.g. `PUT`.
+    pub method: String,
+    /// Path of the request relative to the API URL of the user, with usernames replaced.
+    pub path: String,
+    /// HTTP status code of the response, if a response was received.
+    pub status: Option<u16>,
+    /// Time until the response was received in milliseconds.
+    pub duration_ms: u64,
+    /// Description of the error, if the request failed.
+    pub error: Option<String>,
+}
+
+/// Counters and log of recent requests of a bridge.
+///
+/// Clones of a log share the same storage. All logs are equal, so the log does not affect the
+/// equality of bridges.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct RequestLog(Arc<Mutex<RequestLogState>>);
+
+#[derive(Debug, Default)]
+struct RequestLogState {
+    errors: ErrorCounters,
+    requests: VecDeque<LoggedRequest>,
+}
+
+impl RequestLog {
+    /// Records a request that was sent to the bridge.
+    ///
+    /// `result` contains the status code and the parsed body of the response, which is `None` if
+    /// the body was not valid JSON.
+    pub(crate) fn record(
+        &self,
+        method: RequestMethod,
+        path: &str,
+        duration: Duration,
+        result: std::result::Result<(u16, Option<&JsonValue>), &UreqError>,
+    ) {
+        let (status, error) = match result {
+            Ok((status, Some(_))) => (Some(status), None),
+            Ok((status, None)) => (Some(status), Some("invalid JSON response".to_owned())),
+            Err(UreqError::Status(status, _)) => {
+                (Some(*status), Some(format!("status code {}", status)))
+            }
+            Err(UreqError::Transport(v)) => (None, Some(v.kind().to_string())),
+        };
+        let api_errors = match result {
+            Ok((_, Some(JsonValue::Array(values)))) => {
+                values.iter().filter(|v| v.get("error").is_some()).count()
+            }
+            _ => 0,
+        };
+        let mut state = self.0.lock().expect("request log mutex is poisoned");
+        match result {
+            Ok((_, None)) | Err(UreqError::Transport(_)) => state.errors.transport += 1,
+            Err(UreqError::Status(..)) => state.errors.http_status += 1,
+            Ok(_) => {}
+        }
+        state.errors.api += api_errors as u64;
+        if state.requests.len() == REQUEST_LOG_SIZE {
+            state.requests.pop_front();
+        }
+        state.requests.push_back(LoggedRequest {
+            time: Utc::now(),
+            method: method_name(method).to_owned(),
+            path: sanitize_path(path),
+            status,
+            duration_ms: u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+            error,
+        });
+    }
+
+    /// Returns the error counters and the logged requests.
+    fn snapshot(&self) -> (ErrorCounters, Vec<LoggedRequest>) {
+        let state = self.0.lock().expect("request log mutex is poisoned");
+        (state.errors, state.requests.iter().cloned().collect())
+    }
+}
+
+impl PartialEq for RequestLog {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for RequestLog {}
+
+impl Hash for RequestLog {
+    fn hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+fn method_name(method: RequestMethod) -> &'static str {
+    match method {
+        RequestMethod::Put => "PUT",
+        RequestMethod::Post => "POST",
+        RequestMethod::Get => "GET",
+        RequestMethod::Delete => "DELETE",
+    }
+}
+
+/// Replaces the username in paths of whitelist entries, e.g. `config/whitelist/<username>`.
+fn sanitize_path(path: &str) -> String {
+    match path.strip_prefix("config/whitelist/") {
+        Some(v) if !v.is_empty() => "config/whitelist/<redacted>".to_owned(),
+        _ => path.to_owned(),
+    }
+}
+
+/// Replaces the usernames in the whitelist of the configuration.
+///
+/// Usernames are replaced with `user<n>`, numbered in the order of the whitelist.
+fn sanitize_config(mut config: JsonValue) -> JsonValue {
+    if let Some(JsonValue::Object(whitelist)) = config.get_mut("whitelist") {
+        *whitelist = std::mem::take(whitelist)
+            .into_iter()
+            .enumerate()
+            .map(|(i, (_, v))| (format!("user{}", i + 1), v))
+            .collect::<Map<_, _>>();
+    }
+    config
+}
+
+pub(super) fn diagnostics_bundle(bridge: &Bridge) -> Result<DiagnosticsBundle> {
+    let (errors, requests) = bridge.request_log.snapshot();
+    let config: JsonValue = bridge.api_request("config", RequestMethod::Get, None)?;
+    let capabilities: JsonValue = bridge.api_request("capabilities", RequestMethod::Get, None)?;
+    let version = |key: &str| config.get(key).and_then(JsonValue::as_str).map(Into::into);
+    Ok(DiagnosticsBundle {
+        created: Utc::now(),
+        crate_version: env!("CARGO_PKG_VERSION").to_owned(),
+        software_version: version("swversion"),
+        api_version: version("apiversion"),
+        config: sanitize_config(config),
+        capabilities,
+        errors,
+        requests,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ErrorCounters;
+    use crate::bridge::test_server::TestServer;
+    use crate::resource::light;
+    use serde_json::json;
+
+    #[test]
+    fn diagnostics_bundle() {
+        let server = TestServer::start(vec![
+            json!([{"success": {"/lights/1/state/on": true}}]),
+            json!([{"error": {"type": 3, "address": "/lights/2", "description": "not available"}}]),
+            json!({"swversion": "1941132080", "apiversion": "1.41.0", "whitelist": {}}),
+            json!({"lights": {"available": 60}}),
+        ]);
+        let bridge = server.bridge();
+        let modifier = light::StateModifier::new().with_on(true);
+        bridge.set_light_state("1", &modifier).unwrap();
+        bridge.set_light_state("2", &modifier).unwrap();
+        let bundle = bridge.diagnostics_bundle().unwrap();
+        server.finish();
+
+        assert_eq!(bundle.software_version.as_deref(), Some("1941132080"));
+        assert_eq!(
+            bundle.errors,
+            ErrorCounters {
+                api: 1,
+                ..Default::default()
+            }
+        );
+        let requests: Vec<_> = bundle
+            .requests
+            .iter()
+            .map(|v| (v.method.as_str(), v.path.as_str(), v.status))
+            .collect();
+        assert_eq!(
+            requests,
+            vec![
+                ("PUT", "lights/1/state", Some(200)),
+                ("PUT", "lights/2/state", Some(200)),
+            ]
+        );
+    }
+
+    #[test]
+    fn sanitize_path() {
+        assert_eq!(
+            super::sanitize_path("config/whitelist/secret"),
+            "config/whitelist/<redacted>"
+        );
+        assert_eq!(super::sanitize_path("lights/1/state"), "lights/1/state");
+    }
+
+    #[test]
+    fn sanitize_config() {
+        let config = json!({
+            "name": "Philips hue",
+            "whitelist": {
+                "secret1": {
+                    "last use date": "2020-01-01T12:00:00",
+                    "create date": "2019-01-01T12:00:00",
+                    "name": "app#device"
+                },
+                "secret2": {
+                    "last use date": "2020-01-01T12:00:00",
+                    "create date": "2019-01-01T12:00:00",
+                    "name": "other#device"
+                }
+            }
+        });
+        let expected = json!({
+            "name": "Philips hue",
+            "whitelist": {
+                "user1": {
+                    "last use date": "2020-01-01T12:00:00",
+                    "create date": "2019-01-01T12:00:00",
+                    "name": "app#device"
+                },
+                "user2": {
+                    "last use date": "2020-01-01T12:00:00",
+                    "create date": "2019-01-01T12:00:00",
+                    "name": "other#device"
+                }
+            }
+        });
+        assert_eq!(super::sanitize_config(config), expected);
+        assert_eq!(super::sanitize_config(json!({})), json!({}));
+    }
+}
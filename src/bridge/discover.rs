@@ -1,6 +1,14 @@
-use crate::Result;
-use serde::Deserialize;
-use std::net::IpAddr;
+use crate::{Error, Result};
+use chrono::{DateTime, Utc};
+use derive_setters::Setters;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 /// Discovers bridges in the local netowork using N-UPnP.
 ///
@@ -31,16 +39,763 @@ use std::net::IpAddr;
 /// # }
 /// ```
 pub fn discover_nupnp() -> Result<Vec<IpAddr>> {
-    let http_response = ureq::get("https://discovery.meethue.com").call()?;
+    Ok(discover_nupnp_with_options(&DiscoveryOptions::default())?.ip_addresses)
+}
+
+/// URL of the official N-UPnP discovery endpoint.
+const DEFAULT_DISCOVERY_URL: &str = "https://discovery.meethue.com";
+
+/// Default timeout of a request to a N-UPnP discovery endpoint.
+const DEFAULT_DISCOVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Options for discovering bridges with [`discover_nupnp_with_options`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Setters)]
+#[setters(prefix = "with_")]
+pub struct DiscoveryOptions {
+    /// URLs of the discovery endpoints that are tried in order. Defaults to
+    /// `https://discovery.meethue.com`.
+    ///
+    /// Custom endpoints have to return the same JSON format as the official endpoint.
+    pub urls: Vec<String>,
+    /// Timeout of a request to a discovery endpoint. Defaults to 10 seconds.
+    pub timeout: Duration,
+}
+
+impl DiscoveryOptions {
+    /// Creates new [`DiscoveryOptions`] that use the official discovery endpoint.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Default for DiscoveryOptions {
+    fn default() -> Self {
+        Self {
+            urls: vec![DEFAULT_DISCOVERY_URL.to_owned()],
+            timeout: DEFAULT_DISCOVERY_TIMEOUT,
+        }
+    }
+}
+
+/// Bridges that were discovered with [`discover_nupnp_with_options`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Discovered {
+    /// URL of the discovery endpoint that returned the bridges.
+    pub url: String,
+    /// IP addresses of the discovered bridges.
+    pub ip_addresses: Vec<IpAddr>,
+}
+
+/// Discovers bridges in the local network using N-UPnP with custom discovery endpoints.
+///
+/// The endpoints are tried in order until one of them responds. This is useful if the official
+/// endpoint is not reachable or to use a self-hosted endpoint, e.g. of a diyHue installation. If
+/// no endpoint responds, the error of the last endpoint is returned.
+///
+/// # Examples
+///
+/// Use a local endpoint and fall back to the official endpoint:
+/// ```no_run
+/// use huelib2::bridge::{self, DiscoveryOptions};
+///
+/// # fn main() -> Result<(), huelib2::Error> {
+/// let options = DiscoveryOptions::new().with_urls(vec![
+///     "http://192.168.1.10/discovery".into(),
+///     "https://discovery.meethue.com".into(),
+/// ]);
+/// let discovered = bridge::discover_nupnp_with_options(&options)?;
+/// println!("Found {:?} using {}", discovered.ip_addresses, discovered.url);
+/// # Ok(())
+/// # }
+/// ```
+pub fn discover_nupnp_with_options(options: &DiscoveryOptions) -> Result<Discovered> {
+    discover_nupnp_bridges_with_options(options).map(|(url, bridges)| Discovered {
+        url: url.to_owned(),
+        ip_addresses: bridges.into_values().collect(),
+    })
+}
+
+/// Discovers bridges using the first N-UPnP endpoint of the options that responds.
+///
+/// Returns the URL of the endpoint together with the IP addresses keyed by bridge identifier.
+fn discover_nupnp_bridges_with_options(
+    options: &DiscoveryOptions,
+) -> Result<(&str, HashMap<String, IpAddr>)> {
+    first_endpoint(&options.urls, |url| {
+        discover_nupnp_bridges(url, options.timeout)
+    })
+}
+
+/// Calls `f` with every URL until it succeeds and returns the URL together with the result.
+fn first_endpoint<T, F>(urls: &[String], mut f: F) -> Result<(&str, T)>
+where
+    F: FnMut(&str) -> Result<T>,
+{
+    let mut last_error = None;
+    for url in urls {
+        match f(url) {
+            Ok(v) => return Ok((url, v)),
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(last_error.unwrap_or(Error::NoDiscoveryEndpoint))
+}
+
+/// Discovers bridges using N-UPnP and returns their IP addresses keyed by bridge identifier.
+///
+/// The request is aborted when the timeout elapsed.
+fn discover_nupnp_bridges(url: &str, timeout: Duration) -> Result<HashMap<String, IpAddr>> {
+    let agent = ureq::AgentBuilder::new().timeout(timeout).build();
+    let http_response = agent.get(url).call()?;
     #[derive(Deserialize)]
     struct BridgeJson {
+        id: String,
         #[serde(rename = "internalipaddress")]
         ip_address: String,
     }
     let bridges: Vec<BridgeJson> = http_response.into_json()?;
-    let mut ip_addresses = Vec::<IpAddr>::new();
+    let mut ip_addresses = HashMap::new();
     for b in bridges {
-        ip_addresses.push(b.ip_address.parse()?);
+        ip_addresses.insert(b.id, b.ip_address.parse()?);
     }
     Ok(ip_addresses)
 }
+
+/// Address of the SSDP multicast group.
+const SSDP_ADDRESS: (Ipv4Addr, u16) = (Ipv4Addr::new(239, 255, 255, 250), 1900);
+
+/// Maximum time to wait for a SSDP response before checking whether the search was cancelled.
+const SSDP_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Search request that is sent to the SSDP multicast group.
+const SSDP_SEARCH: &str = "M-SEARCH * HTTP/1.1\r\n\
+HOST: 239.255.255.250:1900\r\n\
+MAN: \"ssdp:discover\"\r\n\
+MX: 2\r\n\
+ST: ssdp:all\r\n\r\n";
+
+/// Discovers bridges in the local network using SSDP and returns their IP addresses keyed by
+/// bridge identifier.
+///
+/// Responses are collected until the timeout elapsed.
+fn discover_ssdp_bridges(timeout: Duration) -> Result<HashMap<String, IpAddr>> {
+    let mut bridges = HashMap::new();
+    search_ssdp(
+        SSDP_ADDRESS.into(),
+        Instant::now() + timeout,
+        || false,
+        |id, ip_address| {
+            bridges.insert(id, ip_address);
+            true
+        },
+    )?;
+    Ok(bridges)
+}
+
+/// Sends a SSDP search to the address and calls `f` for every response of a bridge until the
+/// deadline.
+///
+/// The search is stopped early if `f` returns `false` or `cancelled` returns `true`.
+fn search_ssdp<C, F>(address: SocketAddr, deadline: Instant, cancelled: C, mut f: F) -> Result<()>
+where
+    C: Fn() -> bool,
+    F: FnMut(String, IpAddr) -> bool,
+{
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).map_err(Error::Ssdp)?;
+    socket
+        .send_to(SSDP_SEARCH.as_bytes(), address)
+        .map_err(Error::Ssdp)?;
+    let mut buffer = [0; 2048];
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() || cancelled() {
+            return Ok(());
+        }
+        socket
+            .set_read_timeout(Some(remaining.min(SSDP_POLL_INTERVAL)))
+            .map_err(Error::Ssdp)?;
+        let (len, address): (usize, SocketAddr) = match socket.recv_from(&mut buffer) {
+            Ok(v) => v,
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                ) =>
+            {
+                continue
+            }
+            Err(e) => return Err(Error::Ssdp(e)),
+        };
+        let response = String::from_utf8_lossy(&buffer[..len]);
+        if let Some(id) = parse_ssdp_bridge_id(&response) {
+            if !f(id, address.ip()) {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Returns the bridge identifier of a SSDP response, if it was sent by a bridge.
+fn parse_ssdp_bridge_id(response: &str) -> Option<String> {
+    response.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        match name.trim().eq_ignore_ascii_case("hue-bridgeid") {
+            true => Some(value.trim().to_lowercase()),
+            false => None,
+        }
+    })
+}
+
+/// Method that was used to discover a bridge.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum DiscoverySource {
+    /// The bridge was returned by the N-UPnP discovery endpoint.
+    Nupnp,
+    /// The bridge responded to a SSDP search in the local network.
+    Ssdp,
+}
+
+/// Bridge that was discovered with [`discover_all`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct DiscoveredBridge {
+    /// Identifier of the bridge in lowercase.
+    pub id: String,
+    /// IP address of the bridge.
+    pub ip_address: IpAddr,
+    /// Methods that discovered the bridge.
+    pub sources: Vec<DiscoverySource>,
+}
+
+/// Discovers bridges using all available methods.
+///
+/// N-UPnP and SSDP are run concurrently, SSDP responses are collected until the timeout elapsed.
+/// The N-UPnP endpoints are tried as described in [`discover_nupnp_with_options`]. Bridges that
+/// were found by both methods are merged by their identifier; the IP address from the local
+/// network (SSDP) is preferred. An error is only returned if all methods failed.
+///
+/// mDNS discovery is not supported.
+///
+/// # Examples
+///
+/// ```no_run
+/// use huelib2::bridge::{self, DiscoveryOptions};
+/// use std::time::Duration;
+///
+/// # fn main() -> Result<(), huelib2::Error> {
+/// let options = DiscoveryOptions::new();
+/// for bridge in bridge::discover_all(Duration::from_secs(3), &options)? {
+///     println!("{} at {} ({:?})", bridge.id, bridge.ip_address, bridge.sources);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn discover_all(
+    timeout: Duration,
+    options: &DiscoveryOptions,
+) -> Result<Vec<DiscoveredBridge>> {
+    let nupnp = {
+        let options = options.clone();
+        std::thread::spawn(move || {
+            discover_nupnp_bridges_with_options(&options).map(|(_, bridges)| bridges)
+        })
+    };
+    let ssdp = discover_ssdp_bridges(timeout);
+    let nupnp = nupnp.join().expect("discovery thread panicked");
+    merge_discovered(vec![
+        (DiscoverySource::Nupnp, nupnp),
+        (DiscoverySource::Ssdp, ssdp),
+    ])
+}
+
+/// Discovers bridges in a background thread and returns them as soon as they are found.
+///
+/// This runs the same methods as [`discover_all`] without blocking the calling thread. Every
+/// bridge is sent once through the returned channel, together with the method that found it
+/// first. The channel is closed when all methods finished, which happens at the latest when the
+/// timeout elapsed. Errors of the discovery methods are ignored.
+///
+/// Discovery is cancelled by dropping the receiver. The receiver being dropped is noticed when the
+/// next bridge is sent; both methods then stop before their next step, i.e. before trying the
+/// next N-UPnP endpoint or waiting for the next SSDP response.
+///
+/// # Examples
+///
+/// ```no_run
+/// use huelib2::bridge::{self, DiscoveryOptions};
+/// use std::time::Duration;
+///
+/// let receiver = bridge::discover_in_background(Duration::from_secs(3), &DiscoveryOptions::new());
+/// for bridge in receiver {
+///     println!("Found {} at {}", bridge.id, bridge.ip_address);
+/// }
+/// ```
+pub fn discover_in_background(
+    timeout: Duration,
+    options: &DiscoveryOptions,
+) -> Receiver<DiscoveredBridge> {
+    discover_in_background_at(timeout, options, SSDP_ADDRESS.into())
+}
+
+/// Discovers bridges in a background thread, sending the SSDP search to the given address.
+fn discover_in_background_at(
+    timeout: Duration,
+    options: &DiscoveryOptions,
+    ssdp_address: SocketAddr,
+) -> Receiver<DiscoveredBridge> {
+    let (sender, receiver) = mpsc::channel();
+    let deadline = Instant::now() + timeout;
+    let seen = Arc::new(Mutex::new(HashSet::new()));
+    let cancelled = Arc::new(AtomicBool::new(false));
+    // Sends a bridge if it was not sent before and returns whether discovery should continue.
+    let send = {
+        let cancelled = Arc::clone(&cancelled);
+        move |source: DiscoverySource, id: String, ip_address: IpAddr| {
+            let id = id.to_lowercase();
+            if !seen.lock().expect("mutex is poisoned").insert(id.clone()) {
+                return !cancelled.load(Ordering::Relaxed);
+            }
+            let bridge = DiscoveredBridge {
+                id,
+                ip_address,
+                sources: vec![source],
+            };
+            if sender.send(bridge).is_err() {
+                cancelled.store(true, Ordering::Relaxed);
+            }
+            !cancelled.load(Ordering::Relaxed)
+        }
+    };
+    let send_nupnp = send.clone();
+    let options = options.clone();
+    let cancelled_nupnp = Arc::clone(&cancelled);
+    std::thread::spawn(move || {
+        for url in &options.urls {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() || cancelled_nupnp.load(Ordering::Relaxed) {
+                return;
+            }
+            if let Ok(bridges) = discover_nupnp_bridges(url, remaining.min(options.timeout)) {
+                for (id, ip_address) in bridges {
+                    if !send_nupnp(DiscoverySource::Nupnp, id, ip_address) {
+                        return;
+                    }
+                }
+                return;
+            }
+        }
+    });
+    std::thread::spawn(move || {
+        let _ = search_ssdp(
+            ssdp_address,
+            deadline,
+            || cancelled.load(Ordering::Relaxed),
+            |id, ip_address| send(DiscoverySource::Ssdp, id, ip_address),
+        );
+    });
+    receiver
+}
+
+/// Merges the results of the discovery methods by bridge identifier.
+///
+/// Later sources override the IP address of earlier sources.
+fn merge_discovered(
+    results: Vec<(DiscoverySource, Result<HashMap<String, IpAddr>>)>,
+) -> Result<Vec<DiscoveredBridge>> {
+    let mut bridges: Vec<DiscoveredBridge> = Vec::new();
+    let mut first_error = None;
+    let mut succeeded = false;
+    for (source, result) in results {
+        let discovered = match result {
+            Ok(v) => v,
+            Err(e) => {
+                first_error.get_or_insert(e);
+                continue;
+            }
+        };
+        succeeded = true;
+        for (id, ip_address) in discovered {
+            let id = id.to_lowercase();
+            match bridges.iter_mut().find(|v| v.id == id) {
+                Some(bridge) => {
+                    bridge.ip_address = ip_address;
+                    bridge.sources.push(source);
+                }
+                None => bridges.push(DiscoveredBridge {
+                    id,
+                    ip_address,
+                    sources: vec![source],
+                }),
+            }
+        }
+    }
+    match (succeeded, first_error) {
+        (false, Some(e)) => Err(e),
+        _ => {
+            bridges.sort_by(|a, b| a.id.cmp(&b.id));
+            Ok(bridges)
+        }
+    }
+}
+
+/// On-disk cache for the results of [`discover_nupnp`].
+///
+/// The cache maps the identifier of a bridge to its last known IP address. Entries expire after
+/// the time to live. The cache is stored as JSON file. Bridges are discovered with the
+/// [`DiscoveryOptions`] of the cache, see [`with_options`](Self::with_options).
+///
+/// # Examples
+///
+/// Discover bridges at most once per day:
+/// ```no_run
+/// use huelib2::bridge::DiscoveryCache;
+/// use std::time::Duration;
+///
+/// # fn main() -> Result<(), huelib2::Error> {
+/// let cache = DiscoveryCache::new("bridges.json", Duration::from_secs(24 * 60 * 60));
+/// let ip_addresses = cache.discover()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct DiscoveryCache {
+    /// Path of the cache file.
+    path: PathBuf,
+    /// Time to live of entries.
+    ttl: Duration,
+    /// Options that are used to discover bridges.
+    options: DiscoveryOptions,
+}
+
+/// Entry of the discovery cache.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+struct DiscoveryCacheEntry {
+    ip_address: IpAddr,
+    updated: DateTime<Utc>,
+}
+
+impl DiscoveryCache {
+    /// Creates a new discovery cache that is stored at the given path.
+    ///
+    /// The file is created when discovery is run for the first time.
+    pub fn new<P>(path: P, ttl: Duration) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self {
+            path: path.into(),
+            ttl,
+            options: DiscoveryOptions::default(),
+        }
+    }
+
+    /// Sets the options that are used to discover bridges.
+    ///
+    /// Defaults to [`DiscoveryOptions::default`].
+    pub fn with_options(mut self, options: DiscoveryOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Returns the path of the cache file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the time to live of entries.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Returns the options that are used to discover bridges.
+    pub fn options(&self) -> &DiscoveryOptions {
+        &self.options
+    }
+
+    /// Returns the IP addresses of bridges in the local network.
+    ///
+    /// The cached IP addresses are returned if the cache contains entries that have not expired.
+    /// Otherwise the bridges are discovered using [`discover_nupnp_with_options`] and the cache is
+    /// updated.
+    pub fn discover(&self) -> Result<Vec<IpAddr>> {
+        let entries = self.valid_entries(self.load(), Utc::now());
+        if !entries.is_empty() {
+            return Ok(entries.into_values().map(|v| v.ip_address).collect());
+        }
+        Ok(self.refresh()?.into_values().collect())
+    }
+
+    /// Returns the IP address of the bridge with the given identifier.
+    ///
+    /// The cached IP address is returned if it has not expired. Otherwise the bridges are
+    /// discovered using [`discover_nupnp_with_options`] and the cache is updated. Returns `None` if
+    /// the bridge was not discovered.
+    pub fn get<S>(&self, bridge_id: S) -> Result<Option<IpAddr>>
+    where
+        S: AsRef<str>,
+    {
+        let bridge_id = bridge_id.as_ref();
+        let entries = self.valid_entries(self.load(), Utc::now());
+        if let Some(v) = entries.get(bridge_id) {
+            return Ok(Some(v.ip_address));
+        }
+        Ok(self.refresh()?.get(bridge_id).copied())
+    }
+
+    /// Discovers bridges using [`discover_nupnp_with_options`] regardless of cached entries and
+    /// updates the cache.
+    ///
+    /// This is useful if a cached IP address is no longer valid. Returns the IP addresses of the
+    /// discovered bridges keyed by bridge identifier.
+    pub fn refresh(&self) -> Result<HashMap<String, IpAddr>> {
+        let (_, bridges) = discover_nupnp_bridges_with_options(&self.options)?;
+        self.update(&bridges, Utc::now())?;
+        Ok(bridges)
+    }
+
+    /// Removes the cache file.
+    pub fn clear(&self) -> Result<()> {
+        match std::fs::remove_file(&self.path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(Error::DiscoveryCache(e)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Reads the entries from the cache file.
+    ///
+    /// A missing or invalid file is treated as an empty cache.
+    fn load(&self) -> HashMap<String, DiscoveryCacheEntry> {
+        std::fs::read(&self.path)
+            .ok()
+            .and_then(|v| serde_json::from_slice(&v).ok())
+            .unwrap_or_default()
+    }
+
+    /// Adds the discovered bridges to the cache file.
+    fn update(&self, bridges: &HashMap<String, IpAddr>, now: DateTime<Utc>) -> Result<()> {
+        let mut entries = self.load();
+        for (id, ip_address) in bridges {
+            let entry = DiscoveryCacheEntry {
+                ip_address: *ip_address,
+                updated: now,
+            };
+            entries.insert(id.clone(), entry);
+        }
+        let entries = self.valid_entries(entries, now);
+        std::fs::write(&self.path, serde_json::to_vec(&entries)?).map_err(Error::DiscoveryCache)
+    }
+
+    /// Returns the entries that have not expired.
+    fn valid_entries(
+        &self,
+        mut entries: HashMap<String, DiscoveryCacheEntry>,
+        now: DateTime<Utc>,
+    ) -> HashMap<String, DiscoveryCacheEntry> {
+        entries.retain(|_, v| {
+            (now - v.updated)
+                .to_std()
+                .is_ok_and(|elapsed| elapsed < self.ttl)
+        });
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ssdp_bridge_id() {
+        let response = "HTTP/1.1 200 OK\r\n\
+            LOCATION: http://192.168.1.2:80/description.xml\r\n\
+            hue-bridgeid: 001788FFFE000000\r\n\r\n";
+        assert_eq!(
+            super::parse_ssdp_bridge_id(response),
+            Some("001788fffe000000".to_owned())
+        );
+        assert_eq!(super::parse_ssdp_bridge_id("HTTP/1.1 200 OK\r\n"), None);
+    }
+
+    #[test]
+    fn merge_discovered() {
+        let ip_1 = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2));
+        let ip_2 = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 3));
+        let nupnp = vec![("001788FFFE000000".to_owned(), ip_1)]
+            .into_iter()
+            .collect();
+        let ssdp = vec![
+            ("001788fffe000000".to_owned(), ip_2),
+            ("001788fffe000001".to_owned(), ip_1),
+        ]
+        .into_iter()
+        .collect();
+        let bridges = super::merge_discovered(vec![
+            (DiscoverySource::Nupnp, Ok(nupnp)),
+            (DiscoverySource::Ssdp, Ok(ssdp)),
+        ])
+        .unwrap();
+        assert_eq!(
+            bridges,
+            vec![
+                DiscoveredBridge {
+                    id: "001788fffe000000".into(),
+                    ip_address: ip_2,
+                    sources: vec![DiscoverySource::Nupnp, DiscoverySource::Ssdp],
+                },
+                DiscoveredBridge {
+                    id: "001788fffe000001".into(),
+                    ip_address: ip_1,
+                    sources: vec![DiscoverySource::Ssdp],
+                },
+            ]
+        );
+
+        let bridges = super::merge_discovered(vec![
+            (DiscoverySource::Nupnp, Err(Error::GetUsername)),
+            (DiscoverySource::Ssdp, Ok(HashMap::new())),
+        ]);
+        assert_eq!(bridges.unwrap(), vec![]);
+        let bridges =
+            super::merge_discovered(vec![(DiscoverySource::Nupnp, Err(Error::GetUsername))]);
+        assert!(matches!(bridges, Err(Error::GetUsername)));
+    }
+
+    /// Binds a SSDP responder on the loopback interface that answers one search with the bridge
+    /// identifier, or does not answer if the identifier is `None`.
+    fn ssdp_responder(bridge_id: Option<&'static str>) -> SocketAddr {
+        let socket = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let address = socket.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let mut buffer = [0; 2048];
+            let (_, client) = socket.recv_from(&mut buffer).unwrap();
+            if let Some(id) = bridge_id {
+                let response = format!("HTTP/1.1 200 OK\r\nhue-bridgeid: {}\r\n\r\n", id);
+                socket.send_to(response.as_bytes(), client).unwrap();
+            }
+        });
+        address
+    }
+
+    #[test]
+    fn discover_in_background() {
+        let server = crate::bridge::test_server::TestServer::start(vec![serde_json::json!([
+            {"id": "001788FFFE000000", "internalipaddress": "192.168.1.2"}
+        ])]);
+        let options = DiscoveryOptions::new().with_urls(vec![server.bridge().base_url().into()]);
+        let ssdp_address = ssdp_responder(Some("001788FFFE000001"));
+        let receiver =
+            super::discover_in_background_at(Duration::from_millis(500), &options, ssdp_address);
+        let mut bridges: Vec<_> = receiver.iter().collect();
+        bridges.sort_by(|a, b| a.id.cmp(&b.id));
+        assert_eq!(
+            bridges,
+            vec![
+                DiscoveredBridge {
+                    id: "001788fffe000000".into(),
+                    ip_address: IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)),
+                    sources: vec![DiscoverySource::Nupnp],
+                },
+                DiscoveredBridge {
+                    id: "001788fffe000001".into(),
+                    ip_address: IpAddr::V4(Ipv4Addr::LOCALHOST),
+                    sources: vec![DiscoverySource::Ssdp],
+                },
+            ]
+        );
+        server.finish();
+    }
+
+    #[test]
+    fn discover_in_background_deadline() {
+        let start = Instant::now();
+        let options = DiscoveryOptions::new().with_urls(Vec::new());
+        let receiver =
+            super::discover_in_background_at(Duration::ZERO, &options, ssdp_responder(None));
+        assert_eq!(receiver.iter().count(), 0);
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn first_endpoint() {
+        let urls = vec!["a".to_owned(), "b".to_owned(), "c".to_owned()];
+        let result = super::first_endpoint(&urls, |url| match url {
+            "a" => Err(Error::GetUsername),
+            v => Ok(v.to_uppercase()),
+        });
+        assert_eq!(result.unwrap(), ("b", "B".to_owned()));
+
+        let result = super::first_endpoint(&urls, |_| Err::<(), _>(Error::GetUsername));
+        assert!(matches!(result, Err(Error::GetUsername)));
+
+        let result = super::first_endpoint(&[], |_| Ok(()));
+        assert!(matches!(result, Err(Error::NoDiscoveryEndpoint)));
+    }
+
+    #[test]
+    fn discover_nupnp_with_options() {
+        let server = crate::bridge::test_server::TestServer::start(vec![serde_json::json!([
+            {"id": "001788FFFE000000", "internalipaddress": "192.168.1.2"}
+        ])]);
+        let url = format!("{}/discovery", server.bridge().base_url());
+        let options = DiscoveryOptions::new()
+            .with_urls(vec![url.clone()])
+            .with_timeout(Duration::from_secs(5));
+        let discovered = super::discover_nupnp_with_options(&options).unwrap();
+        assert_eq!(discovered.url, url);
+        assert_eq!(
+            discovered.ip_addresses,
+            vec![IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2))]
+        );
+        server.finish();
+    }
+
+    #[test]
+    fn discovery_cache() {
+        let path =
+            std::env::temp_dir().join(format!("huelib2-discovery-{}.json", std::process::id()));
+        let cache = DiscoveryCache::new(&path, Duration::from_secs(60));
+        cache.clear().unwrap();
+        assert!(cache.load().is_empty());
+
+        let ip_address = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2));
+        let bridges = vec![("001788fffe000000".to_owned(), ip_address)]
+            .into_iter()
+            .collect();
+        let now = Utc::now();
+        cache.update(&bridges, now).unwrap();
+        let entries = cache.load();
+        assert_eq!(
+            entries.get("001788fffe000000"),
+            Some(&DiscoveryCacheEntry {
+                ip_address,
+                updated: now
+            })
+        );
+        assert_eq!(cache.valid_entries(entries.clone(), now).len(), 1);
+        let later = now + chrono::Duration::seconds(61);
+        assert!(cache.valid_entries(entries, later).is_empty());
+
+        cache.clear().unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn discovery_cache_options() {
+        let server = crate::bridge::test_server::TestServer::start(vec![serde_json::json!([
+            {"id": "001788fffe000000", "internalipaddress": "192.168.1.2"}
+        ])]);
+        let path = std::env::temp_dir().join(format!(
+            "huelib2-discovery-options-{}.json",
+            std::process::id()
+        ));
+        let options = DiscoveryOptions::new().with_urls(vec![server.bridge().base_url().into()]);
+        let cache =
+            DiscoveryCache::new(&path, Duration::from_secs(60)).with_options(options.clone());
+        assert_eq!(cache.options(), &options);
+        cache.clear().unwrap();
+        let ip_address = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2));
+        assert_eq!(cache.get("001788fffe000000").unwrap(), Some(ip_address));
+        assert_eq!(cache.discover().unwrap(), vec![ip_address]);
+        assert_eq!(server.finish().len(), 1);
+        cache.clear().unwrap();
+    }
+}
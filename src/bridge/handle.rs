@@ -0,0 +1,264 @@
+use super::{Bridge, ResponsesModified};
+use crate::resource::{self, group, light, sensor};
+use crate::Result;
+use std::time::Duration;
+
+/// Handle for a single light of a bridge.
+///
+/// This is obtained with [`Bridge::light`] and forwards to the light methods of the bridge.
+#[derive(Clone, Copy, Debug)]
+pub struct LightHandle<'a> {
+    bridge: &'a Bridge,
+    id: &'a str,
+}
+
+impl<'a> LightHandle<'a> {
+    pub(super) fn new(bridge: &'a Bridge, id: &'a str) -> Self {
+        Self { bridge, id }
+    }
+
+    /// Returns the identifier of the light.
+    pub fn id(&self) -> &str {
+        self.id
+    }
+
+    /// Returns the light.
+    pub fn get(&self) -> Result<resource::Light> {
+        self.bridge.get_light(self.id)
+    }
+
+    /// Modifies the state of the light.
+    pub fn set_state(&self, modifier: &light::StateModifier) -> Result<ResponsesModified> {
+        self.bridge.set_light_state(self.id, modifier)
+    }
+
+    /// Lets the light perform one breathe cycle, so it can be identified.
+    pub fn identify(&self) -> Result<ResponsesModified> {
+        self.bridge.identify_light(self.id)
+    }
+
+    /// Modifies attributes of the light.
+    pub fn set_attribute(&self, modifier: &light::AttributeModifier) -> Result<ResponsesModified> {
+        self.bridge.set_light_attribute(self.id, modifier)
+    }
+
+    /// Modifies the configuration of the light.
+    pub fn set_config(&self, modifier: &light::ConfigModifier) -> Result<ResponsesModified> {
+        self.bridge.set_light_config(self.id, modifier)
+    }
+
+    /// Changes the name of the light.
+    pub fn rename<S>(&self, name: S) -> Result<ResponsesModified>
+    where
+        S: Into<String>,
+    {
+        self.set_attribute(&light::AttributeModifier::new().with_name(name.into()))
+    }
+
+    /// Deletes the light.
+    pub fn delete(&self) -> Result<()> {
+        self.bridge.delete_light(self.id)
+    }
+}
+
+/// Handle for a single group of a bridge.
+///
+/// This is obtained with [`Bridge::group`] and forwards to the group methods of the bridge.
+#[derive(Clone, Copy, Debug)]
+pub struct GroupHandle<'a> {
+    bridge: &'a Bridge,
+    id: &'a str,
+}
+
+impl<'a> GroupHandle<'a> {
+    pub(super) fn new(bridge: &'a Bridge, id: &'a str) -> Self {
+        Self { bridge, id }
+    }
+
+    /// Returns the identifier of the group.
+    pub fn id(&self) -> &str {
+        self.id
+    }
+
+    /// Returns the group.
+    pub fn get(&self) -> Result<resource::Group> {
+        self.bridge.get_group(self.id)
+    }
+
+    /// Modifies the state of the group.
+    pub fn set_state(&self, modifier: &group::StateModifier) -> Result<ResponsesModified> {
+        self.bridge.set_group_state(self.id, modifier)
+    }
+
+    /// Lets all lights of the group perform one breathe cycle, so the group can be identified.
+    pub fn identify(&self) -> Result<ResponsesModified> {
+        self.bridge.identify_group(self.id)
+    }
+
+    /// Recalls a scene on the group.
+    ///
+    /// See [`Bridge::recall_scene`] for more information.
+    pub fn recall_scene<S>(
+        &self,
+        scene_id: S,
+        transition: Option<Duration>,
+    ) -> Result<ResponsesModified>
+    where
+        S: Into<String>,
+    {
+        self.bridge.recall_scene(self.id, scene_id, transition)
+    }
+
+    /// Modifies attributes of the group.
+    pub fn set_attribute(&self, modifier: &group::AttributeModifier) -> Result<ResponsesModified> {
+        self.bridge.set_group_attribute(self.id, modifier)
+    }
+
+    /// Changes the name of the group.
+    pub fn rename<S>(&self, name: S) -> Result<ResponsesModified>
+    where
+        S: Into<String>,
+    {
+        self.set_attribute(&group::AttributeModifier::new().with_name(name.into()))
+    }
+
+    /// Deletes the group.
+    pub fn delete(&self) -> Result<()> {
+        self.bridge.delete_group(self.id)
+    }
+}
+
+/// Handle for a single sensor of a bridge.
+///
+/// This is obtained with [`Bridge::sensor`] and forwards to the sensor methods of the bridge.
+#[derive(Clone, Copy, Debug)]
+pub struct SensorHandle<'a> {
+    bridge: &'a Bridge,
+    id: &'a str,
+}
+
+impl<'a> SensorHandle<'a> {
+    pub(super) fn new(bridge: &'a Bridge, id: &'a str) -> Self {
+        Self { bridge, id }
+    }
+
+    /// Returns the identifier of the sensor.
+    pub fn id(&self) -> &str {
+        self.id
+    }
+
+    /// Returns the sensor.
+    pub fn get(&self) -> Result<resource::Sensor> {
+        self.bridge.get_sensor(self.id)
+    }
+
+    /// Modifies the state of the sensor.
+    pub fn set_state(&self, modifier: &sensor::StateModifier) -> Result<ResponsesModified> {
+        self.bridge.set_sensor_state(self.id, modifier)
+    }
+
+    /// Modifies attributes of the sensor.
+    pub fn set_attribute(&self, modifier: &sensor::AttributeModifier) -> Result<ResponsesModified> {
+        self.bridge.set_sensor_attribute(self.id, modifier)
+    }
+
+    /// Modifies the configuration of the sensor.
+    pub fn set_config<M>(&self, modifier: &M) -> Result<ResponsesModified>
+    where
+        M: sensor::SensorConfigModifier,
+    {
+        self.bridge.set_sensor_config(self.id, modifier)
+    }
+
+    /// Changes the name of the sensor.
+    pub fn rename<S>(&self, name: S) -> Result<ResponsesModified>
+    where
+        S: Into<String>,
+    {
+        self.set_attribute(&sensor::AttributeModifier::new().with_name(name.into()))
+    }
+
+    /// Deletes the sensor.
+    pub fn delete(&self) -> Result<()> {
+        self.bridge.delete_sensor(self.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::resource::{light, RequestMethod};
+    use crate::Bridge;
+    use serde_json::json;
+    use std::net::{IpAddr, Ipv4Addr};
+    use std::time::Duration;
+
+    #[test]
+    fn light_handle() {
+        let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "user").with_dry_run();
+        let desk = bridge.light("3");
+        assert_eq!(desk.id(), "3");
+        desk.set_state(&light::StateModifier::new().with_on(true))
+            .unwrap();
+        desk.identify().unwrap();
+        desk.rename("Desk").unwrap();
+        desk.delete().unwrap();
+
+        let requests = bridge.take_recorded_requests();
+        let requests: Vec<_> = requests
+            .iter()
+            .map(|v| (v.method, v.path.as_str(), v.body.clone()))
+            .collect();
+        assert_eq!(
+            requests,
+            vec![
+                (
+                    RequestMethod::Put,
+                    "lights/3/state",
+                    Some(json!({"on": true}))
+                ),
+                (
+                    RequestMethod::Put,
+                    "lights/3/state",
+                    Some(json!({"alert": "select"}))
+                ),
+                (
+                    RequestMethod::Put,
+                    "lights/3",
+                    Some(json!({"name": "Desk"}))
+                ),
+                (RequestMethod::Delete, "lights/3", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn group_handle() {
+        let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "user").with_dry_run();
+        let kitchen = bridge.group("2");
+        kitchen.recall_scene("abc", None).unwrap();
+        kitchen
+            .recall_scene("abc", Some(Duration::from_millis(1500)))
+            .unwrap();
+
+        let requests = bridge.take_recorded_requests();
+        let requests: Vec<_> = requests
+            .iter()
+            .map(|v| (v.method, v.path.as_str(), v.body.clone()))
+            .collect();
+        assert_eq!(
+            requests,
+            vec![
+                (
+                    RequestMethod::Put,
+                    "groups/2/action",
+                    Some(json!({"scene": "abc"}))
+                ),
+                (
+                    RequestMethod::Put,
+                    "groups/2/action",
+                    Some(json!({"scene": "abc", "transitiontime": 15}))
+                ),
+            ]
+        );
+    }
+}
@@ -1,20 +1,52 @@
 use crate::resource::{self, Creator, Modifier, RequestMethod, Scanner};
-use crate::{response::Modified, Response, Result};
+use crate::response::{Modified, ResourceContext};
+use crate::{util, Response, Result};
 use serde::de::DeserializeOwned;
 use serde_json::Value as JsonValue;
-use std::{collections::HashMap, net::IpAddr};
+use std::{collections::HashMap, hash::Hash, net::IpAddr, time::Duration};
 
+mod base_url;
+mod batch;
+mod cache;
+mod connection;
 #[cfg(feature = "upnp-description")]
 mod description;
+mod diagnostics;
 mod discover;
+mod handle;
+mod recorder;
 mod register;
+mod self_check;
+mod set;
+mod setup;
+mod strict;
+#[cfg(test)]
+pub(crate) mod test_server;
+mod unauthenticated;
+mod vacation;
 
+pub use batch::Batch;
 #[cfg(feature = "upnp-description")]
 pub use description::{
     description, Description, DescriptionDevice, DescriptionIcon, DescriptionSpecVersion,
 };
-pub use discover::discover_nupnp;
-pub use register::{register_user, register_user_with_clientkey};
+pub use diagnostics::{DiagnosticsBundle, ErrorCounters, LoggedRequest};
+pub use discover::{
+    discover_all, discover_in_background, discover_nupnp, discover_nupnp_with_options, Discovered,
+    DiscoveredBridge, DiscoveryCache, DiscoveryOptions, DiscoverySource,
+};
+pub use handle::{GroupHandle, LightHandle, SensorHandle};
+pub use recorder::RecordedRequest;
+pub use register::{
+    register_user, register_user_polling, register_user_polling_with_base_url,
+    register_user_with_base_url, register_user_with_clientkey,
+    register_user_with_clientkey_polling,
+};
+pub use self_check::{SelfCheckReport, Verification};
+pub use set::{BridgeSet, OwnedResource};
+pub use setup::{setup_new_bridge, setup_new_bridge_with_base_url, BridgeSetup};
+pub use unauthenticated::get_unauthenticated_config;
+pub use vacation::VacationMode;
 
 type ResponsesModified = Vec<Response<Modified>>;
 
@@ -22,23 +54,55 @@ fn parse_response<T>(response: JsonValue) -> crate::Result<T>
 where
     T: DeserializeOwned,
 {
-    if let Ok(mut v) = serde_json::from_value::<Vec<Response<JsonValue>>>(response.clone()) {
-        if let Some(v) = v.pop() {
-            v.into_result()?;
+    if let Ok(v) = serde_json::from_value::<Vec<Response<JsonValue>>>(response.clone()) {
+        let errors: Vec<_> = v
+            .into_iter()
+            .filter_map(|v| v.into_result().err())
+            .collect();
+        if !errors.is_empty() {
+            return Err(crate::Error::from_response_errors(errors));
         }
     }
     Ok(serde_json::from_value(response)?)
 }
 
-/// A bridge with IP address and username.
+/// Returns the errors in the responses of a request that modified a resource as `Err`.
+fn check_responses(responses: ResponsesModified) -> crate::Result<ResponsesModified> {
+    let errors: Vec<_> = responses
+        .iter()
+        .filter_map(|v| match v {
+            Response::Error(e) => Some(e.clone()),
+            Response::Success(_) => None,
+        })
+        .collect();
+    if errors.is_empty() {
+        Ok(responses)
+    } else {
+        Err(crate::Error::from_response_errors(errors))
+    }
+}
+
+/// A bridge with base URL and username.
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct Bridge {
     /// Name of the user that is connected to the bridge.
     username: String,
     /// IP address of the bridge.
     ip_address: IpAddr,
+    /// Url of the bridge without the path to the Philips Hue API.
+    base_url: String,
     /// Url to the Philips Hue API.
     api_url: String,
+    /// Recorder for requests if the bridge is in dry-run mode.
+    recorder: Option<recorder::Recorder>,
+    /// HTTP connections to the bridge.
+    connection: connection::Connection,
+    /// Cache for responses if caching is enabled.
+    cache: Option<cache::Cache>,
+    /// Whether values that were adjusted by the bridge are returned as error.
+    strict_mode: bool,
+    /// Error counters and recent requests for diagnostics.
+    request_log: diagnostics::RequestLog,
 }
 
 impl Bridge {
@@ -58,47 +122,513 @@ impl Bridge {
     where
         S: Into<String>,
     {
-        let username = username.into();
+        Self::with_url(base_url::from_ip(ip_address), ip_address, username.into())
+    }
+
+    /// Creates a new bridge from the URL that the Philips Hue API is served under.
+    ///
+    /// This is useful for bridges that are not reachable at `http://<ip_address>/api`, e.g.
+    /// emulated bridges on a non-standard port or bridges behind a reverse proxy. The base URL
+    /// contains the scheme, the host, an optional port and an optional path prefix, but not the
+    /// `/api` path. If the host is a name, it is resolved once to determine the
+    /// [`ip_address`](Self::ip_address) of the bridge. Returns [`Error::InvalidBaseUrl`] if the
+    /// URL does not start with `http://` or `https://`, has no host or the host cannot be
+    /// resolved.
+    ///
+    /// # Examples
+    ///
+    /// Create a bridge that is served on port 8080:
+    /// ```
+    /// use huelib2::Bridge;
+    ///
+    /// # fn main() -> huelib2::Result<()> {
+    /// let bridge = Bridge::from_base_url("http://192.168.1.2:8080", "username")?;
+    /// assert_eq!(bridge.base_url(), "http://192.168.1.2:8080");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Error::InvalidBaseUrl`]: crate::Error::InvalidBaseUrl
+    pub fn from_base_url<S1, S2>(base_url: S1, username: S2) -> Result<Self>
+    where
+        S1: AsRef<str>,
+        S2: Into<String>,
+    {
+        let (base_url, ip_address) = base_url::parse(base_url.as_ref())?;
+        Ok(Self::with_url(base_url, ip_address, username.into()))
+    }
+
+    fn with_url(base_url: String, ip_address: IpAddr, username: String) -> Self {
         Bridge {
-            api_url: format!("http://{}/api/{}", ip_address, username),
+            api_url: format!("{}/api/{}", base_url, username),
             username,
             ip_address,
+            base_url,
+            recorder: None,
+            connection: connection::Connection::new(true),
+            cache: None,
+            strict_mode: false,
+            request_log: diagnostics::RequestLog::default(),
         }
     }
 
+    /// Enables caching of responses from the bridge.
+    ///
+    /// Responses of requests that read from the bridge are cached for the given time to live.
+    /// When a request modifies a resource, all cached responses of the same resource kind are
+    /// invalidated. Modifying a group also invalidates cached lights. Clones of the bridge share
+    /// the cache.
+    ///
+    /// Note that changes made by other clients or by the bridge itself (e.g. rules and schedules)
+    /// are only visible after the cached responses have expired.
+    ///
+    /// # Examples
+    ///
+    /// Cache responses for one second:
+    /// ```no_run
+    /// use huelib2::Bridge;
+    /// use std::net::{IpAddr, Ipv4Addr};
+    /// use std::time::Duration;
+    ///
+    /// # fn main() -> huelib2::Result<()> {
+    /// let ip = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2));
+    /// let bridge = Bridge::new(ip, "username").with_cache(Duration::from_secs(1));
+    /// let lights = bridge.get_all_lights()?;
+    /// // Does not send a request to the bridge.
+    /// let lights = bridge.get_all_lights()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_cache(self, ttl: Duration) -> Self {
+        Self {
+            cache: Some(cache::Cache::new(ttl)),
+            ..self
+        }
+    }
+
+    /// Returns the time to live of cached responses, if caching is enabled.
+    pub fn cache_ttl(&self) -> Option<Duration> {
+        self.cache.as_ref().map(cache::Cache::ttl)
+    }
+
+    /// Removes all cached responses.
+    pub fn clear_cache(&self) {
+        if let Some(cache) = &self.cache {
+            cache.clear();
+        }
+    }
+
+    /// Sets whether connections to the bridge are kept alive and reused for multiple requests.
+    ///
+    /// Connections are kept alive by default. Some bridge firmwares drop idle connections without
+    /// closing them, which causes the first request after a long idle period to fail. Disabling
+    /// keep-alive opens a new connection for every request. See also
+    /// [`close_idle_connections`].
+    ///
+    /// [`close_idle_connections`]: #method.close_idle_connections
+    pub fn with_keep_alive(self, value: bool) -> Self {
+        Self {
+            connection: connection::Connection::new(value),
+            ..self
+        }
+    }
+
+    /// Returns whether connections to the bridge are kept alive.
+    pub fn keep_alive(&self) -> bool {
+        self.connection.keep_alive()
+    }
+
+    /// Closes all idle connections to the bridge.
+    ///
+    /// The next request opens a new connection. This is useful after the host resumed from sleep,
+    /// when kept alive connections might have been dropped by the bridge. Clones of the bridge
+    /// share their connections.
+    pub fn close_idle_connections(&self) {
+        self.connection.close_idle();
+    }
+
+    /// Sets whether values that were adjusted by the bridge are returned as error.
+    ///
+    /// The bridge silently clamps values that are out of range, e.g. a brightness of 255 is set to
+    /// 254. In strict mode, the values in the responses of modifiers are compared with the
+    /// requested values and [`Error::Clamped`] is returned if they differ. Floating point values
+    /// are allowed to differ by less than 0.001, because the bridge rounds them. Strict mode is
+    /// disabled by default.
+    ///
+    /// Note that the request has already been applied by the bridge when the error is returned.
+    ///
+    /// [`Error::Clamped`]: crate::Error::Clamped
+    pub fn with_strict_mode(self, value: bool) -> Self {
+        Self {
+            strict_mode: value,
+            ..self
+        }
+    }
+
+    /// Returns whether strict mode is enabled.
+    pub fn strict_mode(&self) -> bool {
+        self.strict_mode
+    }
+
+    /// Puts the bridge into dry-run mode.
+    ///
+    /// In dry-run mode, requests that would change the state of the bridge (`PUT`, `POST` and
+    /// `DELETE`) are recorded instead of being sent, and a response acknowledging the request is
+    /// simulated. Creators return an empty identifier. Requests that only read from the bridge
+    /// are still sent.
+    ///
+    /// The recorded requests can be obtained with [`take_recorded_requests`]. Clones of the
+    /// bridge share the recorded requests.
+    ///
+    /// # Examples
+    ///
+    /// Preview the requests sent by a modifier:
+    /// ```
+    /// use huelib2::{resource::light, Bridge};
+    /// use std::net::{IpAddr, Ipv4Addr};
+    ///
+    /// # fn main() -> huelib2::Result<()> {
+    /// let ip = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2));
+    /// let bridge = Bridge::new(ip, "username").with_dry_run();
+    /// let modifier = light::StateModifier::new().with_on(true);
+    /// bridge.set_light_state("1", &modifier)?;
+    /// for request in bridge.take_recorded_requests() {
+    ///     println!("{:?} {} {:?}", request.method, request.path, request.body);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`take_recorded_requests`]: #method.take_recorded_requests
+    pub fn with_dry_run(self) -> Self {
+        Self {
+            recorder: Some(recorder::Recorder::default()),
+            ..self
+        }
+    }
+
+    /// Returns whether the bridge is in dry-run mode.
+    pub fn is_dry_run(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    /// Removes and returns the requests that were recorded in dry-run mode.
+    ///
+    /// Returns an empty vector if the bridge is not in dry-run mode.
+    pub fn take_recorded_requests(&self) -> Vec<RecordedRequest> {
+        self.recorder
+            .as_ref()
+            .map(recorder::Recorder::take)
+            .unwrap_or_default()
+    }
+
     /// Returns the name of the user that is connected to the bridge.
     pub fn username(&self) -> &str {
         &self.username
     }
 
     /// Returns the IP address of the bridge.
+    ///
+    /// If the bridge was created from a base URL with a host name, this is the address that the
+    /// name resolved to when the bridge was created. Requests are always sent to the base URL.
     pub fn ip_address(&self) -> &IpAddr {
         &self.ip_address
     }
 
+    /// Returns the URL of the bridge without the path to the Philips Hue API, e.g.
+    /// `http://192.168.1.2`.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
     /// Sends a HTTP request to the Philips Hue API and returns the response.
+    ///
+    /// If the response of a `GET` request contains an error, the error is returned. The resource
+    /// that was accessed is attached to errors returned by the Philips Hue API, unless the
+    /// request targets the whole bridge.
     pub(crate) fn api_request<S, T>(
         &self,
         url_suffix: S,
         request_method: RequestMethod,
-        body: Option<JsonValue>,
+        body: Option<&JsonValue>,
     ) -> Result<T>
     where
         S: AsRef<str>,
         T: DeserializeOwned,
     {
-        let url = format!("{}/{}", self.api_url, url_suffix.as_ref());
+        let url_suffix = url_suffix.as_ref();
+        let requested = match request_method {
+            RequestMethod::Put if self.strict_mode => body,
+            _ => None,
+        };
+        self.send_request(url_suffix, request_method, body)
+            .and_then(|v| {
+                if let Some(requested) = requested {
+                    strict::check_clamped(requested, &v)?;
+                }
+                Ok(v)
+            })
+            .and_then(|v| match request_method {
+                RequestMethod::Get => parse_response(v),
+                _ => Ok(serde_json::from_value(v)?),
+            })
+            .map_err(|e| e.with_resource_context(request_method, url_suffix))
+    }
+
+    /// Sends a HTTP request that changes the bridge and returns the responses.
+    ///
+    /// Errors returned by the Philips Hue API are not converted into an `Err`, because a request
+    /// can partially succeed. Instead, the resource that was accessed is attached to every error
+    /// response.
+    pub(crate) fn api_request_responses<S, T>(
+        &self,
+        url_suffix: S,
+        request_method: RequestMethod,
+        body: Option<&JsonValue>,
+    ) -> Result<Vec<Response<T>>>
+    where
+        S: AsRef<str>,
+        T: DeserializeOwned,
+    {
+        let url_suffix = url_suffix.as_ref();
+        let responses: Vec<Response<T>> = self.api_request(url_suffix, request_method, body)?;
+        let resource = ResourceContext::new(request_method, url_suffix);
+        Ok(responses
+            .into_iter()
+            .map(|v| v.with_resource(resource.as_ref()))
+            .collect())
+    }
+
+    /// Sends a HTTP request to the Philips Hue API and returns the response as JSON.
+    fn send_request(
+        &self,
+        url_suffix: &str,
+        request_method: RequestMethod,
+        body: Option<&JsonValue>,
+    ) -> Result<JsonValue> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!(
+            "api_request",
+            method = ?request_method,
+            url_suffix = url_suffix
+        )
+        .entered();
+        let start = std::time::Instant::now();
+        #[cfg(feature = "tracing")]
+        tracing::debug!(body = ?body, "sending request");
+
+        if let (Some(cache), RequestMethod::Get) = (&self.cache, request_method) {
+            if let Some(value) = cache.get(url_suffix) {
+                #[cfg(feature = "tracing")]
+                tracing::debug!("using cached response");
+                return Ok(value);
+            }
+        }
+
+        if let Some(recorder) = &self.recorder {
+            if request_method != RequestMethod::Get {
+                return Ok(recorder.record(request_method, url_suffix, body.cloned()));
+            }
+        }
+
+        let url = match url_suffix {
+            "" => self.api_url.clone(),
+            v => format!("{}/{}", self.api_url, v),
+        };
+        let agent = self.connection.agent();
         let request = match request_method {
-            RequestMethod::Put => ureq::put(&url),
-            RequestMethod::Post => ureq::post(&url),
-            RequestMethod::Get => ureq::get(&url),
-            RequestMethod::Delete => ureq::delete(&url),
+            RequestMethod::Put => agent.put(&url),
+            RequestMethod::Post => agent.post(&url),
+            RequestMethod::Get => agent.get(&url),
+            RequestMethod::Delete => agent.delete(&url),
         };
         let response = match body {
-            Some(v) => request.send_json(v)?,
-            None => request.call()?,
+            Some(v) => request.send_json(v),
+            None => request.call(),
         };
-        Ok(response.into_json()?)
+
+        #[cfg(feature = "tracing")]
+        match &response {
+            Ok(v) => tracing::debug!(
+                status = v.status(),
+                duration = ?start.elapsed(),
+                "received response"
+            ),
+            Err(e) => tracing::warn!(error = %e, duration = ?start.elapsed(), "request failed"),
+        }
+
+        let result = response.map(|v| {
+            let status = v.status();
+            (status, v.into_json::<JsonValue>())
+        });
+        self.request_log.record(
+            request_method,
+            url_suffix,
+            start.elapsed(),
+            match &result {
+                Ok((status, value)) => Ok((*status, value.as_ref().ok())),
+                Err(e) => Err(e),
+            },
+        );
+        let value = result?.1?;
+        if let Some(cache) = &self.cache {
+            match request_method {
+                RequestMethod::Get => cache.insert(url_suffix, value.clone()),
+                _ => cache.invalidate(url_suffix),
+            }
+        }
+        Ok(value)
+    }
+
+    /// Sends `PUT` requests to multiple URL suffixes and returns the result for every key.
+    ///
+    /// The requests are distributed over up to `threads` threads. Requests to the same resource
+    /// are sent by the same thread in the given order. Every thread waits for `pacing` between two
+    /// consecutive requests.
+    pub(crate) fn put_many<K>(
+        &self,
+        targets: Vec<(K, String, &JsonValue)>,
+        threads: usize,
+        pacing: Duration,
+    ) -> resource::ModifiedMany<K>
+    where
+        K: Eq + Hash + Send,
+    {
+        let put = |targets: Vec<(K, String, &JsonValue)>| {
+            targets
+                .into_iter()
+                .enumerate()
+                .map(|(i, (key, url_suffix, body))| {
+                    if i > 0 && !pacing.is_zero() {
+                        std::thread::sleep(pacing);
+                    }
+                    let result =
+                        self.api_request_responses(url_suffix, RequestMethod::Put, Some(body));
+                    (key, result)
+                })
+                .collect::<Vec<_>>()
+        };
+        let threads = threads.min(targets.len()).max(1);
+        if threads == 1 {
+            return put(targets).into_iter().collect();
+        }
+        // Resources are assigned to the threads in the order of their first request.
+        let mut resources: HashMap<String, usize> = HashMap::new();
+        let mut chunks: Vec<Vec<(K, String, &JsonValue)>> =
+            (0..threads).map(|_| Vec::new()).collect();
+        for target in targets {
+            let resource = target
+                .1
+                .splitn(3, '/')
+                .take(2)
+                .collect::<Vec<_>>()
+                .join("/");
+            let next = resources.len() % threads;
+            let chunk = *resources.entry(resource).or_insert(next);
+            chunks[chunk].push(target);
+        }
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = chunks
+                .into_iter()
+                .map(|chunk| scope.spawn(|| put(chunk)))
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("request thread panicked"))
+                .collect()
+        })
+    }
+
+    /// Executes the commands of a batch.
+    ///
+    /// Returns the result of every command in the order the commands were added to the batch.
+    /// See [`Batch`] for more information.
+    pub fn execute_batch(&self, batch: &Batch) -> Vec<Result<ResponsesModified>> {
+        batch::execute(self, batch)
+    }
+
+    /// Verifies that the bridge is usable.
+    ///
+    /// This checks whether the bridge is reachable and the user is authorized, determines the
+    /// clock drift of the bridge, the software and API version, the capacity headroom and which
+    /// devices cannot be reached by the bridge.
+    ///
+    /// An unreachable bridge or an unauthorized user is reported in the returned
+    /// [`SelfCheckReport`] and not as an error.
+    ///
+    /// # Examples
+    ///
+    /// Verify the bridge on startup:
+    /// ```no_run
+    /// # fn main() -> huelib2::Result<()> {
+    /// # use huelib2::Bridge;
+    /// # use std::net::{IpAddr, Ipv4Addr};
+    /// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+    /// // let bridge = Bridge::new(...);
+    /// let report = bridge.self_check()?;
+    /// if !report.is_healthy() {
+    ///     println!("Bridge is not healthy: {:?}", report);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn self_check(&self) -> Result<SelfCheckReport> {
+        self_check::self_check(self)
+    }
+
+    /// Verifies that the IP address belongs to a bridge and that the user is whitelisted.
+    ///
+    /// This sends at most two requests and is cheaper than [`self_check`]. Failing connections and
+    /// unknown users are returned as [`Verification`] instead of an error.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use huelib2::bridge::Verification;
+    ///
+    /// # fn main() -> huelib2::Result<()> {
+    /// # use huelib2::Bridge;
+    /// # use std::net::{IpAddr, Ipv4Addr};
+    /// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+    /// // let bridge = Bridge::new(...);
+    /// match bridge.verify()? {
+    ///     Verification::Ok(config) => println!("Connected to {}", config.name),
+    ///     Verification::Unauthorized(_) => println!("User is not whitelisted"),
+    ///     Verification::NotABridge => println!("Not a Philips Hue bridge"),
+    ///     Verification::Unreachable => println!("Bridge is unreachable"),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`self_check`]: #method.self_check
+    /// [`Verification`]: crate::bridge::Verification
+    pub fn verify(&self) -> Result<Verification> {
+        self_check::verify(self)
+    }
+
+    /// Returns information about the bridge that can be attached to bug reports.
+    ///
+    /// See [`DiagnosticsBundle`] for the information that is included. Errors and requests are
+    /// tracked for the bridge and all of its clones. Requests that are answered from the cache or
+    /// recorded in dry-run mode are not included.
+    ///
+    /// # Examples
+    ///
+    /// Print the bundle as JSON:
+    /// ```no_run
+    /// # fn main() -> huelib2::Result<()> {
+    /// # use huelib2::Bridge;
+    /// # use std::net::{IpAddr, Ipv4Addr};
+    /// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+    /// // let bridge = Bridge::new(...);
+    /// let bundle = bridge.diagnostics_bundle()?;
+    /// println!("{}", serde_json::to_string_pretty(&bundle)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn diagnostics_bundle(&self) -> Result<DiagnosticsBundle> {
+        diagnostics::diagnostics_bundle(self)
     }
 
     /// Modifies the configuration of the bridge.
@@ -106,9 +636,75 @@ impl Bridge {
         modifier.execute(self, ())
     }
 
+    /// Starts a touchlink procedure which adds the closest lamp to the ZigBee network.
+    ///
+    /// This can be used to adopt or reset third-party lamps. The lamp has to be close to the
+    /// bridge. When the procedure has finished, search for new lights using
+    /// [`search_new_lights`] to add the lamp.
+    ///
+    /// [`search_new_lights`]: #method.search_new_lights
+    pub fn touchlink(&self) -> Result<ResponsesModified> {
+        self.set_config(&resource::config::Modifier::new().with_touchlink(true))
+    }
+
     /// Returns the configuration of the bridge.
     pub fn get_config(&self) -> Result<resource::Config> {
-        parse_response(self.api_request("config", RequestMethod::Get, None)?)
+        self.api_request("config", RequestMethod::Get, None)
+    }
+
+    /// Deletes a user from the whitelist of the bridge.
+    ///
+    /// Newer firmware versions of the bridge do not allow deleting users with the API. In that case
+    /// [`Error::DeleteUserNotAllowed`] is returned and the user has to be removed using the
+    /// Philips Hue account instead.
+    ///
+    /// [`Error::DeleteUserNotAllowed`]: crate::Error::DeleteUserNotAllowed
+    pub fn delete_user<S>(&self, username: S) -> Result<()>
+    where
+        S: Into<String>,
+    {
+        let username = username.into();
+        let response: Vec<Response<JsonValue>> = self.api_request_responses(
+            format!("config/whitelist/{}", username),
+            RequestMethod::Delete,
+            None,
+        )?;
+        for i in response {
+            i.into_result()
+                .map_err(|e| crate::Error::from_delete_user_error(e, &username))?;
+        }
+        Ok(())
+    }
+
+    /// Returns the full state of the bridge.
+    ///
+    /// This returns all resources and the configuration of the bridge with a single request.
+    pub fn get_full_state(&self) -> Result<resource::FullState> {
+        self.api_request("", RequestMethod::Get, None)
+    }
+
+    /// Returns a handle for the light with the given identifier.
+    ///
+    /// The handle carries the identifier, so it does not have to be passed to every method.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use huelib2::resource::light;
+    ///
+    /// # fn main() -> huelib2::Result<()> {
+    /// # use huelib2::Bridge;
+    /// # use std::net::{IpAddr, Ipv4Addr};
+    /// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+    /// // let bridge = Bridge::new(...);
+    /// let desk = bridge.light("1");
+    /// desk.rename("Desk")?;
+    /// desk.set_state(&light::StateModifier::new().with_on(true))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn light<'a>(&'a self, id: &'a str) -> LightHandle<'a> {
+        LightHandle::new(self, id)
     }
 
     /// Modifies attributes of a light.
@@ -123,106 +719,609 @@ impl Bridge {
         modifier.execute(self, id.into())
     }
 
-    /// Modifies the state of a light.
-    pub fn set_light_state<S>(
+    /// Modifies the configuration of a light.
+    ///
+    /// # Examples
+    ///
+    /// Restore the last state of a light when the power is restored:
+    /// ```no_run
+    /// use huelib2::resource::light;
+    ///
+    /// # fn main() -> huelib2::Result<()> {
+    /// # use huelib2::Bridge;
+    /// # use std::net::{IpAddr, Ipv4Addr};
+    /// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+    /// // let bridge = Bridge::new(...);
+    /// let modifier = light::ConfigModifier::new().with_startup(light::Startup::LastOnState);
+    /// bridge.set_light_config("1", &modifier)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_light_config<S>(
+        &self,
+        id: S,
+        modifier: &resource::light::ConfigModifier,
+    ) -> Result<ResponsesModified>
+    where
+        S: Into<String>,
+    {
+        modifier.execute(self, id.into())
+    }
+
+    /// Modifies the state of a light.
+    pub fn set_light_state<S>(
+        &self,
+        id: S,
+        modifier: &resource::light::StateModifier,
+    ) -> Result<ResponsesModified>
+    where
+        S: Into<String>,
+    {
+        modifier.execute(self, id.into())
+    }
+
+    /// Lets a light perform one breathe cycle, so it can be identified.
+    ///
+    /// This sends the `select` alert effect. To let the light breathe for 15 seconds, set the
+    /// alert effect to [`Alert::LSelect`] with [`set_light_state`].
+    ///
+    /// [`Alert::LSelect`]: resource::Alert::LSelect
+    /// [`set_light_state`]: #method.set_light_state
+    pub fn identify_light<S>(&self, id: S) -> Result<ResponsesModified>
+    where
+        S: Into<String>,
+    {
+        let modifier = resource::light::StateModifier::new().with_alert(resource::Alert::Select);
+        self.set_light_state(id, &modifier)
+    }
+
+    /// Stops the alert effect of a light, e.g. a running `lselect` breathe cycle.
+    ///
+    /// See [`recipes::breathe_light`] to stop the alert effect after a duration.
+    ///
+    /// [`recipes::breathe_light`]: crate::recipes::breathe_light
+    pub fn stop_light_alert<S>(&self, id: S) -> Result<ResponsesModified>
+    where
+        S: Into<String>,
+    {
+        let modifier = resource::light::StateModifier::new().with_alert(resource::Alert::None);
+        self.set_light_state(id, &modifier)
+    }
+
+    /// Modifies the state of multiple lights.
+    ///
+    /// The modifier is serialized once and dispatched according to the given policy. Returns the
+    /// result for every light identifier.
+    ///
+    /// # Examples
+    ///
+    /// Turn on three lights using two threads:
+    /// ```no_run
+    /// use huelib2::resource::{light, DispatchPolicy};
+    ///
+    /// # fn main() -> huelib2::Result<()> {
+    /// # use huelib2::Bridge;
+    /// # use std::net::{IpAddr, Ipv4Addr};
+    /// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+    /// // let bridge = Bridge::new(...);
+    /// let modifier = light::StateModifier::new().with_on(true);
+    /// let results = bridge.set_light_state_many(
+    ///     vec!["1", "2", "3"],
+    ///     &modifier,
+    ///     DispatchPolicy::Parallel(2),
+    /// )?;
+    /// for (id, result) in results {
+    ///     if let Err(e) = result {
+    ///         println!("Failed to modify light {}: {}", id, e);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_light_state_many<I, S>(
+        &self,
+        ids: I,
+        modifier: &resource::light::StateModifier,
+        policy: resource::DispatchPolicy,
+    ) -> Result<resource::ModifiedMany<String>>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        modifier.execute_many(self, ids.into_iter().map(Into::into), policy)
+    }
+
+    /// Returns a light.
+    pub fn get_light<S>(&self, id: S) -> Result<resource::Light>
+    where
+        S: Into<String>,
+    {
+        let id = id.into();
+        let light: resource::Light =
+            self.api_request(format!("lights/{}", id), RequestMethod::Get, None)?;
+        Ok(light.with_id(id))
+    }
+
+    /// Returns the light with the given unique identifier.
+    ///
+    /// Unlike the identifier of a light, the unique identifier (which contains the MAC address of
+    /// the light) stays the same when the light is reset or connected to another bridge. The
+    /// unique identifier is compared case-insensitively. Returns [`Error::UnknownUniqueId`] if no
+    /// light has the unique identifier.
+    ///
+    /// This requests all lights of the bridge. Enable caching with [`with_cache`] to avoid a
+    /// request for every lookup.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> huelib2::Result<()> {
+    /// # use huelib2::Bridge;
+    /// # use std::net::{IpAddr, Ipv4Addr};
+    /// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+    /// // let bridge = Bridge::new(...);
+    /// let light = bridge.get_light_by_unique_id("00:17:88:01:00:bd:c7:b9-0b")?;
+    /// println!("{} has the identifier {}", light.name, light.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Error::UnknownUniqueId`]: crate::Error::UnknownUniqueId
+    /// [`with_cache`]: #method.with_cache
+    pub fn get_light_by_unique_id(&self, unique_id: &str) -> Result<resource::Light> {
+        self.get_all_lights()?
+            .into_iter()
+            .find(|v| v.unique_id.eq_ignore_ascii_case(unique_id))
+            .ok_or_else(|| crate::Error::UnknownUniqueId(unique_id.to_owned()))
+    }
+
+    /// Returns all lights whose name matches the pattern.
+    ///
+    /// # Examples
+    ///
+    /// Turn on all lights in the kitchen:
+    /// ```no_run
+    /// use huelib2::resource::light::{self, NameMatch};
+    ///
+    /// # fn main() -> huelib2::Result<()> {
+    /// # use huelib2::Bridge;
+    /// # use std::net::{IpAddr, Ipv4Addr};
+    /// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+    /// // let bridge = Bridge::new(...);
+    /// let modifier = light::StateModifier::new().with_on(true);
+    /// for light in bridge.get_lights_by_name("kitchen", NameMatch::Contains)? {
+    ///     bridge.set_light_state(light.id, &modifier)?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_lights_by_name(
+        &self,
+        pattern: &str,
+        name_match: resource::light::NameMatch,
+    ) -> Result<Vec<resource::Light>> {
+        let mut lights = self.get_all_lights()?;
+        lights.retain(|v| name_match.matches(&v.name, pattern));
+        Ok(lights)
+    }
+
+    /// Returns all lights that are connected to the bridge.
+    pub fn get_all_lights(&self) -> Result<Vec<resource::Light>> {
+        let map: HashMap<String, resource::Light> =
+            self.api_request("lights", RequestMethod::Get, None)?;
+        Ok(map
+            .into_iter()
+            .map(|(id, light)| light.with_id(id))
+            .collect())
+    }
+
+    /// Starts searching for new lights.
+    ///
+    /// The bridge will open the network for 40 seconds. The overall search might take longer since
+    /// the configuration of new devices can take longer. If many devices are found the command
+    /// will have to be issued a second time after discovery time has elapsed. If the command is
+    /// received again during search the search will continue for at least an additional 40
+    /// seconds.
+    ///
+    /// When the search has finished, new lights will be available using the [`get_new_lights`]
+    /// function.
+    ///
+    /// [`get_new_lights`]: #method.get_new_lights
+    pub fn search_new_lights(&self, scanner: &resource::light::Scanner) -> Result<()> {
+        scanner.execute(self)
+    }
+
+    /// Returns discovered lights.
+    pub fn get_new_lights(&self) -> Result<resource::Scan> {
+        self.api_request("lights/new", RequestMethod::Get, None)
+    }
+
+    /// Deletes a light from the bridge.
+    pub fn delete_light<S>(&self, id: S) -> Result<()>
+    where
+        S: Into<String>,
+    {
+        let response: Vec<Response<JsonValue>> = self.api_request_responses(
+            format!("lights/{}", id.into()),
+            RequestMethod::Delete,
+            None,
+        )?;
+        for i in response {
+            i.into_result()?;
+        }
+        Ok(())
+    }
+
+    /// Returns the renames that [`rename_lights_matching`] would apply, without modifying any
+    /// light.
+    ///
+    /// [`rename_lights_matching`]: #method.rename_lights_matching
+    pub fn preview_rename_lights_matching(
+        &self,
+        pattern: &str,
+        template: &str,
+    ) -> Result<Vec<resource::light::Rename>> {
+        let lights = self.get_all_lights()?;
+        Ok(resource::light::plan_renames(
+            lights.iter().map(|v| (v.id.as_str(), v.name.as_str())),
+            pattern,
+            template,
+        ))
+    }
+
+    /// Renames all lights whose name contains `pattern` using a numbered template.
+    ///
+    /// The matching lights are ordered by their identifier and numbered starting at 1. Every
+    /// `{n}` in `template` is replaced by the number of the light. Use
+    /// [`preview_rename_lights_matching`] to get the renames without applying them.
+    ///
+    /// Returns the renames that were applied.
+    ///
+    /// # Examples
+    ///
+    /// Rename all lights containing `Spot` to `Kitchen spot 1`, `Kitchen spot 2`, etc.:
+    /// ```no_run
+    /// # fn main() -> huelib2::Result<()> {
+    /// # use huelib2::Bridge;
+    /// # use std::net::{IpAddr, Ipv4Addr};
+    /// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+    /// // let bridge = Bridge::new(...);
+    /// let renames = bridge.rename_lights_matching("Spot", "Kitchen spot {n}")?;
+    /// for rename in renames {
+    ///     println!("Renamed `{}` to `{}`", rename.old_name, rename.new_name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`preview_rename_lights_matching`]: #method.preview_rename_lights_matching
+    pub fn rename_lights_matching(
+        &self,
+        pattern: &str,
+        template: &str,
+    ) -> Result<Vec<resource::light::Rename>> {
+        let renames = self.preview_rename_lights_matching(pattern, template)?;
+        for rename in &renames {
+            let modifier =
+                resource::light::AttributeModifier::new().with_name(rename.new_name.clone());
+            for response in self.set_light_attribute(rename.id.clone(), &modifier)? {
+                response.into_result()?;
+            }
+        }
+        Ok(renames)
+    }
+
+    /// Creates a new group.
+    pub fn create_group(&self, creator: &resource::group::Creator) -> Result<String> {
+        creator.execute(self)
+    }
+
+    /// Returns a handle for the group with the given identifier.
+    ///
+    /// See [`light`] for more information.
+    ///
+    /// [`light`]: #method.light
+    pub fn group<'a>(&'a self, id: &'a str) -> GroupHandle<'a> {
+        GroupHandle::new(self, id)
+    }
+
+    /// Modifies attributes of a group.
+    pub fn set_group_attribute<S>(
+        &self,
+        id: S,
+        modifier: &resource::group::AttributeModifier,
+    ) -> Result<ResponsesModified>
+    where
+        S: Into<String>,
+    {
+        modifier.execute(self, id.into())
+    }
+
+    /// Adds a light to a group.
+    ///
+    /// The lights of the group are read, the light is added and the lights are written back. The
+    /// group is read again afterwards and [`Error::GroupConflict`] is returned if its lights differ
+    /// from the written lights, e.g. because another client modified the group at the same time.
+    /// Nothing is modified if the light is already in the group. In dry-run mode, the group is not
+    /// read again.
+    ///
+    /// The conflict detection is best-effort: the Philips Hue API has no conditional writes, so a
+    /// change by another client between reading and writing the lights is overwritten. It is only
+    /// detected if the lights of the group differ from the written lights afterwards. Errors
+    /// returned by the bridge for the write, e.g. because the light is already part of another
+    /// room, are returned in the responses without checking for a conflict.
+    ///
+    /// [`Error::GroupConflict`]: crate::Error::GroupConflict
+    pub fn add_light_to_group<S1, S2>(
+        &self,
+        group_id: S1,
+        light_id: S2,
+    ) -> Result<ResponsesModified>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        let light_id = light_id.into();
+        self.update_group_lights(group_id.into(), |lights| {
+            if lights.contains(&light_id) {
+                return false;
+            }
+            lights.push(light_id);
+            true
+        })
+    }
+
+    /// Removes a light from a group.
+    ///
+    /// Nothing is modified if the light is not in the group. See [`add_light_to_group`] for more
+    /// information.
+    ///
+    /// [`add_light_to_group`]: #method.add_light_to_group
+    pub fn remove_light_from_group<S1, S2>(
+        &self,
+        group_id: S1,
+        light_id: S2,
+    ) -> Result<ResponsesModified>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        let light_id = light_id.into();
+        self.update_group_lights(group_id.into(), |lights| {
+            let len = lights.len();
+            lights.retain(|v| v != &light_id);
+            lights.len() != len
+        })
+    }
+
+    /// Reads the lights of a group, applies `update` and writes them back if `update` returns
+    /// `true`.
+    fn update_group_lights<F>(&self, id: String, update: F) -> Result<ResponsesModified>
+    where
+        F: FnOnce(&mut Vec<String>) -> bool,
+    {
+        if let Some(cache) = &self.cache {
+            cache.invalidate(&format!("groups/{}", id));
+        }
+        let mut lights = self.get_group(id.as_str())?.lights;
+        if !update(&mut lights) {
+            return Ok(Vec::new());
+        }
+        let modifier = resource::group::AttributeModifier::new().with_lights(lights.clone());
+        let responses = self.set_group_attribute(id.as_str(), &modifier)?;
+        let failed = responses.iter().any(|v| matches!(v, Response::Error(_)));
+        if failed || self.is_dry_run() {
+            return Ok(responses);
+        }
+        let mut current = self.get_group(id.as_str())?.lights;
+        current.sort();
+        lights.sort();
+        if current != lights {
+            return Err(crate::Error::GroupConflict(id));
+        }
+        Ok(responses)
+    }
+
+    /// Modifies the state of a group.
+    pub fn set_group_state<S>(
+        &self,
+        id: S,
+        modifier: &resource::group::StateModifier,
+    ) -> Result<ResponsesModified>
+    where
+        S: Into<String>,
+    {
+        modifier.execute(self, id.into())
+    }
+
+    /// Recalls a scene on a group.
+    ///
+    /// If `transition` is given, it overrides the transition time that is stored in the scene. It
+    /// is rounded to the nearest multiple of 100ms.
+    pub fn recall_scene<S1, S2>(
         &self,
-        id: S,
-        modifier: &resource::light::StateModifier,
+        group_id: S1,
+        scene_id: S2,
+        transition: Option<Duration>,
     ) -> Result<ResponsesModified>
     where
-        S: Into<String>,
+        S1: Into<String>,
+        S2: Into<String>,
     {
-        modifier.execute(self, id.into())
+        let mut modifier = resource::group::StateModifier::new().with_scene(scene_id.into());
+        if let Some(v) = transition {
+            modifier = modifier.with_transition_duration(v);
+        }
+        self.set_group_state(group_id, &modifier)
     }
 
-    /// Returns a light.
-    pub fn get_light<S>(&self, id: S) -> Result<resource::Light>
+    /// Modifies the state of all lights of the bridge.
+    ///
+    /// This modifies the special group [`Group::ALL`], so the bridge sends a single command to
+    /// all lights.
+    ///
+    /// # Examples
+    ///
+    /// Turn off all lights:
+    /// ```no_run
+    /// use huelib2::resource::group;
+    ///
+    /// # fn main() -> huelib2::Result<()> {
+    /// # use huelib2::Bridge;
+    /// # use std::net::{IpAddr, Ipv4Addr};
+    /// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+    /// // let bridge = Bridge::new(...);
+    /// bridge.set_all_lights_state(&group::StateModifier::new().with_on(false))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Group::ALL`]: resource::Group::ALL
+    pub fn set_all_lights_state(
+        &self,
+        modifier: &resource::group::StateModifier,
+    ) -> Result<ResponsesModified> {
+        self.set_group_state(resource::Group::ALL, modifier)
+    }
+
+    /// Lets all lights of a group perform one breathe cycle, so the group can be identified.
+    ///
+    /// This sends the `select` alert effect. To let the lights breathe for 15 seconds, set the
+    /// alert effect to [`Alert::LSelect`] with [`set_group_state`].
+    ///
+    /// [`Alert::LSelect`]: resource::Alert::LSelect
+    /// [`set_group_state`]: #method.set_group_state
+    pub fn identify_group<S>(&self, id: S) -> Result<ResponsesModified>
     where
         S: Into<String>,
     {
-        let id = id.into();
-        let light: resource::Light = parse_response(self.api_request(
-            format!("lights/{}", id),
-            RequestMethod::Get,
-            None,
-        )?)?;
-        Ok(light.with_id(id))
-    }
-
-    /// Returns all lights that are connected to the bridge.
-    pub fn get_all_lights(&self) -> Result<Vec<resource::Light>> {
-        let map: HashMap<String, resource::Light> =
-            parse_response(self.api_request("lights", RequestMethod::Get, None)?)?;
-        Ok(map
-            .into_iter()
-            .map(|(id, light)| light.with_id(id))
-            .collect())
+        let modifier = resource::group::StateModifier::new().with_alert(resource::Alert::Select);
+        self.set_group_state(id, &modifier)
     }
 
-    /// Starts searching for new lights.
-    ///
-    /// The bridge will open the network for 40 seconds. The overall search might take longer since
-    /// the configuration of new devices can take longer. If many devices are found the command
-    /// will have to be issued a second time after discovery time has elapsed. If the command is
-    /// received again during search the search will continue for at least an additional 40
-    /// seconds.
+    /// Stops the alert effect of all lights of a group, e.g. a running `lselect` breathe cycle.
     ///
-    /// When the search has finished, new lights will be available using the [`get_new_lights`]
-    /// function.
+    /// See [`recipes::breathe_group`] to stop the alert effect after a duration.
     ///
-    /// [`get_new_lights`]: #method.get_new_lights
-    pub fn search_new_lights(&self, scanner: &resource::light::Scanner) -> Result<()> {
-        scanner.execute(self)
+    /// [`recipes::breathe_group`]: crate::recipes::breathe_group
+    pub fn stop_group_alert<S>(&self, id: S) -> Result<ResponsesModified>
+    where
+        S: Into<String>,
+    {
+        let modifier = resource::group::StateModifier::new().with_alert(resource::Alert::None);
+        self.set_group_state(id, &modifier)
     }
 
-    /// Returns discovered lights.
-    pub fn get_new_lights(&self) -> Result<resource::Scan> {
-        parse_response(self.api_request("lights/new", RequestMethod::Get, None)?)
+    /// Modifies the state of multiple groups.
+    ///
+    /// The modifier is serialized once and dispatched according to the given policy. Returns the
+    /// result for every group identifier.
+    pub fn set_group_state_many<I, S>(
+        &self,
+        ids: I,
+        modifier: &resource::group::StateModifier,
+        policy: resource::DispatchPolicy,
+    ) -> Result<resource::ModifiedMany<String>>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        modifier.execute_many(self, ids.into_iter().map(Into::into), policy)
     }
 
-    /// Deletes a light from the bridge.
-    pub fn delete_light<S>(&self, id: S) -> Result<()>
+    /// Returns the locations of the lights in an entertainment group.
+    ///
+    /// Returns empty locations if the group is not an entertainment group.
+    pub fn get_entertainment_locations<S>(&self, id: S) -> Result<resource::group::Locations>
     where
         S: Into<String>,
     {
-        let response: Vec<Response<JsonValue>> = self.api_request(
-            &format!("lights/{}", id.into()),
-            RequestMethod::Delete,
-            None,
-        )?;
-        for i in response {
-            i.into_result()?;
-        }
-        Ok(())
+        Ok(self.get_group(id)?.locations.unwrap_or_default())
     }
 
-    /// Creates a new group.
-    pub fn create_group(&self, creator: &resource::group::Creator) -> Result<String> {
-        creator.execute(self)
+    /// Returns the changes that [`set_entertainment_locations`] would apply, without modifying
+    /// the group.
+    ///
+    /// [`set_entertainment_locations`]: #method.set_entertainment_locations
+    pub fn preview_entertainment_locations<S>(
+        &self,
+        id: S,
+        locations: &resource::group::Locations,
+    ) -> Result<Vec<resource::group::LocationChange>>
+    where
+        S: Into<String>,
+    {
+        Ok(self.get_entertainment_locations(id)?.diff(locations))
     }
 
-    /// Modifies attributes of a group.
-    pub fn set_group_attribute<S>(
+    /// Sets the lights of an entertainment group and their locations.
+    ///
+    /// The locations are validated against the number of channels per stream of the bridge
+    /// before the group is modified. Use [`preview_entertainment_locations`] to get the changes
+    /// without applying them.
+    ///
+    /// # Examples
+    ///
+    /// Move light `1` to the front left corner:
+    /// ```no_run
+    /// use huelib2::resource::group::Location;
+    ///
+    /// # fn main() -> huelib2::Result<()> {
+    /// # use huelib2::Bridge;
+    /// # use std::net::{IpAddr, Ipv4Addr};
+    /// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+    /// // let bridge = Bridge::new(...);
+    /// let mut locations = bridge.get_entertainment_locations("5")?;
+    /// locations.set("1", Location::new(-1.0, 1.0, 0.0));
+    /// for change in bridge.preview_entertainment_locations("5", &locations)? {
+    ///     println!("{:?}", change);
+    /// }
+    /// bridge.set_entertainment_locations("5", &locations)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`preview_entertainment_locations`]: #method.preview_entertainment_locations
+    pub fn set_entertainment_locations<S>(
         &self,
         id: S,
-        modifier: &resource::group::AttributeModifier,
+        locations: &resource::group::Locations,
     ) -> Result<ResponsesModified>
     where
         S: Into<String>,
     {
-        modifier.execute(self, id.into())
+        locations.validate(&self.get_capabilities()?.streaming)?;
+        locations.execute(self, id.into())
     }
 
-    /// Modifies the state of a group.
-    pub fn set_group_state<S>(
+    /// Starts or stops the streaming session of an entertainment group.
+    ///
+    /// If `owner` is given, the group is fetched first and [`Error::StreamOwned`] is returned
+    /// without modifying the group if a streaming session of another user is active. The owner of
+    /// a new streaming session is always the user of the bridge.
+    ///
+    /// [`Error::StreamOwned`]: crate::Error::StreamOwned
+    pub fn set_group_stream<S>(
         &self,
         id: S,
-        modifier: &resource::group::StateModifier,
+        active: bool,
+        owner: Option<&str>,
     ) -> Result<ResponsesModified>
     where
         S: Into<String>,
     {
-        modifier.execute(self, id.into())
+        let id = id.into();
+        if let Some(owner) = owner {
+            if let Some(stream) = self.get_group(id.as_str())?.stream {
+                match stream.owner {
+                    Some(v) if stream.active && v != owner => {
+                        return Err(crate::Error::StreamOwned(v))
+                    }
+                    _ => {}
+                }
+            }
+        }
+        resource::group::StreamModifier::new(active).execute(self, id)
     }
 
     /// Returns a group.
@@ -231,18 +1330,38 @@ impl Bridge {
         S: Into<String>,
     {
         let id = id.into();
-        let group: resource::Group = parse_response(self.api_request(
-            format!("groups/{}", id),
-            RequestMethod::Get,
-            None,
-        )?)?;
+        let group: resource::Group =
+            self.api_request(format!("groups/{}", id), RequestMethod::Get, None)?;
         Ok(group.with_id(id))
     }
 
+    /// Returns the group with the given name, ignoring case.
+    ///
+    /// A group whose name matches exactly is preferred over groups whose name only differs in
+    /// case. Returns [`Error::UnknownGroupName`] with the names of similar groups if no group
+    /// matches.
+    ///
+    /// [`Error::UnknownGroupName`]: crate::Error::UnknownGroupName
+    pub fn get_group_by_name(&self, name: &str) -> Result<resource::Group> {
+        let mut groups = self.get_all_groups()?;
+        let position = groups.iter().position(|v| v.name == name).or_else(|| {
+            groups
+                .iter()
+                .position(|v| v.name.to_lowercase() == name.to_lowercase())
+        });
+        match position {
+            Some(v) => Ok(groups.swap_remove(v)),
+            None => Err(crate::Error::UnknownGroupName {
+                name: name.to_owned(),
+                near_matches: util::near_matches(name, groups.iter().map(|v| v.name.as_str())),
+            }),
+        }
+    }
+
     /// Returns all groups.
     pub fn get_all_groups(&self) -> Result<Vec<resource::Group>> {
         let map: HashMap<String, resource::Group> =
-            parse_response(self.api_request("groups", RequestMethod::Get, None)?)?;
+            self.api_request("groups", RequestMethod::Get, None)?;
         Ok(map
             .into_iter()
             .map(|(id, group)| group.with_id(id))
@@ -254,8 +1373,8 @@ impl Bridge {
     where
         S: Into<String>,
     {
-        let response: Vec<Response<JsonValue>> = self.api_request(
-            &format!("groups/{}", id.into()),
+        let response: Vec<Response<JsonValue>> = self.api_request_responses(
+            format!("groups/{}", id.into()),
             RequestMethod::Delete,
             None,
         )?;
@@ -282,37 +1401,84 @@ impl Bridge {
         modifier.execute(self, id.into())
     }
 
+    /// Modifies the state of a single light in a scene.
+    ///
+    /// The other lights of the scene are not changed.
+    pub fn set_scene_light_state<S1, S2>(
+        &self,
+        scene_id: S1,
+        light_id: S2,
+        modifier: &resource::scene::LightStateModifier,
+    ) -> Result<ResponsesModified>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        modifier.execute(self, (scene_id.into(), light_id.into()))
+    }
+
     /// Returns a scene.
+    ///
+    /// Unlike [`get_all_scenes`], this includes the states of the lights in the scene.
+    ///
+    /// [`get_all_scenes`]: #method.get_all_scenes
     pub fn get_scene<S>(&self, id: S) -> Result<resource::Scene>
     where
         S: Into<String>,
     {
         let id = id.into();
-        let scene: resource::Scene = parse_response(self.api_request(
-            format!("scenes/{}", id),
-            RequestMethod::Get,
-            None,
-        )?)?;
+        let scene: resource::Scene =
+            self.api_request(format!("scenes/{}", id), RequestMethod::Get, None)?;
         Ok(scene.with_id(id))
     }
 
+    /// Creates a copy of a scene with another name and returns the identifier of the copy.
+    ///
+    /// The copy contains the same lights or group and the same light states as the scene.
+    pub fn duplicate_scene<S1, S2>(&self, id: S1, new_name: S2) -> Result<String>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        let scene = self.get_scene(id)?;
+        self.create_scene(&resource::scene::Creator::from_scene(
+            new_name.into(),
+            &scene,
+        ))
+    }
+
     /// Returns all scenes.
+    ///
+    /// The bridge does not return the states of the lights when listing scenes, so the
+    /// [`light_states`] of the scenes are `None`. Use [`get_scene`] to get them.
+    ///
+    /// [`light_states`]: resource::Scene::light_states
+    /// [`get_scene`]: #method.get_scene
     pub fn get_all_scenes(&self) -> Result<Vec<resource::Scene>> {
         let map: HashMap<String, resource::Scene> =
-            parse_response(self.api_request("scenes", RequestMethod::Get, None)?)?;
+            self.api_request("scenes", RequestMethod::Get, None)?;
         Ok(map
             .into_iter()
             .map(|(id, scene)| scene.with_id(id))
             .collect())
     }
 
+    /// Returns all group scenes that are linked to a group.
+    ///
+    /// Light scenes are not returned, even if they only contain lights of the group.
+    pub fn get_scenes_for_group(&self, group_id: &str) -> Result<Vec<resource::Scene>> {
+        let mut scenes = self.get_all_scenes()?;
+        scenes.retain(|v| v.group() == Some(group_id));
+        Ok(scenes)
+    }
+
     /// Deletes a scene.
     pub fn delete_scene<S>(&self, id: S) -> Result<()>
     where
         S: Into<String>,
     {
-        let response: Vec<Response<JsonValue>> = self.api_request(
-            &format!("scenes/{}", id.into()),
+        let response: Vec<Response<JsonValue>> = self.api_request_responses(
+            format!("scenes/{}", id.into()),
             RequestMethod::Delete,
             None,
         )?;
@@ -324,15 +1490,67 @@ impl Bridge {
 
     /// Returns the capabilities of resources.
     pub fn get_capabilities(&self) -> Result<resource::Capabilities> {
-        parse_response(self.api_request("capabilities", RequestMethod::Get, None)?)
+        self.api_request("capabilities", RequestMethod::Get, None)
     }
 
     /// Creates a new schedule and returns the identifier.
+    ///
+    /// The local time of the schedule is validated before the request is sent, see
+    /// [`LocalTime::validate`].
+    ///
+    /// [`LocalTime::validate`]: resource::schedule::LocalTime::validate
     pub fn create_schedule(&self, creator: &resource::schedule::Creator) -> Result<String> {
+        creator.local_time.validate()?;
         creator.execute(self)
     }
 
+    /// Creates schedules that simulate presence by switching groups on and off.
+    ///
+    /// Returns the identifiers of the created schedules. If a schedule cannot be created, the
+    /// schedules that were already created are deleted. The schedules can be removed with
+    /// [`disable_vacation_mode`].
+    ///
+    /// # Examples
+    ///
+    /// Switch the groups `1` and `2` on around 18:00 on weekdays:
+    /// ```no_run
+    /// use huelib2::{bridge::VacationMode, resource::schedule::Weekdays};
+    /// use chrono::NaiveTime;
+    ///
+    /// # fn main() -> huelib2::Result<()> {
+    /// # use huelib2::Bridge;
+    /// # use std::net::{IpAddr, Ipv4Addr};
+    /// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+    /// // let bridge = Bridge::new(...);
+    /// let mode = VacationMode::new(vec!["1".into(), "2".into()])
+    ///     .with_on_time(NaiveTime::from_hms_opt(18, 0, 0).unwrap())
+    ///     .with_weekdays(Weekdays::WORKDAYS);
+    /// let ids = bridge.enable_vacation_mode(&mode)?;
+    /// println!("Created schedules {:?}", ids);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`disable_vacation_mode`]: #method.disable_vacation_mode
+    pub fn enable_vacation_mode(&self, mode: &VacationMode) -> Result<Vec<String>> {
+        vacation::enable(self, mode)
+    }
+
+    /// Deletes all schedules that were created by [`enable_vacation_mode`].
+    ///
+    /// Returns the identifiers of the deleted schedules.
+    ///
+    /// [`enable_vacation_mode`]: #method.enable_vacation_mode
+    pub fn disable_vacation_mode(&self) -> Result<Vec<String>> {
+        vacation::disable(self)
+    }
+
     /// Modifies attributes of a schedule.
+    ///
+    /// The local time of the modifier is validated before the request is sent, see
+    /// [`LocalTime::validate`].
+    ///
+    /// [`LocalTime::validate`]: resource::schedule::LocalTime::validate
     pub fn set_schedule<S>(
         &self,
         id: S,
@@ -341,40 +1559,142 @@ impl Bridge {
     where
         S: Into<String>,
     {
+        if let Some(local_time) = &modifier.local_time {
+            local_time.validate()?;
+        }
         modifier.execute(self, id.into())
     }
 
+    /// Turns off a light or a group after the given duration.
+    ///
+    /// This creates a timer schedule that is deleted by the bridge after it ran, and returns its
+    /// identifier, so the countdown can be cancelled by deleting the schedule. Returns
+    /// [`Error::OutOfRange`] if the duration is not between 1 second and 23:59:59.
+    ///
+    /// # Examples
+    ///
+    /// Turn off group `1` in 30 minutes:
+    /// ```no_run
+    /// use huelib2::resource::StateTarget;
+    /// use std::time::Duration;
+    ///
+    /// # fn main() -> huelib2::Result<()> {
+    /// # use huelib2::Bridge;
+    /// # use std::net::{IpAddr, Ipv4Addr};
+    /// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+    /// // let bridge = Bridge::new(...);
+    /// let target = StateTarget::Group("1".into());
+    /// let id = bridge.turn_off_after(&target, Duration::from_secs(30 * 60))?;
+    /// // Cancel the countdown.
+    /// bridge.delete_schedule(id)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Error::OutOfRange`]: crate::Error::OutOfRange
+    pub fn turn_off_after(
+        &self,
+        target: &resource::StateTarget,
+        duration: Duration,
+    ) -> Result<String> {
+        use resource::schedule::{Command, Creator, LocalTime};
+
+        let local_time = LocalTime::timer(duration)?;
+        let command = match target {
+            resource::StateTarget::Light(id) => Command::light_state(
+                id.as_str(),
+                &resource::light::StateModifier::new().with_on(false),
+                self.username(),
+            )?,
+            resource::StateTarget::Group(id) => Command::group_state(
+                id.as_str(),
+                &resource::group::StateModifier::new().with_on(false),
+                self.username(),
+            )?,
+        };
+        let creator = Creator::new(command, local_time)
+            .with_description("huelib2 turn off".to_owned())
+            .with_auto_delete(true);
+        self.create_schedule(&creator)
+    }
+
+    /// Enables a schedule.
+    pub fn enable_schedule<S>(&self, id: S) -> Result<ResponsesModified>
+    where
+        S: Into<String>,
+    {
+        let modifier =
+            resource::schedule::Modifier::new().with_status(resource::schedule::Status::Enabled);
+        self.set_schedule(id, &modifier)
+    }
+
+    /// Disables a schedule.
+    pub fn disable_schedule<S>(&self, id: S) -> Result<ResponsesModified>
+    where
+        S: Into<String>,
+    {
+        let modifier =
+            resource::schedule::Modifier::new().with_status(resource::schedule::Status::Disabled);
+        self.set_schedule(id, &modifier)
+    }
+
+    /// Enables a disabled schedule or disables an enabled schedule.
+    ///
+    /// Returns the new status of the schedule.
+    pub fn toggle_schedule<S>(&self, id: S) -> Result<resource::schedule::Status>
+    where
+        S: Into<String>,
+    {
+        use resource::schedule::Status;
+
+        let id = id.into();
+        let status = match self.get_schedule(id.as_str())?.status {
+            Status::Enabled => Status::Disabled,
+            Status::Disabled => Status::Enabled,
+        };
+        let modifier = resource::schedule::Modifier::new().with_status(status);
+        self.set_schedule(id, &modifier)?;
+        Ok(status)
+    }
+
     /// Returns a schedule.
     pub fn get_schedule<S>(&self, id: S) -> Result<resource::Schedule>
     where
         S: Into<String>,
     {
         let id = id.into();
-        let schedule: resource::Schedule = parse_response(self.api_request(
-            format!("schedules/{}", id),
-            RequestMethod::Get,
-            None,
-        )?)?;
+        let schedule: resource::Schedule =
+            self.api_request(format!("schedules/{}", id), RequestMethod::Get, None)?;
         Ok(schedule.with_id(id))
     }
 
     /// Returns all schedules.
     pub fn get_all_schedules(&self) -> Result<Vec<resource::Schedule>> {
         let map: HashMap<String, resource::Schedule> =
-            parse_response(self.api_request("schedules", RequestMethod::Get, None)?)?;
+            self.api_request("schedules", RequestMethod::Get, None)?;
         Ok(map
             .into_iter()
             .map(|(id, schedule)| schedule.with_id(id))
             .collect())
     }
 
+    /// Returns all schedules whose command addresses the given resource.
+    pub fn get_schedules_for(
+        &self,
+        target: &resource::schedule::Target,
+    ) -> Result<Vec<resource::Schedule>> {
+        let mut schedules = self.get_all_schedules()?;
+        schedules.retain(|v| v.command.addresses(target));
+        Ok(schedules)
+    }
+
     /// Deletes a schedule.
     pub fn delete_schedule<S>(&self, id: S) -> Result<()>
     where
         S: Into<String>,
     {
-        let response: Vec<Response<JsonValue>> = self.api_request(
-            &format!("schedules/{}", id.into()),
+        let response: Vec<Response<JsonValue>> = self.api_request_responses(
+            format!("schedules/{}", id.into()),
             RequestMethod::Delete,
             None,
         )?;
@@ -407,18 +1727,15 @@ impl Bridge {
         S: Into<String>,
     {
         let id = id.into();
-        let resourcelink: resource::Resourcelink = parse_response(self.api_request(
-            format!("resourcelinks/{}", id),
-            RequestMethod::Get,
-            None,
-        )?)?;
+        let resourcelink: resource::Resourcelink =
+            self.api_request(format!("resourcelinks/{}", id), RequestMethod::Get, None)?;
         Ok(resourcelink.with_id(id))
     }
 
     /// Returns all resourcelinks.
     pub fn get_all_resourcelinks(&self) -> Result<Vec<resource::Resourcelink>> {
         let map: HashMap<String, resource::Resourcelink> =
-            parse_response(self.api_request("resourcelinks", RequestMethod::Get, None)?)?;
+            self.api_request("resourcelinks", RequestMethod::Get, None)?;
         Ok(map
             .into_iter()
             .map(|(id, resourcelink)| resourcelink.with_id(id))
@@ -430,8 +1747,8 @@ impl Bridge {
     where
         S: Into<String>,
     {
-        let response: Vec<Response<JsonValue>> = self.api_request(
-            &format!("resourcelinks/{}", id.into()),
+        let response: Vec<Response<JsonValue>> = self.api_request_responses(
+            format!("resourcelinks/{}", id.into()),
             RequestMethod::Delete,
             None,
         )?;
@@ -441,6 +1758,15 @@ impl Bridge {
         Ok(())
     }
 
+    /// Returns a handle for the sensor with the given identifier.
+    ///
+    /// See [`light`] for more information.
+    ///
+    /// [`light`]: #method.light
+    pub fn sensor<'a>(&'a self, id: &'a str) -> SensorHandle<'a> {
+        SensorHandle::new(self, id)
+    }
+
     /// Modifies attributes of a sensor.
     pub fn set_sensor_attribute<S>(
         &self,
@@ -466,13 +1792,16 @@ impl Bridge {
     }
 
     /// Modifies the configuration of a sensor.
-    pub fn set_sensor_config<S>(
-        &self,
-        id: S,
-        modifier: &resource::sensor::ConfigModifier,
-    ) -> Result<ResponsesModified>
+    ///
+    /// The modifier is either a [`ConfigModifier`] or a modifier for the configuration of a
+    /// specific sensor type, e.g. a [`PresenceConfigModifier`].
+    ///
+    /// [`ConfigModifier`]: resource::sensor::ConfigModifier
+    /// [`PresenceConfigModifier`]: resource::sensor::PresenceConfigModifier
+    pub fn set_sensor_config<S, M>(&self, id: S, modifier: &M) -> Result<ResponsesModified>
     where
         S: Into<String>,
+        M: resource::sensor::SensorConfigModifier,
     {
         modifier.execute(self, id.into())
     }
@@ -483,24 +1812,108 @@ impl Bridge {
         S: Into<String>,
     {
         let id = id.into();
-        let sensor: resource::Sensor = parse_response(self.api_request(
-            format!("sensors/{}", id),
-            RequestMethod::Get,
-            None,
-        )?)?;
+        let sensor: resource::Sensor =
+            self.api_request(format!("sensors/{}", id), RequestMethod::Get, None)?;
         Ok(sensor.with_id(id))
     }
 
     /// Returns all sensors that are connected to the bridge.
     pub fn get_all_sensors(&self) -> Result<Vec<resource::Sensor>> {
         let map: HashMap<String, resource::Sensor> =
-            parse_response(self.api_request("sensors", RequestMethod::Get, None)?)?;
+            self.api_request("sensors", RequestMethod::Get, None)?;
         Ok(map
             .into_iter()
             .map(|(id, sensor)| sensor.with_id(id))
             .collect())
     }
 
+    /// Creates a new sensor and returns the identifier.
+    ///
+    /// # Examples
+    ///
+    /// Create a generic status sensor that is used as variable by rules:
+    /// ```no_run
+    /// use huelib2::resource::sensor;
+    ///
+    /// # fn main() -> huelib2::Result<()> {
+    /// # use huelib2::Bridge;
+    /// # use std::net::{IpAddr, Ipv4Addr};
+    /// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+    /// // let bridge = Bridge::new(...);
+    /// let id = bridge.create_sensor(&sensor::Creator::generic_status("Mode"))?;
+    /// bridge.set_sensor_status(id, 2)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn create_sensor(&self, creator: &resource::sensor::Creator) -> Result<String> {
+        creator.execute(self)
+    }
+
+    /// Sets the status of a generic status sensor (`CLIPGenericStatus`).
+    pub fn set_sensor_status<S>(&self, id: S, status: i32) -> Result<ResponsesModified>
+    where
+        S: Into<String>,
+    {
+        self.set_sensor_state(id, &resource::sensor::StateModifier::Status(status))
+    }
+
+    /// Sets the flag of a generic flag sensor (`CLIPGenericFlag`).
+    pub fn set_sensor_flag<S>(&self, id: S, flag: bool) -> Result<ResponsesModified>
+    where
+        S: Into<String>,
+    {
+        self.set_sensor_state(id, &resource::sensor::StateModifier::Flag(flag))
+    }
+
+    /// Returns all sensors of the given kind.
+    ///
+    /// # Examples
+    ///
+    /// Print the names of all switches:
+    /// ```no_run
+    /// use huelib2::resource::sensor;
+    ///
+    /// # fn main() -> huelib2::Result<()> {
+    /// # use huelib2::Bridge;
+    /// # use std::net::{IpAddr, Ipv4Addr};
+    /// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+    /// // let bridge = Bridge::new(...);
+    /// for switch in bridge.get_sensors_by_kind(sensor::Kind::ZllSwitch)? {
+    ///     println!("{}", switch.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_sensors_by_kind(
+        &self,
+        kind: resource::sensor::Kind,
+    ) -> Result<Vec<resource::Sensor>> {
+        let mut sensors = self.get_all_sensors()?;
+        sensors.retain(|v| v.kind() == Some(kind));
+        Ok(sensors)
+    }
+
+    /// Returns the name and the battery state in percent of every battery powered device, keyed
+    /// by MAC address.
+    ///
+    /// See [`battery_levels`] for more information.
+    ///
+    /// [`battery_levels`]: resource::sensor::battery_levels
+    pub fn get_battery_levels(&self) -> Result<HashMap<String, (String, u8)>> {
+        Ok(resource::sensor::battery_levels(self.get_all_sensors()?))
+    }
+
+    /// Returns all Hue motion sensors that are connected to the bridge.
+    ///
+    /// See [`MotionSensorDevice::from_sensors`] for more information.
+    ///
+    /// [`MotionSensorDevice::from_sensors`]: resource::sensor::MotionSensorDevice::from_sensors
+    pub fn get_motion_sensors(&self) -> Result<Vec<resource::sensor::MotionSensorDevice>> {
+        Ok(resource::sensor::MotionSensorDevice::from_sensors(
+            self.get_all_sensors()?,
+        ))
+    }
+
     /// Starts searching for new sensors.
     ///
     /// The bridge will open the network for 40 seconds. The overall search might take longer since
@@ -518,8 +1931,13 @@ impl Bridge {
     }
 
     /// Returns discovered sensors.
+    ///
+    /// The scan contains the identifier and the name of every sensor that was found by the last
+    /// search, see [`search_new_sensors`].
+    ///
+    /// [`search_new_sensors`]: #method.search_new_sensors
     pub fn get_new_sensors(&self) -> Result<resource::Scan> {
-        parse_response(self.api_request("senors/new", RequestMethod::Get, None)?)
+        self.api_request("sensors/new", RequestMethod::Get, None)
     }
 
     /// Deletes a sensor from the bridge.
@@ -527,8 +1945,8 @@ impl Bridge {
     where
         S: Into<String>,
     {
-        let response: Vec<Response<JsonValue>> = self.api_request(
-            &format!("sensors/{}", id.into()),
+        let response: Vec<Response<JsonValue>> = self.api_request_responses(
+            format!("sensors/{}", id.into()),
             RequestMethod::Delete,
             None,
         )?;
@@ -562,27 +1980,157 @@ impl Bridge {
     {
         let id = id.into();
         let rule: resource::Rule =
-            parse_response(self.api_request(format!("rules/{}", id), RequestMethod::Get, None)?)?;
+            self.api_request(format!("rules/{}", id), RequestMethod::Get, None)?;
         Ok(rule.with_id(id))
     }
 
     /// Returns all rules.
     pub fn get_all_rules(&self) -> Result<Vec<resource::Rule>> {
         let map: HashMap<String, resource::Rule> =
-            parse_response(self.api_request("rules", RequestMethod::Get, None)?)?;
+            self.api_request("rules", RequestMethod::Get, None)?;
         Ok(map.into_iter().map(|(id, rule)| rule.with_id(id)).collect())
     }
 
+    /// Sets the status of multiple rules, e.g. to disable all motion rules at once.
+    ///
+    /// Returns the result for every rule identifier, so rules that could not be modified can be
+    /// reported individually. Errors returned by the bridge for a rule, e.g. because the rule
+    /// does not exist, are returned as `Err` for that rule.
+    ///
+    /// # Examples
+    ///
+    /// Disable the rules `1` and `2`:
+    /// ```no_run
+    /// use huelib2::resource::rule::Status;
+    ///
+    /// # fn main() -> huelib2::Result<()> {
+    /// # use huelib2::Bridge;
+    /// # use std::net::{IpAddr, Ipv4Addr};
+    /// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+    /// // let bridge = Bridge::new(...);
+    /// for (id, result) in bridge.set_rules_status(vec!["1", "2"], Status::Disabled)? {
+    ///     if let Err(e) = result {
+    ///         println!("Failed to disable rule {}: {}", id, e);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_rules_status<I, S>(
+        &self,
+        ids: I,
+        status: resource::rule::Status,
+    ) -> Result<resource::ModifiedMany<String>>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let modifier = resource::rule::Modifier::new().with_status(status);
+        let results = modifier.execute_many(
+            self,
+            ids.into_iter().map(Into::into),
+            resource::DispatchPolicy::Sequential,
+        )?;
+        Ok(results
+            .into_iter()
+            .map(|(id, result)| (id, result.and_then(check_responses)))
+            .collect())
+    }
+
+    /// Returns all rules whose conditions or actions address the given resource.
+    ///
+    /// This can be used to find the rules that break when a resource is deleted.
+    ///
+    /// # Examples
+    ///
+    /// Print the rules that use the sensor `2`:
+    /// ```no_run
+    /// use huelib2::resource::schedule::Target;
+    ///
+    /// # fn main() -> huelib2::Result<()> {
+    /// # use huelib2::Bridge;
+    /// # use std::net::{IpAddr, Ipv4Addr};
+    /// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+    /// // let bridge = Bridge::new(...);
+    /// for rule in bridge.find_rules_referencing(&Target::Sensor("2".into()))? {
+    ///     println!("{}", rule.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn find_rules_referencing(
+        &self,
+        target: &resource::schedule::Target,
+    ) -> Result<Vec<resource::Rule>> {
+        let mut rules = self.get_all_rules()?;
+        rules.retain(|v| v.references(target));
+        Ok(rules)
+    }
+
     /// Deletes a rule.
     pub fn delete_rule<S>(&self, id: S) -> Result<()>
     where
         S: Into<String>,
     {
-        let response: Vec<Response<JsonValue>> =
-            self.api_request(&format!("rules/{}", id.into()), RequestMethod::Delete, None)?;
+        let response: Vec<Response<JsonValue>> = self.api_request_responses(
+            format!("rules/{}", id.into()),
+            RequestMethod::Delete,
+            None,
+        )?;
         for i in response {
             i.into_result()?;
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::test_server::TestServer;
+    use crate::resource::{self, light};
+    use crate::Response;
+    use serde_json::json;
+
+    #[test]
+    fn resource_context() {
+        let server = TestServer::start(vec![
+            json!([{"error": {"type": 3, "address": "/lights/9", "description": "not available"}}]),
+            json!([{"error": {"type": 201, "address": "/lights/1/state/bri", "description": ""}}]),
+        ]);
+        let bridge = server.bridge();
+        let error = bridge.get_light("9").unwrap_err();
+        assert!(error.is_not_found());
+        assert_eq!(error.resource().unwrap().endpoint, "lights/9");
+
+        let modifier = light::StateModifier::new().with_brightness(resource::Adjust::Override(10));
+        let responses = bridge.set_light_state("1", &modifier).unwrap();
+        let error = responses[0].clone().into_result().unwrap_err();
+        let resource = error.resource.unwrap();
+        assert_eq!(resource.kind, "lights");
+        assert_eq!(resource.id.as_deref(), Some("1"));
+        assert_eq!(resource.endpoint, "lights/1/state");
+        server.finish();
+    }
+
+    #[test]
+    fn add_light_to_group() {
+        let group = |lights: &[&str]| json!({"name": "Kitchen", "lights": lights, "type": "Room", "class": "Kitchen"});
+        let server = TestServer::start(vec![
+            group(&["1"]),
+            json!([{"error": {
+                "type": 306,
+                "address": "/groups/1/lights",
+                "description": "Light already in another room"
+            }}]),
+            group(&["1"]),
+            json!([{"success": {"/groups/1/lights": ["1", "3"]}}]),
+            group(&["1", "3", "4"]),
+        ]);
+        let bridge = server.bridge();
+        let responses = bridge.add_light_to_group("1", "2").unwrap();
+        assert!(matches!(responses[..], [Response::Error(_)]));
+        let error = bridge.add_light_to_group("1", "3").unwrap_err();
+        assert!(matches!(error, crate::Error::GroupConflict(v) if v == "1"));
+        assert_eq!(server.finish().len(), 5);
+    }
+}
@@ -0,0 +1,123 @@
+use crate::resource::RequestMethod;
+use serde_json::{json, Value as JsonValue};
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+/// A request that was recorded by a bridge in dry-run mode instead of being sent.
+///
+/// See [`Bridge::with_dry_run`] for more information.
+///
+/// [`Bridge::with_dry_run`]: super::Bridge::with_dry_run
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordedRequest {
+    /// HTTP method of the request.
+    pub method: RequestMethod,
+    /// Path of the request relative to the API URL of the user, e.g. `lights/1/state`.
+    pub path: String,
+    /// Body of the request.
+    pub body: Option<JsonValue>,
+}
+
+/// Shared storage for recorded requests.
+///
+/// Clones of a recorder share the same storage. Two recorders are equal if they share the same
+/// storage.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Recorder(Arc<Mutex<Vec<RecordedRequest>>>);
+
+impl Recorder {
+    /// Records a request and returns the response that the bridge would likely have sent.
+    pub(crate) fn record(
+        &self,
+        method: RequestMethod,
+        path: &str,
+        body: Option<JsonValue>,
+    ) -> JsonValue {
+        let response = simulated_response(method, path, body.as_ref());
+        self.0
+            .lock()
+            .expect("recorder mutex is poisoned")
+            .push(RecordedRequest {
+                method,
+                path: path.to_owned(),
+                body,
+            });
+        response
+    }
+
+    /// Removes and returns all recorded requests.
+    pub(crate) fn take(&self) -> Vec<RecordedRequest> {
+        std::mem::take(&mut *self.0.lock().expect("recorder mutex is poisoned"))
+    }
+}
+
+impl PartialEq for Recorder {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Recorder {}
+
+impl Hash for Recorder {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (Arc::as_ptr(&self.0) as usize).hash(state);
+    }
+}
+
+/// Returns a response in the format the bridge uses to acknowledge a request.
+///
+/// Modifications acknowledge every attribute in the body, deletions acknowledge the path and
+/// creations return an empty identifier.
+fn simulated_response(method: RequestMethod, path: &str, body: Option<&JsonValue>) -> JsonValue {
+    match method {
+        RequestMethod::Put => match body {
+            Some(JsonValue::Object(map)) => map
+                .iter()
+                .map(|(k, v)| json!({"success": {format!("/{}/{}", path, k): v}}))
+                .collect(),
+            _ => json!([]),
+        },
+        RequestMethod::Post => json!([{"success": {"id": ""}}]),
+        RequestMethod::Delete => json!([{"success": format!("/{} deleted", path)}]),
+        RequestMethod::Get => json!([]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::{light, Modifier};
+    use crate::{response::Modified, Bridge, Response};
+    use std::net::{IpAddr, Ipv4Addr};
+
+    #[test]
+    fn record_modifier() {
+        let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "user").with_dry_run();
+        let modifier = light::StateModifier::new().with_on(true);
+        let responses = modifier.execute(&bridge, "1".into()).unwrap();
+        assert_eq!(
+            responses,
+            vec![Response::Success(Modified {
+                address: "/lights/1/state/on".into(),
+                value: json!(true),
+            })]
+        );
+        let expected = vec![RecordedRequest {
+            method: RequestMethod::Put,
+            path: "lights/1/state".into(),
+            body: Some(json!({"on": true})),
+        }];
+        assert_eq!(bridge.take_recorded_requests(), expected);
+        assert_eq!(bridge.take_recorded_requests(), vec![]);
+    }
+
+    #[test]
+    fn simulated_response() {
+        let value = super::simulated_response(RequestMethod::Delete, "lights/1", None);
+        assert_eq!(value, json!([{"success": "/lights/1 deleted"}]));
+
+        let value = super::simulated_response(RequestMethod::Post, "groups", Some(&json!({})));
+        assert_eq!(value, json!([{"success": {"id": ""}}]));
+    }
+}
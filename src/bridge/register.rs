@@ -1,6 +1,10 @@
 use crate::{Error, Response, Result};
 use serde::Deserialize;
 use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+/// Time to wait between two registration attempts while polling.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
 
 /// Registers a new user on a bridge.
 ///
@@ -25,21 +29,29 @@ pub fn register_user<S>(ip_address: IpAddr, devicetype: S) -> Result<String>
 where
     S: AsRef<str>,
 {
-    let url = format!("http://{}/api", ip_address);
+    register_user_with_base_url(super::base_url::from_ip(ip_address), devicetype)
+}
+
+/// Registers a new user on a bridge that is served under a base URL.
+///
+/// This behaves like [`register_user`] but takes a base URL like [`Bridge::from_base_url`].
+///
+/// [`Bridge::from_base_url`]: super::Bridge::from_base_url
+pub fn register_user_with_base_url<S1, S2>(base_url: S1, devicetype: S2) -> Result<String>
+where
+    S1: AsRef<str>,
+    S2: AsRef<str>,
+{
+    let (base_url, _) = super::base_url::parse(base_url.as_ref())?;
+    let url = format!("{}/api", base_url);
     let body = format!("{{\"devicetype\":\"{}\"}}", devicetype.as_ref());
     let http_response = ureq::post(&url).send_string(&body)?;
     #[derive(Deserialize)]
     struct User {
         username: String,
     }
-    let mut responses: Vec<Response<User>> = http_response.into_json()?;
-    match responses.pop() {
-        Some(v) => match v.into_result() {
-            Ok(user) => Ok(user.username),
-            Err(e) => Err(Error::Response(e)),
-        },
-        None => Err(Error::GetUsername),
-    }
+    let responses: Vec<Response<User>> = http_response.into_json()?;
+    Ok(first_success(responses)?.username)
 }
 
 /// Registers a new user on a bridge with a clientkey.
@@ -69,7 +81,7 @@ pub fn register_user_with_clientkey<S>(
 where
     S: AsRef<str>,
 {
-    let url = format!("http://{}/api", ip_address);
+    let url = format!("{}/api", super::base_url::from_ip(ip_address));
     let body = format!(
         "{{\"devicetype\":\"{}\",\"generateclientkey\":true}}",
         devicetype.as_ref()
@@ -80,12 +92,169 @@ where
         username: String,
         clientkey: String,
     }
-    let mut responses: Vec<Response<User>> = http_response.into_json()?;
-    match responses.pop() {
-        Some(v) => match v.into_result() {
-            Ok(user) => Ok((user.username, user.clientkey)),
-            Err(e) => Err(Error::Response(e)),
-        },
-        None => Err(Error::GetUsername),
+    let responses: Vec<Response<User>> = http_response.into_json()?;
+    let user = first_success(responses)?;
+    Ok((user.username, user.clientkey))
+}
+
+/// Registers a new user on a bridge, waiting until the link button is pressed.
+///
+/// The registration is retried every second while the link button of the bridge has not been
+/// pressed. If the button is not pressed within the timeout, the error of the last attempt is
+/// returned. Other errors are returned immediately.
+///
+/// # Examples
+///
+/// Wait up to 30 seconds for the link button to be pressed:
+/// ```no_run
+/// use huelib2::bridge;
+/// use std::net::{IpAddr, Ipv4Addr};
+/// use std::time::Duration;
+///
+/// # fn main() -> Result<(), huelib2::Error> {
+/// let bridge_ip = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2));
+/// println!("Press the link button of the bridge");
+/// let username = bridge::register_user_polling(bridge_ip, "example", Duration::from_secs(30))?;
+/// println!("Registered user with username `{}`", username);
+/// # Ok(())
+/// # }
+/// ```
+pub fn register_user_polling<S>(
+    ip_address: IpAddr,
+    devicetype: S,
+    timeout: Duration,
+) -> Result<String>
+where
+    S: AsRef<str>,
+{
+    register_user_polling_with_base_url(super::base_url::from_ip(ip_address), devicetype, timeout)
+}
+
+/// Registers a new user on a bridge that is served under a base URL, waiting until the link button
+/// is pressed.
+///
+/// This behaves like [`register_user_polling`] but takes a base URL like
+/// [`Bridge::from_base_url`].
+///
+/// [`Bridge::from_base_url`]: super::Bridge::from_base_url
+pub fn register_user_polling_with_base_url<S1, S2>(
+    base_url: S1,
+    devicetype: S2,
+    timeout: Duration,
+) -> Result<String>
+where
+    S1: AsRef<str>,
+    S2: AsRef<str>,
+{
+    poll(timeout, POLL_INTERVAL, || {
+        register_user_with_base_url(base_url.as_ref(), devicetype.as_ref())
+    })
+}
+
+/// Registers a new user on a bridge with a clientkey, waiting until the link button is pressed.
+///
+/// This behaves like [`register_user_polling`] but also generates a clientkey. See the
+/// [`register_user_with_clientkey`] function for more information.
+pub fn register_user_with_clientkey_polling<S>(
+    ip_address: IpAddr,
+    devicetype: S,
+    timeout: Duration,
+) -> Result<(String, String)>
+where
+    S: AsRef<str>,
+{
+    poll(timeout, POLL_INTERVAL, || {
+        register_user_with_clientkey(ip_address, devicetype.as_ref())
+    })
+}
+
+/// Calls `f` at the given interval until it does not fail because the link button was not pressed
+/// or the timeout has passed.
+fn poll<T, F>(timeout: Duration, interval: Duration, mut f: F) -> Result<T>
+where
+    F: FnMut() -> Result<T>,
+{
+    let start = Instant::now();
+    loop {
+        match f() {
+            Err(e) if e.is_link_button() && start.elapsed() + interval < timeout => {
+                std::thread::sleep(interval)
+            }
+            v => return v,
+        }
+    }
+}
+
+/// Returns the first successful response, or all errors if no response was successful.
+fn first_success<T>(responses: Vec<Response<T>>) -> Result<T> {
+    let mut errors = Vec::new();
+    for response in responses {
+        match response.into_result() {
+            Ok(v) => return Ok(v),
+            Err(e) => errors.push(e),
+        }
+    }
+    if errors.is_empty() {
+        Err(Error::GetUsername)
+    } else {
+        Err(Error::from_response_errors(errors))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::response::{Error as ResponseError, ErrorKind};
+
+    fn link_button_error() -> Error {
+        Error::Response(ResponseError {
+            kind: ErrorKind::LinkButtonNotPressed,
+            address: "".into(),
+            description: "link button not pressed".into(),
+            resource: None,
+        })
+    }
+
+    #[test]
+    fn poll() {
+        let mut attempts = 0;
+        let result = super::poll(Duration::from_secs(1), Duration::from_millis(1), || {
+            attempts += 1;
+            match attempts {
+                3 => Ok(attempts),
+                _ => Err(link_button_error()),
+            }
+        });
+        assert_eq!(result.unwrap(), 3);
+
+        let result: Result<()> =
+            super::poll(Duration::from_millis(10), Duration::from_millis(1), || {
+                Err(link_button_error())
+            });
+        assert!(result.unwrap_err().is_link_button());
+
+        let mut attempts = 0;
+        let result: Result<()> =
+            super::poll(Duration::from_secs(1), Duration::from_millis(1), || {
+                attempts += 1;
+                Err(Error::GetUsername)
+            });
+        assert!(matches!(result, Err(Error::GetUsername)));
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn register_user_with_base_url() {
+        let server = crate::bridge::test_server::TestServer::start(vec![
+            serde_json::json!([{"success": {"username": "new_user"}}]),
+        ]);
+        let base_url = server.bridge().base_url().to_owned();
+        let username = super::register_user_with_base_url(&base_url, "test").unwrap();
+        assert_eq!(username, "new_user");
+        let requests = server.finish();
+        assert_eq!(
+            requests[0].body,
+            Some(serde_json::json!({"devicetype": "test"}))
+        );
     }
 }
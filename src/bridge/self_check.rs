@@ -0,0 +1,238 @@
+use super::Bridge;
+use crate::resource::config::UnauthenticatedConfig;
+use crate::{resource, Error, Result};
+use chrono::Duration;
+
+/// Minimum API version that is supported by this crate.
+#[cfg(not(feature = "old-api"))]
+const MIN_API_VERSION: (u32, u32) = (1, 37);
+/// Minimum API version that is supported by this crate.
+#[cfg(feature = "old-api")]
+const MIN_API_VERSION: (u32, u32) = (1, 16);
+
+/// Maximum clock drift that is considered healthy.
+const MAX_CLOCK_DRIFT_SECONDS: i64 = 60;
+
+/// Report of the verifications done by [`Bridge::self_check`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct SelfCheckReport {
+    /// Whether the bridge could be reached.
+    pub reachable: bool,
+    /// Whether the user is authorized to access the bridge.
+    pub authorized: bool,
+    /// Difference between the time of the bridge and the local time.
+    ///
+    /// Positive values mean that the bridge is ahead of the local time.
+    pub clock_drift: Option<Duration>,
+    /// Software version of the bridge.
+    pub software_version: Option<String>,
+    /// Version of the Philips Hue API.
+    pub api_version: Option<String>,
+    /// Whether the version of the Philips Hue API is supported by this crate.
+    pub api_version_supported: Option<bool>,
+    /// Capabilities of the bridge, used to determine the capacity headroom.
+    pub capabilities: Option<resource::Capabilities>,
+    /// Identifiers of lights that cannot be reached by the bridge.
+    pub unreachable_lights: Vec<String>,
+    /// Identifiers of sensors that cannot be reached by the bridge.
+    pub unreachable_sensors: Vec<String>,
+}
+
+impl SelfCheckReport {
+    fn unreachable() -> Self {
+        Self {
+            reachable: false,
+            authorized: false,
+            clock_drift: None,
+            software_version: None,
+            api_version: None,
+            api_version_supported: None,
+            capabilities: None,
+            unreachable_lights: Vec::new(),
+            unreachable_sensors: Vec::new(),
+        }
+    }
+
+    /// Returns the names of resource kinds that have no capacity left on the bridge.
+    pub fn exhausted_resources(&self) -> Vec<&'static str> {
+        let capabilities = match &self.capabilities {
+            Some(v) => v,
+            None => return Vec::new(),
+        };
+        let resources = [
+            ("lights", capabilities.lights.available),
+            ("groups", capabilities.groups.available),
+            ("sensors", capabilities.sensors.available),
+            ("scenes", capabilities.scenes.available),
+            ("schedules", capabilities.schedules.available),
+            ("rules", capabilities.rules.available),
+            ("resourcelinks", capabilities.resourcelinks.available),
+        ];
+        resources
+            .iter()
+            .filter(|(_, available)| *available == 0)
+            .map(|(name, _)| *name)
+            .collect()
+    }
+
+    /// Returns whether all verifications succeeded.
+    ///
+    /// This is the case if the bridge is reachable, the user is authorized, the clock drift is at
+    /// most one minute, the API version is supported, no resource kind is exhausted and all
+    /// devices are reachable.
+    pub fn is_healthy(&self) -> bool {
+        self.reachable
+            && self.authorized
+            && self
+                .clock_drift
+                .is_some_and(|v| v.num_seconds().abs() <= MAX_CLOCK_DRIFT_SECONDS)
+            && self.api_version_supported == Some(true)
+            && self.exhausted_resources().is_empty()
+            && self.unreachable_lights.is_empty()
+            && self.unreachable_sensors.is_empty()
+    }
+}
+
+/// Result of [`Bridge::verify`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Verification {
+    /// The bridge is reachable and the user is whitelisted.
+    Ok(UnauthenticatedConfig),
+    /// The bridge is reachable, but the user is not whitelisted.
+    Unauthorized(UnauthenticatedConfig),
+    /// The device at the IP address did not respond like a Philips Hue bridge.
+    NotABridge,
+    /// No connection to the IP address could be established.
+    Unreachable,
+}
+
+impl Verification {
+    /// Returns whether the bridge is reachable and the user is whitelisted.
+    pub fn is_ok(&self) -> bool {
+        matches!(self, Self::Ok(_))
+    }
+}
+
+/// Returns whether the API version in the format `<major>.<minor>.<patch>` is supported.
+fn is_api_version_supported(value: &str) -> Option<bool> {
+    let mut parts = value.split('.').map(str::parse::<u32>);
+    let major = parts.next()?.ok()?;
+    let minor = parts.next()?.ok()?;
+    Some((major, minor) >= MIN_API_VERSION)
+}
+
+pub(super) fn self_check(bridge: &Bridge) -> Result<SelfCheckReport> {
+    let mut report = SelfCheckReport::unreachable();
+    match bridge.get_capabilities() {
+        Ok(v) => {
+            report.reachable = true;
+            report.authorized = true;
+            report.capabilities = Some(v);
+        }
+        Err(e) if e.is_unauthorized() => {
+            report.reachable = true;
+            return Ok(report);
+        }
+        Err(Error::Request(v)) if matches!(*v, ureq::Error::Transport(_)) => {
+            return Ok(report);
+        }
+        Err(e) => return Err(e),
+    }
+
+    let config = bridge.get_config()?;
+    report.clock_drift = Some(config.current_time - chrono::Utc::now().naive_utc());
+    report.api_version_supported = is_api_version_supported(&config.api_version);
+    report.api_version = Some(config.api_version);
+    report.software_version = Some(config.software_version);
+
+    let mut unreachable_lights: Vec<String> = bridge
+        .get_all_lights()?
+        .into_iter()
+        .filter(|v| !v.state.reachable)
+        .map(|v| v.id)
+        .collect();
+    unreachable_lights.sort();
+    report.unreachable_lights = unreachable_lights;
+
+    let mut unreachable_sensors: Vec<String> = bridge
+        .get_all_sensors()?
+        .into_iter()
+        .filter(|v| v.config.reachable == Some(false))
+        .map(|v| v.id)
+        .collect();
+    unreachable_sensors.sort();
+    report.unreachable_sensors = unreachable_sensors;
+
+    Ok(report)
+}
+
+pub(super) fn verify(bridge: &Bridge) -> Result<Verification> {
+    let config = match super::unauthenticated::get_unauthenticated_config_at(bridge.base_url()) {
+        Ok(v) => v,
+        Err(Error::Request(e)) if matches!(*e, ureq::Error::Transport(_)) => {
+            return Ok(Verification::Unreachable);
+        }
+        Err(Error::Request(_)) | Err(Error::ParseHttpResponse(_)) => {
+            return Ok(Verification::NotABridge);
+        }
+        Err(e) => return Err(e),
+    };
+    // The configuration is also returned for unknown users, so the capabilities are used to
+    // verify that the user is whitelisted.
+    match bridge.get_capabilities() {
+        Ok(_) => Ok(Verification::Ok(config)),
+        Err(e) if e.is_unauthorized() => Ok(Verification::Unauthorized(config)),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn api_version_supported() {
+        assert_eq!(is_api_version_supported("1.41.0"), Some(true));
+        assert_eq!(is_api_version_supported("2.0.0"), Some(true));
+        assert_eq!(is_api_version_supported("1.15.0"), Some(false));
+        assert_eq!(is_api_version_supported("invalid"), None);
+    }
+
+    #[test]
+    fn is_healthy() {
+        let report = SelfCheckReport::unreachable();
+        assert!(!report.is_healthy());
+
+        let report = SelfCheckReport {
+            reachable: true,
+            authorized: true,
+            clock_drift: Some(Duration::seconds(-5)),
+            software_version: Some("1941132080".into()),
+            api_version: Some("1.41.0".into()),
+            api_version_supported: Some(true),
+            capabilities: None,
+            unreachable_lights: Vec::new(),
+            unreachable_sensors: Vec::new(),
+        };
+        assert!(report.is_healthy());
+
+        let report = SelfCheckReport {
+            unreachable_lights: vec!["1".into()],
+            ..report
+        };
+        assert!(!report.is_healthy());
+    }
+
+    #[test]
+    fn verify_unreachable() {
+        // Bind a port and release it again so that nothing listens on it.
+        let port = std::net::TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .and_then(|v| v.local_addr())
+            .unwrap()
+            .port();
+        let url = format!("http://{}:{}", Ipv4Addr::LOCALHOST, port);
+        let bridge = Bridge::from_base_url(url, "user").unwrap();
+        assert_eq!(bridge.verify().unwrap(), Verification::Unreachable);
+    }
+}
@@ -0,0 +1,368 @@
+use super::{Bridge, ResponsesModified};
+use crate::resource::{self, light};
+use crate::response::{ErrorKind, Modified};
+use crate::{Error, Response, Result};
+use std::collections::BTreeMap;
+
+/// A resource together with the identifier of the bridge it belongs to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnedResource<T> {
+    /// Identifier of the bridge the resource belongs to.
+    pub bridge_id: String,
+    /// The resource.
+    pub resource: T,
+}
+
+/// Collection of multiple bridges.
+///
+/// Bridges are identified by the identifier that is used when inserting them, usually the
+/// bridge identifier from the configuration. Requests that are sent to all bridges run
+/// concurrently and tag every returned resource with the identifier of its bridge.
+///
+/// Lights can also be addressed by their unique identifier, independent of the bridge they are
+/// connected to. The set keeps an index of the unique identifiers, which is built with
+/// [`refresh_light_index`] and refreshed automatically when a light is not found.
+///
+/// # Examples
+///
+/// Get the lights of two bridges:
+/// ```no_run
+/// use huelib2::bridge::BridgeSet;
+/// use huelib2::Bridge;
+/// use std::net::{IpAddr, Ipv4Addr};
+///
+/// # fn main() -> huelib2::Result<()> {
+/// let mut bridges = BridgeSet::new();
+/// bridges.insert(
+///     "001788fffe000000",
+///     Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "username"),
+/// );
+/// bridges.insert(
+///     "001788fffe000001",
+///     Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 3)), "username"),
+/// );
+/// for light in bridges.get_all_lights()? {
+///     println!("{}: {}", light.bridge_id, light.resource.name);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`refresh_light_index`]: #method.refresh_light_index
+#[derive(Clone, Debug, Default)]
+pub struct BridgeSet {
+    bridges: BTreeMap<String, Bridge>,
+    /// Maps the lowercase unique identifiers of lights to the bridge and light identifiers.
+    light_index: BTreeMap<String, LightLocation>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct LightLocation {
+    bridge_id: String,
+    light_id: String,
+}
+
+impl BridgeSet {
+    /// Creates a new, empty [`BridgeSet`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a bridge and returns the bridge that previously had the same identifier.
+    pub fn insert<S>(&mut self, bridge_id: S, bridge: Bridge) -> Option<Bridge>
+    where
+        S: Into<String>,
+    {
+        let bridge_id = bridge_id.into();
+        self.remove_from_index(&bridge_id);
+        self.bridges.insert(bridge_id, bridge)
+    }
+
+    /// Adds a bridge using the bridge identifier from its configuration.
+    ///
+    /// Returns the bridge identifier in lowercase.
+    pub fn add(&mut self, bridge: Bridge) -> Result<String> {
+        let bridge_id = bridge.get_config()?.bridge_id.to_lowercase();
+        self.insert(bridge_id.clone(), bridge);
+        Ok(bridge_id)
+    }
+
+    /// Removes a bridge and returns it.
+    pub fn remove(&mut self, bridge_id: &str) -> Option<Bridge> {
+        self.remove_from_index(bridge_id);
+        self.bridges.remove(bridge_id)
+    }
+
+    /// Returns the bridge with the given identifier.
+    pub fn get(&self, bridge_id: &str) -> Option<&Bridge> {
+        self.bridges.get(bridge_id)
+    }
+
+    /// Returns an iterator over the bridge identifiers and the bridges, ordered by identifier.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Bridge)> {
+        self.bridges.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    /// Returns the number of bridges.
+    pub fn len(&self) -> usize {
+        self.bridges.len()
+    }
+
+    /// Returns whether there are no bridges.
+    pub fn is_empty(&self) -> bool {
+        self.bridges.is_empty()
+    }
+
+    /// Returns all lights of all bridges.
+    ///
+    /// If a request fails, the first error in the order of the bridge identifiers is returned.
+    pub fn get_all_lights(&self) -> Result<Vec<OwnedResource<resource::Light>>> {
+        self.fan_out(Bridge::get_all_lights)
+    }
+
+    /// Returns all sensors of all bridges.
+    ///
+    /// If a request fails, the first error in the order of the bridge identifiers is returned.
+    pub fn get_all_sensors(&self) -> Result<Vec<OwnedResource<resource::Sensor>>> {
+        self.fan_out(Bridge::get_all_sensors)
+    }
+
+    /// Returns all groups of all bridges.
+    ///
+    /// If a request fails, the first error in the order of the bridge identifiers is returned.
+    pub fn get_all_groups(&self) -> Result<Vec<OwnedResource<resource::Group>>> {
+        self.fan_out(Bridge::get_all_groups)
+    }
+
+    /// Rebuilds the index of the unique identifiers of lights by requesting the lights of all
+    /// bridges.
+    ///
+    /// The index is left unchanged if a request fails.
+    pub fn refresh_light_index(&mut self) -> Result<()> {
+        let lights = self.get_all_lights()?;
+        self.light_index = lights
+            .into_iter()
+            .map(|v| {
+                let location = LightLocation {
+                    bridge_id: v.bridge_id,
+                    light_id: v.resource.id,
+                };
+                (v.resource.unique_id.to_lowercase(), location)
+            })
+            .collect();
+        Ok(())
+    }
+
+    /// Returns the bridge identifier and the light identifier of the light with the given unique
+    /// identifier, if it is in the index.
+    ///
+    /// The unique identifier is compared case-insensitively.
+    pub fn find_light(&self, unique_id: &str) -> Option<(&str, &str)> {
+        self.light_index
+            .get(&unique_id.to_lowercase())
+            .map(|v| (v.bridge_id.as_str(), v.light_id.as_str()))
+    }
+
+    /// Modifies the state of the light with the given unique identifier on the bridge it is
+    /// connected to.
+    ///
+    /// If the light is not in the index, or the bridge reports that the light does not exist
+    /// anymore, the index is refreshed and the request is sent again. Returns
+    /// [`Error::UnknownUniqueId`] if no bridge has the light.
+    pub fn set_light_state_by_unique_id(
+        &mut self,
+        unique_id: &str,
+        modifier: &light::StateModifier,
+    ) -> Result<ResponsesModified> {
+        if let Some(result) = self.try_set_light_state(unique_id, modifier) {
+            match result {
+                Ok(responses) if responses.iter().any(is_not_found) => {}
+                v => return v,
+            }
+        }
+        self.refresh_light_index()?;
+        self.try_set_light_state(unique_id, modifier)
+            .unwrap_or_else(|| Err(Error::UnknownUniqueId(unique_id.to_owned())))
+    }
+
+    /// Sends the request to the bridge of the light, if the light is in the index.
+    fn try_set_light_state(
+        &self,
+        unique_id: &str,
+        modifier: &light::StateModifier,
+    ) -> Option<Result<ResponsesModified>> {
+        let (bridge_id, light_id) = self.find_light(unique_id)?;
+        let bridge = self.bridges.get(bridge_id)?;
+        Some(bridge.set_light_state(light_id, modifier))
+    }
+
+    /// Removes the lights of a bridge from the index.
+    fn remove_from_index(&mut self, bridge_id: &str) {
+        self.light_index.retain(|_, v| v.bridge_id != bridge_id);
+    }
+
+    /// Calls `f` for every bridge concurrently and tags the results with the bridge identifier.
+    fn fan_out<T, F>(&self, f: F) -> Result<Vec<OwnedResource<T>>>
+    where
+        T: Send,
+        F: Fn(&Bridge) -> Result<Vec<T>> + Sync,
+    {
+        let f = &f;
+        let results: Vec<(&String, Result<Vec<T>>)> = std::thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .bridges
+                .iter()
+                .map(|(id, bridge)| (id, scope.spawn(move || f(bridge))))
+                .collect();
+            handles
+                .into_iter()
+                .map(|(id, handle)| (id, handle.join().expect("request thread panicked")))
+                .collect()
+        });
+        let mut resources = Vec::new();
+        for (bridge_id, result) in results {
+            resources.extend(result?.into_iter().map(|resource| OwnedResource {
+                bridge_id: bridge_id.clone(),
+                resource,
+            }));
+        }
+        Ok(resources)
+    }
+}
+
+/// Returns whether the response reports that the light does not exist.
+fn is_not_found(response: &Response<Modified>) -> bool {
+    matches!(response, Response::Error(e) if e.kind == ErrorKind::ResourceNotAvailable)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bridge::test_server::TestServer;
+    use serde_json::json;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    #[test]
+    fn fan_out() {
+        let mut bridges = BridgeSet::new();
+        for (id, ip) in [("b", 3), ("a", 2)] {
+            let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, ip)), "user");
+            assert!(bridges.insert(id, bridge).is_none());
+        }
+        assert_eq!(bridges.len(), 2);
+
+        let resources = bridges
+            .fan_out(|bridge| Ok(vec![bridge.base_url().to_owned()]))
+            .unwrap();
+        assert_eq!(
+            resources,
+            vec![
+                OwnedResource {
+                    bridge_id: "a".into(),
+                    resource: "http://192.168.1.2".to_owned(),
+                },
+                OwnedResource {
+                    bridge_id: "b".into(),
+                    resource: "http://192.168.1.3".to_owned(),
+                },
+            ]
+        );
+
+        let result = bridges.fan_out::<(), _>(|bridge| match bridge.base_url() {
+            "http://192.168.1.3" => Err(crate::Error::GetUsername),
+            _ => Ok(Vec::new()),
+        });
+        assert!(matches!(result, Err(crate::Error::GetUsername)));
+    }
+
+    #[test]
+    fn light_index() {
+        let mut bridges = BridgeSet::new();
+        for (id, ip) in [("a", 2), ("b", 3)] {
+            let bridge =
+                Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, ip)), "user").with_dry_run();
+            bridges.insert(id, bridge);
+        }
+        let location = |bridge_id: &str, light_id: &str| LightLocation {
+            bridge_id: bridge_id.into(),
+            light_id: light_id.into(),
+        };
+        bridges
+            .light_index
+            .insert("00:17:88:01:00:00:00:01-0b".into(), location("a", "1"));
+        bridges
+            .light_index
+            .insert("00:17:88:01:00:00:00:02-0b".into(), location("b", "4"));
+        assert_eq!(
+            bridges.find_light("00:17:88:01:00:00:00:01-0B"),
+            Some(("a", "1"))
+        );
+
+        let modifier = light::StateModifier::new().with_on(true);
+        bridges
+            .set_light_state_by_unique_id("00:17:88:01:00:00:00:02-0b", &modifier)
+            .unwrap();
+        assert!(bridges
+            .get("a")
+            .unwrap()
+            .take_recorded_requests()
+            .is_empty());
+        let requests = bridges.get("b").unwrap().take_recorded_requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].path, "lights/4/state");
+
+        bridges.remove("b");
+        assert_eq!(bridges.find_light("00:17:88:01:00:00:00:02-0b"), None);
+        assert_eq!(bridges.light_index.len(), 1);
+    }
+
+    #[test]
+    fn refresh_light_index() {
+        let unique_id = "00:17:88:01:00:00:00:01-0b";
+        let server = TestServer::start(vec![
+            json!([{"error": {
+                "type": 3,
+                "address": "/lights/1/state",
+                "description": "resource, /lights/1/state, not available"
+            }}]),
+            json!({"4": {
+                "state": {"on": false, "alert": "none", "reachable": true},
+                "swupdate": {"state": "noupdates", "lastinstall": null},
+                "type": "On/Off plug-in unit",
+                "name": "Plug",
+                "modelid": "LOM001",
+                "manufacturername": "Signify Netherlands B.V.",
+                "productname": "Hue Smart plug",
+                "capabilities": {
+                    "certified": true,
+                    "control": {},
+                    "streaming": {"renderer": false, "proxy": false}
+                },
+                "config": {"archetype": "plug", "function": "functional", "direction": "omnidirectional"},
+                "uniqueid": unique_id,
+                "swversion": "1.65.11"
+            }}),
+            json!([{"success": {"/lights/4/state/on": true}}]),
+        ]);
+        let mut bridges = BridgeSet::new();
+        bridges.insert("a", server.bridge());
+        bridges.light_index.insert(
+            unique_id.into(),
+            LightLocation {
+                bridge_id: "a".into(),
+                light_id: "1".into(),
+            },
+        );
+
+        let modifier = light::StateModifier::new().with_on(true);
+        let responses = bridges
+            .set_light_state_by_unique_id(unique_id, &modifier)
+            .unwrap();
+        assert!(matches!(responses[..], [Response::Success(_)]));
+        assert_eq!(bridges.find_light(unique_id), Some(("a", "4")));
+
+        let requests = server.finish();
+        let paths: Vec<_> = requests.iter().map(|v| v.path.as_str()).collect();
+        assert_eq!(paths, vec!["lights/1/state", "lights", "lights/4/state"]);
+    }
+}
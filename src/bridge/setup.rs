@@ -0,0 +1,132 @@
+use super::Bridge;
+use crate::resource::config;
+use crate::Result;
+use derive_setters::Setters;
+use std::net::IpAddr;
+use std::time::Duration;
+
+/// Settings for the first setup of a bridge.
+///
+/// See [`setup_new_bridge`] for more information.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Setters)]
+#[setters(prefix = "with_")]
+pub struct BridgeSetup {
+    /// Device type of the registered user, e.g. `my_app#my_device`.
+    #[setters(skip)]
+    pub devicetype: String,
+    /// Name of the bridge.
+    #[setters(strip_option)]
+    pub name: Option<String>,
+    /// Timezone of the bridge as OlsenID, e.g. `Europe/Berlin`.
+    #[setters(strip_option)]
+    pub timezone: Option<String>,
+    /// Whether a search for software updates is started. Defaults to `true`.
+    pub check_for_update: bool,
+    /// Time to wait for the link button to be pressed. Defaults to 30 seconds.
+    pub timeout: Duration,
+}
+
+impl BridgeSetup {
+    /// Creates new [`BridgeSetup`] settings.
+    pub fn new<S>(devicetype: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            devicetype: devicetype.into(),
+            name: None,
+            timezone: None,
+            check_for_update: true,
+            timeout: Duration::from_secs(30),
+        }
+    }
+
+    /// Returns the modifier for the configuration of the bridge.
+    fn modifier(&self) -> Option<config::Modifier> {
+        let mut modifier = config::Modifier::new();
+        modifier.name = self.name.clone();
+        modifier.timezone = self.timezone.clone();
+        if self.check_for_update {
+            modifier.software_update =
+                Some(config::SoftwareUpdateModifier::new().with_check_for_update(true));
+        }
+        if modifier == config::Modifier::new() {
+            return None;
+        }
+        Some(modifier)
+    }
+}
+
+/// Sets up a bridge and returns it with a newly registered user.
+///
+/// This runs the steps of the first setup in order:
+///
+/// 1. Registers a user, waiting until the link button of the bridge is pressed (see
+///    [`register_user_polling`]).
+/// 2. Sets the name and the timezone of the bridge.
+/// 3. Starts a search for software updates.
+///
+/// Whether a bridge is factory new, or was restored from the backup of another bridge, can be
+/// checked before with [`get_unauthenticated_config`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use huelib2::bridge::{self, BridgeSetup};
+/// use std::net::{IpAddr, Ipv4Addr};
+///
+/// # fn main() -> Result<(), huelib2::Error> {
+/// let bridge_ip = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2));
+/// if bridge::get_unauthenticated_config(bridge_ip)?.factory_new {
+///     let setup = BridgeSetup::new("example")
+///         .with_name("Home".into())
+///         .with_timezone("Europe/Berlin".into());
+///     println!("Press the link button of the bridge");
+///     let bridge = bridge::setup_new_bridge(bridge_ip, &setup)?;
+///     println!("Registered user with username `{}`", bridge.username());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`register_user_polling`]: super::register_user_polling
+/// [`get_unauthenticated_config`]: super::get_unauthenticated_config
+pub fn setup_new_bridge(ip_address: IpAddr, setup: &BridgeSetup) -> Result<Bridge> {
+    setup_new_bridge_with_base_url(super::base_url::from_ip(ip_address), setup)
+}
+
+/// Sets up a bridge that is served under a base URL and returns it with a newly registered user.
+///
+/// This behaves like [`setup_new_bridge`] but takes a base URL like [`Bridge::from_base_url`].
+pub fn setup_new_bridge_with_base_url<S>(base_url: S, setup: &BridgeSetup) -> Result<Bridge>
+where
+    S: AsRef<str>,
+{
+    let username =
+        super::register_user_polling_with_base_url(&base_url, &setup.devicetype, setup.timeout)?;
+    let bridge = Bridge::from_base_url(base_url, username)?;
+    if let Some(modifier) = setup.modifier() {
+        bridge.set_config(&modifier)?;
+    }
+    Ok(bridge)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn modifier() {
+        let setup = BridgeSetup::new("test").with_check_for_update(false);
+        assert_eq!(setup.modifier(), None);
+
+        let setup = BridgeSetup::new("test").with_timezone("Europe/Berlin".into());
+        let modifier_json = serde_json::to_value(setup.modifier().unwrap()).unwrap();
+        let expected_json = json!({
+            "timezone": "Europe/Berlin",
+            "swupdate2": {"checkforupdate": true}
+        });
+        assert_eq!(modifier_json, expected_json);
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{Error, Result};
+use serde_json::Value as JsonValue;
+
+/// Maximum difference between floating point values that is not considered clamping.
+const FLOAT_TOLERANCE: f64 = 1e-3;
+
+/// Returns an error if a value in the response of a modifier differs from the requested value.
+///
+/// Attributes in the response that were not requested (e.g. `bri` for `bri_inc`) are ignored.
+pub(super) fn check_clamped(requested: &JsonValue, response: &JsonValue) -> Result<()> {
+    let modified = response
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|v| v.get("success").and_then(JsonValue::as_object))
+        .flatten();
+    for (address, applied) in modified {
+        let field = address.rsplit('/').next().unwrap_or_default();
+        if let Some(requested) = requested.get(field) {
+            if !values_match(requested, applied) {
+                return Err(Error::Clamped {
+                    field: field.to_owned(),
+                    requested: requested.clone(),
+                    applied: applied.clone(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns whether the values are equal, allowing rounding of floating point values.
+fn values_match(a: &JsonValue, b: &JsonValue) -> bool {
+    match (a, b) {
+        (JsonValue::Number(a), JsonValue::Number(b)) if a.is_f64() || b.is_f64() => {
+            match (a.as_f64(), b.as_f64()) {
+                (Some(a), Some(b)) => (a - b).abs() < FLOAT_TOLERANCE,
+                _ => false,
+            }
+        }
+        (JsonValue::Array(a), JsonValue::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| values_match(a, b))
+        }
+        (a, b) => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn check_clamped() {
+        let requested = json!({"on": true, "bri": 255, "xy": [0.31234, 0.3], "bri_inc": 10});
+        let response = json!([
+            {"success": {"/lights/1/state/on": true}},
+            {"success": {"/lights/1/state/xy": [0.3123, 0.3]}},
+            {"success": {"/lights/1/state/bri": 254}}
+        ]);
+        let error = super::check_clamped(&requested, &response).unwrap_err();
+        assert!(matches!(
+            error,
+            Error::Clamped { field, requested, applied }
+                if field == "bri" && requested == json!(255) && applied == json!(254)
+        ));
+
+        let requested = json!({"bri": 254});
+        let response = json!([
+            {"success": {"/lights/1/state/bri": 254}},
+            {"error": {"type": 201, "address": "/lights/1/state/hue", "description": ""}}
+        ]);
+        assert!(super::check_clamped(&requested, &response).is_ok());
+    }
+
+    #[test]
+    fn values_match() {
+        assert!(super::values_match(&json!(1), &json!(1)));
+        assert!(!super::values_match(&json!(1), &json!(2)));
+        assert!(super::values_match(&json!(0.5), &json!(0.5004)));
+        assert!(!super::values_match(&json!(0.5), &json!(0.51)));
+        assert!(!super::values_match(&json!([0.5]), &json!([0.5, 0.5])));
+        assert!(super::values_match(&json!("a"), &json!("a")));
+    }
+}
@@ -0,0 +1,105 @@
+use super::RecordedRequest;
+use crate::resource::RequestMethod;
+use crate::Bridge;
+use serde_json::Value as JsonValue;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, TcpListener};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+/// HTTP server that answers requests to the Philips Hue API with predefined responses.
+///
+/// The responses are sent in the given order, one per request. The server stops after all
+/// responses were sent.
+pub(crate) struct TestServer {
+    base_url: String,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl TestServer {
+    /// Starts a server on a free port of the loopback interface.
+    pub(crate) fn start(responses: Vec<JsonValue>) -> Self {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let handle = {
+            let requests = Arc::clone(&requests);
+            std::thread::spawn(move || {
+                for response in responses {
+                    let (stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream);
+                    let request = read_request(&mut reader);
+                    requests.lock().unwrap().push(request);
+                    let body = response.to_string();
+                    write!(
+                        reader.get_mut(),
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                         Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    )
+                    .unwrap();
+                }
+            })
+        };
+        Self {
+            base_url,
+            requests,
+            handle: Some(handle),
+        }
+    }
+
+    /// Returns a bridge with the user `user` that sends its requests to the server.
+    pub(crate) fn bridge(&self) -> Bridge {
+        Bridge::from_base_url(&self.base_url, "user").unwrap()
+    }
+
+    /// Waits until all responses were sent and returns the received requests.
+    pub(crate) fn finish(mut self) -> Vec<RecordedRequest> {
+        if let Some(handle) = self.handle.take() {
+            handle.join().expect("test server panicked");
+        }
+        self.requests.lock().unwrap().drain(..).collect()
+    }
+}
+
+/// Reads a request and returns it with the path relative to the API URL of the user.
+fn read_request(reader: &mut impl BufRead) -> RecordedRequest {
+    let mut line = String::new();
+    reader.read_line(&mut line).unwrap();
+    let mut parts = line.split_whitespace();
+    let method = match parts.next() {
+        Some("PUT") => RequestMethod::Put,
+        Some("POST") => RequestMethod::Post,
+        Some("DELETE") => RequestMethod::Delete,
+        _ => RequestMethod::Get,
+    };
+    let path = parts
+        .next()
+        .unwrap_or_default()
+        .splitn(4, '/')
+        .nth(3)
+        .unwrap_or_default()
+        .to_owned();
+    let mut content_length = 0;
+    loop {
+        line.clear();
+        reader.read_line(&mut line).unwrap();
+        if line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap();
+            }
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).unwrap();
+    RecordedRequest {
+        method,
+        path,
+        body: serde_json::from_slice(&body).ok(),
+    }
+}
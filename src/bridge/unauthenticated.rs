@@ -0,0 +1,33 @@
+use crate::resource::config::UnauthenticatedConfig;
+use crate::Result;
+use std::net::IpAddr;
+
+/// Returns the configuration of a bridge that can be accessed without a registered user.
+///
+/// This sends a HTTP GET request to `/api/config`. It can be used to identify a bridge before
+/// registering a user, e.g. to get the bridge identifier and the API version.
+///
+/// # Examples
+///
+/// Print the identifier of a bridge:
+/// ```no_run
+/// use huelib2::bridge;
+/// use std::net::{IpAddr, Ipv4Addr};
+///
+/// # fn main() -> Result<(), huelib2::Error> {
+/// let bridge_ip = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2));
+/// let config = bridge::get_unauthenticated_config(bridge_ip)?;
+/// println!("Found bridge `{}` ({})", config.name, config.bridge_id);
+/// # Ok(())
+/// # }
+/// ```
+pub fn get_unauthenticated_config(ip_address: IpAddr) -> Result<UnauthenticatedConfig> {
+    get_unauthenticated_config_at(&super::base_url::from_ip(ip_address))
+}
+
+/// Returns the configuration of the bridge with the given base URL.
+pub(super) fn get_unauthenticated_config_at(base_url: &str) -> Result<UnauthenticatedConfig> {
+    let url = format!("{}/api/config", base_url);
+    let http_response = ureq::get(&url).call()?;
+    Ok(http_response.into_json()?)
+}
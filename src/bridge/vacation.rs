@@ -0,0 +1,154 @@
+use super::Bridge;
+use crate::resource::group;
+use crate::resource::schedule::{self, LocalTime, Weekdays};
+use crate::Result;
+use chrono::NaiveTime;
+use derive_setters::Setters;
+use std::time::Duration;
+
+/// Description of the schedules that are created for the vacation mode.
+///
+/// This is used to find the schedules again when disabling the vacation mode.
+const DESCRIPTION: &str = "huelib2 vacation mode";
+
+/// Settings for simulating presence by switching groups on and off in the evening.
+///
+/// For every group, a recurring schedule that turns the group on and a recurring schedule that
+/// turns the group off are created. The bridge delays both schedules by a random duration every
+/// day, so the lights are not switched at the same time on every day.
+///
+/// See [`Bridge::enable_vacation_mode`] for more information.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Setters)]
+#[setters(prefix = "with_")]
+pub struct VacationMode {
+    /// Identifiers of the groups that are switched on and off.
+    #[setters(skip)]
+    pub groups: Vec<String>,
+    /// Earliest time when the groups are turned on. Defaults to 19:00.
+    pub on_time: NaiveTime,
+    /// Earliest time when the groups are turned off. Defaults to 22:30.
+    pub off_time: NaiveTime,
+    /// Maximum random delay that is added to the on and off times. Defaults to one hour.
+    ///
+    /// The delay is truncated to whole seconds and must be less than 24 hours.
+    pub randomization: Duration,
+    /// Days on which the schedules are active. Defaults to every day.
+    pub weekdays: Weekdays,
+}
+
+impl VacationMode {
+    /// Creates a new [`VacationMode`] for the given groups.
+    pub fn new(groups: Vec<String>) -> Self {
+        Self {
+            groups,
+            on_time: NaiveTime::from_hms_opt(19, 0, 0).expect("invalid time"),
+            off_time: NaiveTime::from_hms_opt(22, 30, 0).expect("invalid time"),
+            randomization: Duration::from_secs(60 * 60),
+            weekdays: Weekdays::ALL,
+        }
+    }
+
+    /// Returns the recurring randomized local time for the given time.
+    fn local_time(&self, time: NaiveTime) -> LocalTime {
+        LocalTime::recurring(self.weekdays, time).with_randomization(self.randomization)
+    }
+
+    /// Returns the creators of the schedules.
+    fn creators(&self, username: &str) -> Result<Vec<schedule::Creator>> {
+        let mut creators = Vec::new();
+        for id in &self.groups {
+            for (on, time) in [(true, self.on_time), (false, self.off_time)] {
+                let modifier = group::StateModifier::new().with_on(on);
+                let command = schedule::Command::from_modifier(&modifier, id.clone(), username)?;
+                let name = format!("Vacation {} {}", if on { "on" } else { "off" }, id);
+                let creator = schedule::Creator::new(command, self.local_time(time))
+                    .with_name(name)
+                    .with_description(DESCRIPTION.to_owned());
+                creators.push(creator);
+            }
+        }
+        Ok(creators)
+    }
+}
+
+pub(super) fn enable(bridge: &Bridge, mode: &VacationMode) -> Result<Vec<String>> {
+    let mut ids = Vec::new();
+    for creator in mode.creators(bridge.username())? {
+        match bridge.create_schedule(&creator) {
+            Ok(v) => ids.push(v),
+            Err(e) => {
+                for id in ids {
+                    let _ = bridge.delete_schedule(id);
+                }
+                return Err(e);
+            }
+        }
+    }
+    Ok(ids)
+}
+
+pub(super) fn disable(bridge: &Bridge) -> Result<Vec<String>> {
+    let mut ids: Vec<String> = bridge
+        .get_all_schedules()?
+        .into_iter()
+        .filter(|v| v.description == DESCRIPTION)
+        .map(|v| v.id)
+        .collect();
+    ids.sort();
+    for id in &ids {
+        bridge.delete_schedule(id.as_str())?;
+    }
+    Ok(ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::RequestMethod;
+    use serde_json::json;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    #[test]
+    fn local_time() {
+        let mode = VacationMode::new(vec!["1".into()])
+            .with_randomization(Duration::from_millis((90 * 60 + 5) * 1000 + 500))
+            .with_weekdays(Weekdays::WORKDAYS);
+        let time = NaiveTime::from_hms_opt(19, 0, 0).unwrap();
+        assert_eq!(mode.local_time(time).to_string(), "W124/T19:00:00A01:30:05");
+    }
+
+    #[test]
+    fn enable() {
+        let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "user").with_dry_run();
+        let mode = VacationMode::new(vec!["1".into(), "2".into()]);
+        let ids = bridge.enable_vacation_mode(&mode).unwrap();
+        assert_eq!(ids.len(), 4);
+
+        let requests = bridge.take_recorded_requests();
+        assert_eq!(requests.len(), 4);
+        assert!(requests.iter().all(|v| v.method == RequestMethod::Post));
+        assert_eq!(
+            requests[1].body,
+            Some(json!({
+                "name": "Vacation off 1",
+                "description": "huelib2 vacation mode",
+                "command": {
+                    "address": "/api/user/groups/1/action",
+                    "method": "PUT",
+                    "body": {"on": false}
+                },
+                "localtime": "W127/T22:30:00A01:00:00"
+            }))
+        );
+    }
+
+    #[test]
+    fn enable_invalid_randomization() {
+        let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "user").with_dry_run();
+        let mode = VacationMode::new(vec!["1".into()])
+            .with_randomization(Duration::from_secs(24 * 60 * 60));
+        let error = bridge.enable_vacation_mode(&mode).unwrap_err();
+        assert!(matches!(error, crate::Error::InvalidLocalTime(_)));
+        assert!(bridge.take_recorded_requests().is_empty());
+    }
+}
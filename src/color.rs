@@ -1,3 +1,4 @@
+use crate::units::Brightness;
 use std::num::ParseIntError;
 use thiserror::Error as ThisError;
 
@@ -61,13 +62,148 @@ impl Color {
         let z = red * 0.000_000 + green * 0.053_077 + blue * 1.035_763;
         Self {
             space_coordinates: (
-                x / (x + y + z + std::f32::MIN_POSITIVE),
-                y / (x + y + z + std::f32::MIN_POSITIVE),
+                x / (x + y + z + f32::MIN_POSITIVE),
+                y / (x + y + z + f32::MIN_POSITIVE),
             ),
             brightness: Some((y * 255.0) as u8),
         }
     }
 
+    /// Creates a new color from hue, saturation and value.
+    ///
+    /// The hue is given in degrees and wrapped into the range from 0 to 360. Saturation and value
+    /// range from 0 to 1 and are clamped to this range.
+    ///
+    /// This changes the color and brightness of a light.
+    ///
+    /// # Examples
+    ///
+    /// Generate an orange color:
+    /// ```
+    /// use huelib2::Color;
+    ///
+    /// let color = Color::from_hsv(30.0, 1.0, 1.0);
+    /// assert_eq!(color, Color::from_rgb(255, 128, 0));
+    /// ```
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32) -> Self {
+        let finite_or_zero = |v: f32| if v.is_finite() { v } else { 0.0 };
+        let hue = finite_or_zero(hue).rem_euclid(360.0) / 60.0;
+        let saturation = finite_or_zero(saturation).clamp(0.0, 1.0);
+        let value = finite_or_zero(value).clamp(0.0, 1.0);
+        let chroma = value * saturation;
+        let second = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+        let (red, green, blue) = match hue as u8 {
+            0 => (chroma, second, 0.0),
+            1 => (second, chroma, 0.0),
+            2 => (0.0, chroma, second),
+            3 => (0.0, second, chroma),
+            4 => (second, 0.0, chroma),
+            _ => (chroma, 0.0, second),
+        };
+        let min = value - chroma;
+        let to_u8 = |v: f32| ((v + min) * 255.0).round() as u8;
+        Self::from_rgb(to_u8(red), to_u8(green), to_u8(blue))
+    }
+
+    /// Creates a new color from a name.
+    ///
+    /// Supported are the basic colors `red`, `orange`, `yellow`, `green`, `cyan`, `blue`,
+    /// `purple`, `magenta` and `pink`, which change the color and brightness of a light, as well
+    /// as the white tones `warm white`, `white`, `cool white` and `daylight`, which only change the
+    /// color. Names are compared case-insensitively, and spaces, dashes and underscores are
+    /// ignored. Returns `None` if the name is unknown.
+    ///
+    /// # Examples
+    ///
+    /// Generate a color and use it in a modifier:
+    /// ```
+    /// use huelib2::{Color, resource::light};
+    ///
+    /// let color = Color::from_named("warm white").unwrap();
+    /// let modifier = light::StateModifier::new().with_color(color);
+    /// ```
+    pub fn from_named(name: impl AsRef<str>) -> Option<Self> {
+        let name: String = name
+            .as_ref()
+            .chars()
+            .filter(|v| !matches!(v, ' ' | '-' | '_'))
+            .flat_map(char::to_lowercase)
+            .collect();
+        let color = match name.as_str() {
+            "red" => Self::from_rgb(255, 0, 0),
+            "orange" => Self::from_rgb(255, 128, 0),
+            "yellow" => Self::from_rgb(255, 255, 0),
+            "green" => Self::from_rgb(0, 255, 0),
+            "cyan" => Self::from_rgb(0, 255, 255),
+            "blue" => Self::from_rgb(0, 0, 255),
+            "purple" => Self::from_rgb(128, 0, 255),
+            "magenta" => Self::from_rgb(255, 0, 255),
+            "pink" => Self::from_rgb(255, 105, 180),
+            // Color space coordinates of black-body radiators at 2700K, 4000K, 5000K and 6500K.
+            "warmwhite" => Self::from_space_coordinates(0.4599, 0.4106),
+            "white" => Self::from_space_coordinates(0.3805, 0.3768),
+            "coolwhite" => Self::from_space_coordinates(0.3457, 0.3585),
+            "daylight" => Self::from_space_coordinates(0.3127, 0.3290),
+            _ => return None,
+        };
+        Some(color)
+    }
+
+    /// Returns the color space coordinates of the color.
+    pub fn space_coordinates(&self) -> (f32, f32) {
+        self.space_coordinates
+    }
+
+    /// Returns the brightness of the color, if the color changes the brightness of a light.
+    ///
+    /// The brightness is clamped into the range of [`Brightness`], e.g. black has the minimum
+    /// brightness.
+    pub fn brightness(&self) -> Option<Brightness> {
+        self.brightness.map(Brightness::clamped)
+    }
+
+    /// Moves the color into the color gamut of a light.
+    ///
+    /// The gamut is the triangle of the red, green and blue color space coordinates that a light
+    /// can display, as returned in the control capabilities of the light. Colors outside of the
+    /// triangle are mapped to the nearest point on its edges. The color is returned unchanged if
+    /// the gamut does not consist of exactly three points.
+    ///
+    /// # Examples
+    ///
+    /// Map pure green to the nearest color of a light with gamut C:
+    /// ```
+    /// use huelib2::Color;
+    ///
+    /// // The gamut of a light is returned in `light.capabilities.control.color_gamut`.
+    /// let gamut = [(0.6915, 0.3083), (0.17, 0.7), (0.1532, 0.0475)];
+    /// let color = Color::from_rgb(0, 255, 0).clamp_to_gamut(&gamut);
+    /// assert_eq!(color.space_coordinates(), (0.17, 0.7));
+    /// ```
+    pub fn clamp_to_gamut(self, gamut: &[(f32, f32)]) -> Self {
+        let (red, green, blue) = match gamut {
+            [red, green, blue] => (*red, *green, *blue),
+            _ => return self,
+        };
+        let point = self.space_coordinates;
+        let cross = |a: (f32, f32), b: (f32, f32)| {
+            (b.0 - a.0) * (point.1 - a.1) - (b.1 - a.1) * (point.0 - a.0)
+        };
+        let sides = [cross(red, green), cross(green, blue), cross(blue, red)];
+        if sides.iter().all(|v| *v >= 0.0) || sides.iter().all(|v| *v <= 0.0) {
+            return self;
+        }
+        let space_coordinates = [(red, green), (green, blue), (blue, red)]
+            .iter()
+            .map(|(a, b)| closest_point_on_segment(point, *a, *b))
+            .min_by(|a, b| distance(point, *a).total_cmp(&distance(point, *b)))
+            .unwrap_or(point);
+        Self {
+            space_coordinates,
+            ..self
+        }
+    }
+
     /// Creates a new color from a hex value.
     ///
     /// The string must begin with a `#` followed by either 3 or 6 hexadecimal digits.
@@ -117,6 +253,20 @@ impl Color {
     }
 }
 
+fn closest_point_on_segment(point: (f32, f32), a: (f32, f32), b: (f32, f32)) -> (f32, f32) {
+    let ab = (b.0 - a.0, b.1 - a.1);
+    let length = ab.0 * ab.0 + ab.1 * ab.1;
+    if length == 0.0 {
+        return a;
+    }
+    let t = (((point.0 - a.0) * ab.0 + (point.1 - a.1) * ab.1) / length).clamp(0.0, 1.0);
+    (a.0 + t * ab.0, a.1 + t * ab.1)
+}
+
+fn distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+    (a.0 - b.0).hypot(a.1 - b.1)
+}
+
 /// Errors that can occur while parsing a hex string to a color.
 #[derive(Clone, Debug, Eq, PartialEq, ThisError)]
 pub enum ParseHexError {
@@ -139,16 +289,92 @@ mod tests {
         assert_eq!(color.brightness, None);
     }
 
+    #[test]
+    fn clamp_to_gamut() {
+        let gamut = [(0.6915, 0.3083), (0.17, 0.7), (0.1532, 0.0475)];
+        let assert_near = |color: Color, x: f32, y: f32| {
+            let (a, b) = color.space_coordinates();
+            assert!((a - x).abs() < 1e-3 && (b - y).abs() < 1e-3, "{:?}", color);
+        };
+        let color = Color::from_space_coordinates(0.3, 0.3);
+        assert_eq!(color.clamp_to_gamut(&gamut), color);
+        assert_eq!(color.clamp_to_gamut(&[]), color);
+
+        let color = Color::from_space_coordinates(0.8, 0.3083);
+        assert_near(color.clamp_to_gamut(&gamut), 0.6915, 0.3083);
+
+        let color = Color::from_rgb(0, 255, 0);
+        assert_near(color.clamp_to_gamut(&gamut), 0.17, 0.7);
+        assert_eq!(color.clamp_to_gamut(&gamut).brightness, color.brightness);
+    }
+
+    #[test]
+    fn hsv() {
+        assert_eq!(Color::from_hsv(0.0, 1.0, 1.0), Color::from_rgb(255, 0, 0));
+        assert_eq!(Color::from_hsv(120.0, 1.0, 1.0), Color::from_rgb(0, 255, 0));
+        assert_eq!(
+            Color::from_hsv(-120.0, 1.0, 1.0),
+            Color::from_rgb(0, 0, 255)
+        );
+        assert_eq!(
+            Color::from_hsv(300.0, 0.5, 0.5),
+            Color::from_rgb(128, 64, 128)
+        );
+        assert_eq!(
+            Color::from_hsv(42.0, 0.0, 2.0),
+            Color::from_rgb(255, 255, 255)
+        );
+        assert_eq!(
+            Color::from_hsv(f32::NAN, 1.0, 0.0),
+            Color::from_rgb(0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn named() {
+        assert_eq!(Color::from_named("Red"), Some(Color::from_rgb(255, 0, 0)));
+        assert_eq!(
+            Color::from_named("warm_white"),
+            Color::from_named("Warm White")
+        );
+        assert_eq!(Color::from_named("daylight").unwrap().brightness, None);
+        assert_eq!(Color::from_named("unknown"), None);
+    }
+
+    #[test]
+    fn named_whites_ordered_by_temperature() {
+        // Correlated color temperature in kelvin after McCamy's approximation.
+        let cct = |name: &str| {
+            let (x, y) = Color::from_named(name).unwrap().space_coordinates();
+            let n = (x - 0.3320) / (0.1858 - y);
+            449.0 * n.powi(3) + 3525.0 * n.powi(2) + 6823.3 * n + 5520.33
+        };
+        let temperatures: Vec<f32> = ["warm white", "white", "cool white", "daylight"]
+            .iter()
+            .map(|v| cct(v))
+            .collect();
+        assert!(
+            temperatures.windows(2).all(|v| v[0] < v[1]),
+            "{:?}",
+            temperatures
+        );
+        for (temperature, expected) in temperatures.iter().zip(&[2700.0, 4000.0, 5000.0, 6500.0]) {
+            assert!((temperature - expected).abs() < 100.0, "{:?}", temperatures);
+        }
+    }
+
     #[test]
     fn rgb_white() {
         let color = Color::from_rgb(255, 255, 255);
         assert_eq!(color.brightness, Some(255));
+        assert_eq!(color.brightness(), Some(Brightness::MAX));
     }
 
     #[test]
     fn rgb_black() {
         let color = Color::from_rgb(0, 0, 0);
         assert_eq!(color.brightness, Some(0));
+        assert_eq!(color.brightness(), Some(Brightness::MIN));
     }
 
     #[test]
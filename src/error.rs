@@ -1,8 +1,11 @@
-use crate::response::Error as ResponseError;
+use crate::resource::RequestMethod;
+use crate::response::{Error as ResponseError, ErrorKind, ResourceContext};
 use chrono::ParseError as ChronoParseError;
-use serde_json::Error as SerdeJsonError;
+use serde_json::{Error as SerdeJsonError, Value as JsonValue};
 #[cfg(feature = "upnp-description")]
 use serde_xml_rs::Error as SerdeXmlError;
+#[cfg(feature = "yaml-export")]
+use serde_yaml::Error as SerdeYamlError;
 use std::result::Result as StdResult;
 use std::{io::Error as IoError, net::AddrParseError};
 use thiserror::Error as ThisError;
@@ -22,6 +25,12 @@ pub enum Error {
     #[error("Failed to get identifier of created resource")]
     GetCreatedId,
 
+    /// Error that can occur when the bridge does not allow deleting users with the API.
+    ///
+    /// Newer firmware versions only allow removing users using the Philips Hue account.
+    #[error("Deleting users is not allowed by the bridge")]
+    DeleteUserNotAllowed,
+
     /// Error that can occur while converting a string to a date.
     #[error("Failed to parse date")]
     ParseDate(#[from] ChronoParseError),
@@ -49,9 +58,201 @@ pub enum Error {
     #[error("Failed to parse description")]
     ParseDescription(#[from] SerdeXmlError),
 
+    #[cfg(feature = "yaml-export")]
+    /// Error that can occur when serializing an [`HomeAssistantExport`].
+    ///
+    /// [`HomeAssistantExport`]: crate::export::HomeAssistantExport
+    #[error("Failed to serialize yaml")]
+    SerializeYaml(#[from] SerdeYamlError),
+
+    /// Error that occurs in strict mode when the bridge adjusted a requested value.
+    ///
+    /// See [`Bridge::with_strict_mode`] for more information.
+    ///
+    /// [`Bridge::with_strict_mode`]: crate::Bridge::with_strict_mode
+    #[error("Bridge adjusted {field} from {requested} to {applied}")]
+    Clamped {
+        /// Name of the attribute, e.g. `bri`.
+        field: String,
+        /// Value that was requested.
+        requested: JsonValue,
+        /// Value that was applied by the bridge.
+        applied: JsonValue,
+    },
+
+    /// Error that can occur when the locations of an entertainment group are invalid.
+    #[error("Invalid locations of entertainment group")]
+    InvalidLocations(#[from] crate::resource::group::LocationError),
+
+    /// Error that can occur when a value is outside of the range that is accepted by the bridge.
+    #[error("Value out of range")]
+    OutOfRange(#[from] crate::units::OutOfRangeError),
+
+    /// Error that can occur when parsing the local time of a schedule.
+    #[error("Failed to parse local time")]
+    ParseLocalTime(#[from] crate::resource::schedule::ParseLocalTimeError),
+
+    /// Error that can occur when the local time of a schedule is not accepted by the bridge.
+    ///
+    /// This is returned before the request is sent, see [`LocalTime::validate`].
+    ///
+    /// [`LocalTime::validate`]: crate::resource::schedule::LocalTime::validate
+    #[error("Invalid local time of schedule")]
+    InvalidLocalTime(#[from] crate::resource::schedule::LocalTimeError),
+
+    /// Error that can occur while discovering bridges using SSDP.
+    #[error("Failed to discover bridges using SSDP")]
+    Ssdp(#[source] IoError),
+
+    /// Error that can occur when discovering bridges without any discovery endpoint.
+    #[error("No discovery endpoint configured")]
+    NoDiscoveryEndpoint,
+
+    /// Error that can occur when the base URL of a bridge is not a valid HTTP or HTTPS URL.
+    #[error("Invalid base URL: {0}")]
+    InvalidBaseUrl(String),
+
+    /// Error that can occur when no light with a unique identifier exists on a bridge or on any
+    /// bridge of a [`BridgeSet`].
+    ///
+    /// [`BridgeSet`]: crate::bridge::BridgeSet
+    #[error("No light with unique identifier {0} found")]
+    UnknownUniqueId(String),
+
+    /// Error that can occur when no group with a name exists on a bridge.
+    ///
+    /// See [`Bridge::get_group_by_name`] for more information.
+    ///
+    /// [`Bridge::get_group_by_name`]: crate::Bridge::get_group_by_name
+    #[error("No group named {name} found (similar names: {})", .near_matches.join(", "))]
+    UnknownGroupName {
+        /// Name that was searched for.
+        name: String,
+        /// Names of groups that are similar to the name, ordered by similarity.
+        near_matches: Vec<String>,
+    },
+
+    /// Error that can occur when the streaming session of an entertainment group is owned by
+    /// another user.
+    ///
+    /// See [`Bridge::set_group_stream`] for more information.
+    ///
+    /// [`Bridge::set_group_stream`]: crate::Bridge::set_group_stream
+    #[error("Stream of group is owned by {0}")]
+    StreamOwned(String),
+
+    /// Error that can occur when the lights of a group are modified by someone else while
+    /// adding or removing a light.
+    ///
+    /// See [`Bridge::add_light_to_group`] for more information.
+    ///
+    /// [`Bridge::add_light_to_group`]: crate::Bridge::add_light_to_group
+    #[error("Lights of group {0} were modified concurrently")]
+    GroupConflict(String),
+
+    /// Error that can occur while reading or writing the discovery cache.
+    #[error("Failed to access discovery cache")]
+    DiscoveryCache(#[source] IoError),
+
     /// Error that is returned by the Philips Hue API.
     #[error("Error returned from Philips Hue API")]
     Response(#[from] ResponseError),
+
+    /// Multiple errors that are returned by the Philips Hue API for the same request.
+    #[error("{} errors returned from Philips Hue API", .0.len())]
+    Responses(Vec<ResponseError>),
+}
+
+impl Error {
+    /// Returns all errors that were returned by the Philips Hue API.
+    ///
+    /// Returns an empty slice if the error did not originate from the Philips Hue API.
+    pub fn response_errors(&self) -> &[ResponseError] {
+        match self {
+            Self::Response(e) => std::slice::from_ref(e),
+            Self::Responses(v) => v,
+            _ => &[],
+        }
+    }
+
+    /// Returns the address of the attribute that caused the error, if the error was returned by
+    /// the Philips Hue API.
+    ///
+    /// The address has the format `/<kind>/<id>/<attribute>`, e.g. `/lights/1/state/bri`. If
+    /// multiple errors were returned, the address of the first error is returned.
+    pub fn address(&self) -> Option<&str> {
+        self.response_errors().first().map(|v| v.address.as_str())
+    }
+
+    /// Returns the resource that was accessed by the request, if the error was returned by the
+    /// Philips Hue API.
+    ///
+    /// If multiple errors were returned, the resource of the first error is returned.
+    pub fn resource(&self) -> Option<&ResourceContext> {
+        self.response_errors().first()?.resource.as_deref()
+    }
+
+    /// Returns whether the user is not authorized to access the bridge.
+    pub fn is_unauthorized(&self) -> bool {
+        self.has_response_error(ErrorKind::UnauthorizedUser)
+    }
+
+    /// Returns whether the accessed resource does not exist.
+    pub fn is_not_found(&self) -> bool {
+        self.has_response_error(ErrorKind::ResourceNotAvailable)
+    }
+
+    /// Returns whether the link button of the bridge was not pressed when registering a user.
+    pub fn is_link_button(&self) -> bool {
+        self.has_response_error(ErrorKind::LinkButtonNotPressed)
+    }
+
+    /// Returns whether the Philips Hue API returned an error of the given kind.
+    fn has_response_error(&self, kind: ErrorKind) -> bool {
+        self.response_errors().iter().any(|v| v.kind == kind)
+    }
+
+    /// Creates an error from an error that is returned when deleting a user.
+    ///
+    /// Bridges that do not allow deleting users either report that the method is not available
+    /// or that the user is not authorized to access the whitelist entry.
+    pub(crate) fn from_delete_user_error(error: ResponseError, username: &str) -> Self {
+        let entry_address = format!("/config/whitelist/{}", username);
+        match error.kind {
+            ErrorKind::MethodNotAvailableForResource => Self::DeleteUserNotAllowed,
+            ErrorKind::UnauthorizedUser if error.address == entry_address => {
+                Self::DeleteUserNotAllowed
+            }
+            _ => Self::Response(error),
+        }
+    }
+
+    /// Creates an error from the errors returned by the Philips Hue API for a request.
+    ///
+    /// A single error is returned as [`Error::Response`].
+    pub(crate) fn from_response_errors(mut errors: Vec<ResponseError>) -> Self {
+        match errors.len() {
+            1 => Self::Response(errors.remove(0)),
+            _ => Self::Responses(errors),
+        }
+    }
+
+    /// Attaches the resource that is accessed by a request to the errors returned by the
+    /// Philips Hue API.
+    ///
+    /// Other errors are returned unchanged.
+    pub(crate) fn with_resource_context(self, method: RequestMethod, url_suffix: &str) -> Self {
+        let resource = ResourceContext::new(method, url_suffix);
+        match self {
+            Self::Response(e) => Self::Response(e.with_resource(resource.as_ref())),
+            Self::Responses(v) => Self::Responses(
+                v.into_iter()
+                    .map(|e| e.with_resource(resource.as_ref()))
+                    .collect(),
+            ),
+            v => v,
+        }
+    }
 }
 
 impl From<UreqError> for Error {
@@ -59,3 +260,85 @@ impl From<UreqError> for Error {
         Self::Request(Box::new(ureq_error))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with_resource_context() {
+        let error = Error::Response(ResponseError {
+            kind: ErrorKind::DeviceIsSetToOff,
+            address: "/lights/1/state/bri".into(),
+            description: "parameter, bri, is not modifiable. Device is set to off.".into(),
+            resource: None,
+        })
+        .with_resource_context(RequestMethod::Put, "lights/1/state");
+        assert!(matches!(error, Error::Response(_)));
+        let resource = error.resource().expect("error has no resource context");
+        assert_eq!(resource.kind, "lights");
+        assert_eq!(resource.id.as_deref(), Some("1"));
+        assert_eq!(resource.endpoint, "lights/1/state");
+        assert_eq!(error.address(), Some("/lights/1/state/bri"));
+
+        let error = Error::GetCreatedId.with_resource_context(RequestMethod::Get, "lights");
+        assert!(matches!(error, Error::GetCreatedId));
+        assert_eq!(error.resource(), None);
+    }
+
+    #[test]
+    fn response_errors() {
+        let error = Error::from_response_errors(vec![
+            ResponseError {
+                kind: ErrorKind::InvalidValueForParameter,
+                address: "/lights/1/state/bri".into(),
+                description: "invalid value, 300, for parameter, bri".into(),
+                resource: None,
+            },
+            ResponseError {
+                kind: ErrorKind::ResourceNotAvailable,
+                address: "/lights/1/state/ct".into(),
+                description: "parameter, ct, not available".into(),
+                resource: None,
+            },
+        ])
+        .with_resource_context(RequestMethod::Put, "lights/1/state");
+        assert_eq!(error.response_errors().len(), 2);
+        assert!(error.response_errors().iter().all(|v| v.resource.is_some()));
+        assert_eq!(error.address(), Some("/lights/1/state/bri"));
+        assert!(error.is_not_found());
+        assert!(!error.is_unauthorized());
+        assert!(!error.is_link_button());
+
+        let error = Error::GetUsername;
+        assert!(error.response_errors().is_empty());
+        assert!(!error.is_not_found());
+    }
+
+    #[test]
+    fn from_delete_user_error() {
+        let error = |kind, address: &str| ResponseError {
+            kind,
+            address: address.into(),
+            description: String::new(),
+            resource: None,
+        };
+        let value = Error::from_delete_user_error(
+            error(ErrorKind::UnauthorizedUser, "/config/whitelist/user"),
+            "user",
+        );
+        assert!(matches!(value, Error::DeleteUserNotAllowed));
+
+        let value = Error::from_delete_user_error(
+            error(
+                ErrorKind::MethodNotAvailableForResource,
+                "/config/whitelist/user",
+            ),
+            "user",
+        );
+        assert!(matches!(value, Error::DeleteUserNotAllowed));
+
+        let value = Error::from_delete_user_error(error(ErrorKind::UnauthorizedUser, "/"), "user");
+        assert!(value.is_unauthorized());
+    }
+}
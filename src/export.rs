@@ -0,0 +1,350 @@
+use crate::resource::{config::FullState, group, Group, Light, Scene};
+use crate::Result;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// Lights, rooms and scenes of a bridge, named using the conventions of Home Assistant.
+///
+/// This can be used to bootstrap the configuration of other home-automation systems. Every light
+/// and scene gets an entity identifier (e.g. `light.living_room_ceiling`) and every room becomes
+/// an area. Identifiers are derived from the names of the resources: they are lowercase, only
+/// contain ASCII letters, digits and underscores and are made unique by appending a number.
+///
+/// # Examples
+///
+/// Print the configuration of a bridge as YAML:
+/// ```no_run
+/// use huelib2::export::HomeAssistantExport;
+///
+/// # fn main() -> huelib2::Result<()> {
+/// # use huelib2::Bridge;
+/// # use std::net::{IpAddr, Ipv4Addr};
+/// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+/// // let bridge = Bridge::new(...);
+/// let full_state = bridge.get_full_state()?;
+/// let export = HomeAssistantExport::from_full_state(&full_state);
+/// println!("{}", export.to_yaml()?);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "yaml-export")))]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize)]
+pub struct HomeAssistantExport {
+    /// Exported lights.
+    #[serde(rename = "light")]
+    pub lights: Vec<ExportedLight>,
+    /// Exported rooms.
+    #[serde(rename = "area")]
+    pub areas: Vec<ExportedArea>,
+    /// Exported scenes.
+    #[serde(rename = "scene")]
+    pub scenes: Vec<ExportedScene>,
+}
+
+/// A light of a [`HomeAssistantExport`].
+#[cfg_attr(docsrs, doc(cfg(feature = "yaml-export")))]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize)]
+pub struct ExportedLight {
+    /// Entity identifier of the light, e.g. `light.kitchen`.
+    pub entity_id: String,
+    /// Name of the light.
+    pub name: String,
+    /// Unique identifier of the light on the bridge.
+    pub unique_id: String,
+    /// Hardware model of the light.
+    pub model: String,
+    /// Identifier of the area that the light is in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub area: Option<String>,
+}
+
+/// A room of a [`HomeAssistantExport`].
+#[cfg_attr(docsrs, doc(cfg(feature = "yaml-export")))]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize)]
+pub struct ExportedArea {
+    /// Identifier of the area, e.g. `kitchen`.
+    pub id: String,
+    /// Name of the room.
+    pub name: String,
+    /// Class of the room.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub class: Option<group::Class>,
+    /// Entity identifiers of the lights in the room.
+    pub lights: Vec<String>,
+}
+
+/// A scene of a [`HomeAssistantExport`].
+#[cfg_attr(docsrs, doc(cfg(feature = "yaml-export")))]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize)]
+pub struct ExportedScene {
+    /// Entity identifier of the scene, e.g. `scene.kitchen_relax`.
+    ///
+    /// The identifier is prefixed with the name of the room if the scene belongs to a room.
+    pub entity_id: String,
+    /// Name of the scene.
+    pub name: String,
+    /// Identifier of the scene on the bridge.
+    pub scene_id: String,
+    /// Identifier of the area that the scene belongs to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub area: Option<String>,
+    /// Entity identifiers of the lights in the scene.
+    pub lights: Vec<String>,
+}
+
+impl HomeAssistantExport {
+    /// Creates an export from the full state of a bridge.
+    pub fn from_full_state(full_state: &FullState) -> Self {
+        Self::new(&full_state.lights, &full_state.groups, &full_state.scenes)
+    }
+
+    /// Creates an export from lights, groups and scenes.
+    ///
+    /// Groups that are not rooms are ignored.
+    pub fn new(lights: &[Light], groups: &[Group], scenes: &[Scene]) -> Self {
+        let mut lights: Vec<&Light> = lights.iter().collect();
+        lights.sort_by(|a, b| id_order(&a.id, &b.id));
+        let mut rooms: Vec<&Group> = groups
+            .iter()
+            .filter(|v| v.kind == group::Kind::Creatable(group::CreatableKind::Room))
+            .collect();
+        rooms.sort_by(|a, b| id_order(&a.id, &b.id));
+        let mut scenes: Vec<&Scene> = scenes.iter().collect();
+        scenes.sort_by(|a, b| id_order(&a.id, &b.id));
+
+        let mut used = HashSet::new();
+        let area_ids: HashMap<&str, String> = rooms
+            .iter()
+            .map(|v| (v.id.as_str(), unique(slugify(&v.name), &mut used)))
+            .collect();
+        let light_areas: HashMap<&str, &str> = rooms
+            .iter()
+            .flat_map(|room| {
+                room.lights
+                    .iter()
+                    .map(move |v| (v.as_str(), room.id.as_str()))
+            })
+            .collect();
+
+        let mut used = HashSet::new();
+        let light_entities: HashMap<&str, String> = lights
+            .iter()
+            .map(|v| {
+                let entity_id = format!("light.{}", unique(slugify(&v.name), &mut used));
+                (v.id.as_str(), entity_id)
+            })
+            .collect();
+        let entities = |ids: &[String]| -> Vec<String> {
+            ids.iter()
+                .filter_map(|v| light_entities.get(v.as_str()).cloned())
+                .collect()
+        };
+
+        let mut used = HashSet::new();
+        let scenes = scenes
+            .into_iter()
+            .map(|v| {
+                let room = v
+                    .group()
+                    .and_then(|id| rooms.iter().find(|room| room.id == id));
+                let slug = match room {
+                    Some(room) => slugify(&format!("{} {}", room.name, v.name)),
+                    None => slugify(&v.name),
+                };
+                let lights = match (&v.lights, room) {
+                    (Some(lights), _) => entities(lights),
+                    (None, Some(room)) => entities(&room.lights),
+                    (None, None) => Vec::new(),
+                };
+                ExportedScene {
+                    entity_id: format!("scene.{}", unique(slug, &mut used)),
+                    name: v.name.clone(),
+                    scene_id: v.id.clone(),
+                    area: room.map(|room| area_ids[room.id.as_str()].clone()),
+                    lights,
+                }
+            })
+            .collect();
+
+        Self {
+            lights: lights
+                .iter()
+                .map(|v| ExportedLight {
+                    entity_id: light_entities[v.id.as_str()].clone(),
+                    name: v.name.clone(),
+                    unique_id: v.unique_id.clone(),
+                    model: v.model_id.clone(),
+                    area: light_areas
+                        .get(v.id.as_str())
+                        .map(|room_id| area_ids[room_id].clone()),
+                })
+                .collect(),
+            areas: rooms
+                .iter()
+                .map(|v| ExportedArea {
+                    id: area_ids[v.id.as_str()].clone(),
+                    name: v.name.clone(),
+                    class: v.class,
+                    lights: entities(&v.lights),
+                })
+                .collect(),
+            scenes,
+        }
+    }
+
+    /// Serializes the export to a YAML document.
+    pub fn to_yaml(&self) -> Result<String> {
+        Ok(serde_yaml::to_string(self)?)
+    }
+}
+
+/// Orders identifiers numerically if possible.
+fn id_order(a: &str, b: &str) -> std::cmp::Ordering {
+    (a.len(), a).cmp(&(b.len(), b))
+}
+
+/// Converts a name to an identifier that only contains lowercase ASCII letters, digits and
+/// underscores.
+fn slugify(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars().flat_map(char::to_lowercase) {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('_') {
+            slug.push('_');
+        }
+    }
+    let len = slug.trim_end_matches('_').len();
+    slug.truncate(len);
+    if slug.is_empty() {
+        slug.push_str("unnamed");
+    }
+    slug
+}
+
+/// Appends a number to the identifier if it is already used.
+fn unique(slug: String, used: &mut HashSet<String>) -> String {
+    if used.insert(slug.clone()) {
+        return slug;
+    }
+    (2..)
+        .map(|n| format!("{}_{}", slug, n))
+        .find(|v| used.insert(v.clone()))
+        .expect("no unique identifier")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn light(id: &str, name: &str) -> Light {
+        let json = json!({
+            "state": {"on": false, "reachable": true},
+            "swupdate": {"state": "noupdates", "lastinstall": null},
+            "type": "Dimmable light",
+            "name": name,
+            "modelid": "LWB010",
+            "capabilities": {
+                "certified": true,
+                "control": {},
+                "streaming": {"renderer": false, "proxy": false}
+            },
+            "config": {
+                "archetype": "classicbulb",
+                "function": "functional",
+                "direction": "omnidirectional"
+            },
+            "uniqueid": format!("00:17:88:01:00:00:00:0{}-0b", id),
+            "swversion": "1.50.2_r30933"
+        });
+        serde_json::from_value::<Light>(json).unwrap().with_id(id)
+    }
+
+    #[test]
+    fn slugify() {
+        assert_eq!(super::slugify("Living room"), "living_room");
+        assert_eq!(super::slugify(" Hue lamp (1) "), "hue_lamp_1");
+        assert_eq!(super::slugify("!!"), "unnamed");
+    }
+
+    #[test]
+    fn export() {
+        let lights = vec![
+            light("10", "Ceiling"),
+            light("2", "Ceiling"),
+            light("1", "Desk"),
+        ];
+        let room = json!({
+            "name": "Living room",
+            "lights": ["1", "2"],
+            "sensors": [],
+            "type": "Room",
+            "class": "Living room"
+        });
+        let zone = json!({"name": "Zone", "lights": ["10"], "sensors": [], "type": "Zone"});
+        let groups = vec![
+            serde_json::from_value::<Group>(room).unwrap().with_id("1"),
+            serde_json::from_value::<Group>(zone).unwrap().with_id("2"),
+        ];
+        let scene = json!({
+            "name": "Relax",
+            "type": "GroupScene",
+            "group": "1",
+            "owner": "user",
+            "recycle": false,
+            "locked": false,
+            "appdata": {},
+            "version": 2
+        });
+        let scenes = vec![serde_json::from_value::<Scene>(scene)
+            .unwrap()
+            .with_id("abc")];
+
+        let export = HomeAssistantExport::new(&lights, &groups, &scenes);
+        let export_json = serde_json::to_value(&export).unwrap();
+        let expected_json = json!({
+            "light": [
+                {
+                    "entity_id": "light.desk",
+                    "name": "Desk",
+                    "unique_id": "00:17:88:01:00:00:00:01-0b",
+                    "model": "LWB010",
+                    "area": "living_room"
+                },
+                {
+                    "entity_id": "light.ceiling",
+                    "name": "Ceiling",
+                    "unique_id": "00:17:88:01:00:00:00:02-0b",
+                    "model": "LWB010",
+                    "area": "living_room"
+                },
+                {
+                    "entity_id": "light.ceiling_2",
+                    "name": "Ceiling",
+                    "unique_id": "00:17:88:01:00:00:00:010-0b",
+                    "model": "LWB010"
+                }
+            ],
+            "area": [
+                {
+                    "id": "living_room",
+                    "name": "Living room",
+                    "class": "Living room",
+                    "lights": ["light.desk", "light.ceiling"]
+                }
+            ],
+            "scene": [
+                {
+                    "entity_id": "scene.living_room_relax",
+                    "name": "Relax",
+                    "scene_id": "abc",
+                    "area": "living_room",
+                    "lights": ["light.desk", "light.ceiling"]
+                }
+            ]
+        });
+        assert_eq!(export_json, expected_json);
+        assert!(export.to_yaml().unwrap().starts_with("light:\n"));
+    }
+}
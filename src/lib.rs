@@ -13,21 +13,29 @@
 //! [serde]: https://github.com/serde-rs/serde
 //! [serde_json]: https://github.com/serde-rs/json
 //! [serde_repr]: https://github.com/dtolnay/serde-repr
+//! [tracing]: https://github.com/tokio-rs/tracing
 //!
 //! # Features
 //!
 //! - `upnp-description`: Adds support for accessing the UPnP description of a bridge. See the
-//! [`bridge::Description`] struct for more information.
+//!   [`bridge::Description`] struct for more information.
 //! - `old-api`: Minimal effort support for older api versions. Useful for users of the no longer
-//! supported Hue v1 bridge. This lowers the supported API version to `1.16` not all features
-//! are guarenteed to work.
+//!   supported Hue v1 bridge. This lowers the supported API version to `1.16` not all features
+//!   are guarenteed to work.
+//! - `tracing`: Emits [tracing] events for every request sent to the bridge, including the request
+//!   method, URL suffix, body, response status and duration.
+//! - `yaml-export`: Adds support for exporting lights, rooms and scenes as YAML document using
+//!   the naming conventions of Home Assistant. See the [`export::HomeAssistantExport`] struct for
+//!   more information.
 //!
 //! # Connecting to a bridge
 //!
 //! To connect to a bridge, the IP address of the bridge and the name of a registered user is
 //! needed. You can use the [`bridge::discover_nupnp`] function to get the IP addresses of bridges
 //! that are in the local network and the [`bridge::register_user`] function to register a new user
-//! on a bridge.
+//! on a bridge. The [`bridge::DiscoveryCache`] can be used to avoid discovering bridges every
+//! time the program is started. The [`bridge::get_unauthenticated_config`] function returns the
+//! identifier and API version of a bridge without a registered user.
 //!
 //! To able to send requests to the bridge, a [`Bridge`] must be created. For example:
 //! ```no_run
@@ -59,6 +67,14 @@
 //! - [`Modifier::execute`]: Can be used instead of `Bridge::set_*` methods.
 //! - [`Scanner::execute`]: Can be used instead of `Bridge::search_new_*` methods
 //!
+//! # Wire format
+//!
+//! Creators, modifiers and scanners serialize to exactly the attribute names that are documented
+//! in the Philips Hue API. Attributes that are `None` are omitted. Attributes that take an
+//! [`Adjust`] value are serialized with the plain attribute name (e.g. `bri`) when overridden
+//! and with the `_inc` suffix (e.g. `bri_inc`) when incremented or decremented, where
+//! decrements are negative values.
+//!
 //! # Examples
 //!
 //! _Note: In the following examples the creation of `bridge` is abbreviated to reduce irrelevant
@@ -80,7 +96,7 @@
 //!     # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
 //!     // let bridge = Bridge::new(...);
 //!     let creator = group::Creator::new("example".into(), vec!["1".into()])
-//!         .with_class("Office".to_string());
+//!         .with_class(group::Class::Office);
 //!     let id = bridge.create_group(&creator)?;
 //!     println!("Created group with id `{}`", id);
 //!     # Ok(())
@@ -99,7 +115,7 @@
 //!     # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
 //!     // let bridge = Bridge::new(...);
 //!     let id = group::Creator::new("example".into(), vec!["1".into()])
-//!         .with_class("Office".to_string())
+//!         .with_class(group::Class::Office)
 //!         .execute(&bridge)?;
 //!     println!("Created group with id `{}`", id);
 //!     # Ok(())
@@ -204,6 +220,7 @@
 //! [`Modifier::execute`]: resource::Modifier::execute
 //! [`Scanner::execute`]: resource::Scanner::execute
 //! [`group::Creator`]: resource::group::Creator
+//! [`Adjust`]: resource::Adjust
 
 #![forbid(unsafe_code)]
 #![warn(rust_2018_idioms, missing_docs, missing_debug_implementations)]
@@ -217,10 +234,17 @@ mod error;
 pub mod bridge;
 /// Module for generating colors.
 pub mod color;
+/// Module for exporting the configuration of a bridge to other home-automation systems.
+#[cfg(feature = "yaml-export")]
+pub mod export;
+/// Module for common tasks that involve multiple resources.
+pub mod recipes;
 /// Module for bridge resources.
 pub mod resource;
 /// Responses returned from the Philips Hue API.
 pub mod response;
+/// Module for units of light attributes.
+pub mod units;
 
 pub use bridge::Bridge;
 pub use color::Color;
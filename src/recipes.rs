@@ -0,0 +1,988 @@
+use crate::resource::resourcelink::{self, Link, LinkKind};
+use crate::resource::schedule::{self, LocalTime, Weekdays};
+use crate::resource::{group, light, scene, Adjust, Alert, StateTarget};
+use crate::{response::Modified, util, Bridge, Response, Result};
+use derive_setters::Setters;
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Description of the schedules that are created for alarms.
+const ALARM_DESCRIPTION: &str = "huelib2 alarm";
+
+/// Time between the pre-step of an alarm and the start of the fade.
+const ALARM_PRE_STEP: Duration = Duration::from_secs(10);
+
+/// Description of the resourcelinks that are created for wake up routines.
+const WAKE_UP_DESCRIPTION: &str = "huelib2 wake up";
+
+/// Class identifier of the resourcelinks that are created for wake up routines.
+const WAKE_UP_CLASS_ID: u16 = 1;
+
+/// Color temperature in mired at the end of a wake up routine.
+const WAKE_UP_COLOR_TEMPERATURE: u16 = 370;
+
+/// Description of the schedules that stop breathe cycles.
+const BREATHE_DESCRIPTION: &str = "huelib2 breathe";
+
+/// Maximum duration of the `lselect` alert effect.
+const MAX_BREATHE_DURATION: Duration = Duration::from_secs(15);
+
+/// Alarm that was created with [`alarm`].
+///
+/// An alarm consists of a scene with the target state of the lights, a schedule that recalls
+/// the scene and, if possible, a pre-step schedule that turns the lights on at the lowest
+/// brightness shortly before. The handle contains the identifiers of the resources, so the alarm
+/// can be modified or deleted later.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Alarm {
+    scene_id: String,
+    schedule_id: String,
+    pre_step_schedule_id: Option<String>,
+}
+
+impl Alarm {
+    /// Creates a handle for an alarm that already exists on the bridge.
+    pub fn new<S1, S2>(scene_id: S1, schedule_id: S2) -> Self
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        Self {
+            scene_id: scene_id.into(),
+            schedule_id: schedule_id.into(),
+            pre_step_schedule_id: None,
+        }
+    }
+
+    /// Sets the identifier of the pre-step schedule.
+    pub fn with_pre_step_schedule_id<S>(mut self, pre_step_schedule_id: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.pre_step_schedule_id = Some(pre_step_schedule_id.into());
+        self
+    }
+
+    /// Returns the identifier of the scene.
+    pub fn scene_id(&self) -> &str {
+        &self.scene_id
+    }
+
+    /// Returns the identifier of the schedule.
+    pub fn schedule_id(&self) -> &str {
+        &self.schedule_id
+    }
+
+    /// Returns the identifier of the pre-step schedule, if the alarm has one.
+    pub fn pre_step_schedule_id(&self) -> Option<&str> {
+        self.pre_step_schedule_id.as_deref()
+    }
+
+    /// Changes the time when the alarm starts.
+    ///
+    /// The pre-step schedule is moved accordingly. It is disabled if the new time does not allow a
+    /// pre-step, see [`alarm`].
+    pub fn set_time(&self, bridge: &Bridge, time: LocalTime) -> Result<Vec<Response<Modified>>> {
+        let mut responses = Vec::new();
+        if let Some(id) = &self.pre_step_schedule_id {
+            let modifier = match pre_step_time(&time) {
+                Some(v) => schedule::Modifier::new()
+                    .with_local_time(v)
+                    .with_status(schedule::Status::Enabled),
+                None => schedule::Modifier::new().with_status(schedule::Status::Disabled),
+            };
+            responses.extend(bridge.set_schedule(id.as_str(), &modifier)?);
+        }
+        let modifier = schedule::Modifier::new().with_local_time(time);
+        responses.extend(bridge.set_schedule(self.schedule_id.as_str(), &modifier)?);
+        Ok(responses)
+    }
+
+    /// Deletes the schedules and the scene of the alarm.
+    pub fn delete(self, bridge: &Bridge) -> Result<()> {
+        if let Some(id) = self.pre_step_schedule_id {
+            bridge.delete_schedule(id)?;
+        }
+        bridge.delete_schedule(self.schedule_id)?;
+        bridge.delete_scene(self.scene_id)
+    }
+}
+
+/// Creates an alarm that slowly turns on the lights of a group.
+///
+/// A scene with the target state for all lights of the group is created, together with a
+/// schedule that recalls the scene at the given local time. The lights fade to the target state
+/// during `fade`, starting at the local time, so the fade duration has to be subtracted from the
+/// local time to reach the target state at a specific time. The fade duration is rounded to
+/// multiples of 100ms and limited to the maximum transition time of about 109 minutes.
+///
+/// To fade in like a sunrise instead of from the last state of the lights, a pre-step schedule
+/// turns the lights on at the lowest brightness 10 seconds before the local time. The pre-step is
+/// skipped for randomized times, recurring timers and timers of less than 11 seconds, because it
+/// could not be guaranteed to run before the fade.
+///
+/// If a schedule cannot be created, the already created resources are deleted again.
+///
+/// # Examples
+///
+/// Wake up at 7:00 on weekdays with a fade of 30 minutes:
+/// ```no_run
+/// use huelib2::{recipes, resource::light};
+/// use std::time::Duration;
+///
+/// # fn main() -> huelib2::Result<()> {
+/// # use huelib2::Bridge;
+/// # use std::net::{IpAddr, Ipv4Addr};
+/// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+/// // let bridge = Bridge::new(...);
+/// let target_state = light::StaticStateModifier::new()
+///     .with_brightness(254)
+///     .with_color_temperature(250);
+/// let alarm = recipes::alarm(
+///     &bridge,
+///     "W124/T06:30:00".parse()?,
+///     "1",
+///     &target_state,
+///     Duration::from_secs(30 * 60),
+/// )?;
+/// alarm.set_time(&bridge, "W124/T07:00:00".parse()?)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn alarm<S>(
+    bridge: &Bridge,
+    time: LocalTime,
+    group: S,
+    target_state: &light::StaticStateModifier,
+    fade: Duration,
+) -> Result<Alarm>
+where
+    S: Into<String>,
+{
+    let group = group.into();
+    let lights = bridge.get_group(group.as_str())?.lights;
+    create_alarm(bridge, time, group, lights, target_state, fade)
+}
+
+fn create_alarm(
+    bridge: &Bridge,
+    time: LocalTime,
+    group: String,
+    lights: Vec<String>,
+    target_state: &light::StaticStateModifier,
+    fade: Duration,
+) -> Result<Alarm> {
+    let mut target_state = target_state.clone();
+    if target_state.on.is_none() {
+        target_state.on = Some(true);
+    }
+    let light_states: HashMap<String, light::StaticStateModifier> = lights
+        .iter()
+        .map(|v| (v.clone(), target_state.clone()))
+        .collect();
+    let name = format!("Alarm {}", group);
+    let scene_creator =
+        scene::Creator::light_scene(name.clone(), lights).with_light_states(light_states);
+    let scene_id = bridge.create_scene(&scene_creator)?;
+
+    let create_schedule = |modifier: &group::StateModifier, time, name| {
+        let command = schedule::Command::from_modifier(modifier, group.clone(), bridge.username())?;
+        let creator = schedule::Creator::new(command, time)
+            .with_name(name)
+            .with_description(ALARM_DESCRIPTION.to_owned());
+        bridge.create_schedule(&creator)
+    };
+    let pre_step_schedule_id = match pre_step_time(&time) {
+        Some(pre_step_time) => {
+            let modifier = group::StateModifier::new()
+                .with_on(true)
+                .with_brightness(Adjust::Override(1))
+                .with_transition_time(0);
+            match create_schedule(&modifier, pre_step_time, format!("{} start", name)) {
+                Ok(v) => Some(v),
+                Err(e) => {
+                    let _ = bridge.delete_scene(scene_id);
+                    return Err(e);
+                }
+            }
+        }
+        None => None,
+    };
+    let modifier = group::StateModifier::new()
+        .with_scene(scene_id.clone())
+        .with_transition_duration(fade);
+    match create_schedule(&modifier, time, name) {
+        Ok(schedule_id) => Ok(Alarm {
+            scene_id,
+            schedule_id,
+            pre_step_schedule_id,
+        }),
+        Err(e) => {
+            if let Some(id) = pre_step_schedule_id {
+                let _ = bridge.delete_schedule(id);
+            }
+            let _ = bridge.delete_scene(scene_id);
+            Err(e)
+        }
+    }
+}
+
+/// Returns the time of the pre-step of an alarm that starts at the given time.
+///
+/// Returns `None` if the pre-step could not be guaranteed to run before the alarm.
+fn pre_step_time(time: &LocalTime) -> Option<LocalTime> {
+    let offset = chrono::Duration::from_std(ALARM_PRE_STEP).ok()?;
+    match *time {
+        LocalTime::AbsoluteTime(v) => Some(LocalTime::AbsoluteTime(v - offset)),
+        LocalTime::RecurringTime { weekdays, time } => {
+            let (time, overflow) = time.overflowing_sub_signed(offset);
+            let weekdays = match overflow {
+                0 => weekdays,
+                _ => previous_weekdays(weekdays),
+            };
+            Some(LocalTime::RecurringTime { weekdays, time })
+        }
+        LocalTime::Timer(v) => v
+            .checked_sub(ALARM_PRE_STEP)
+            .filter(|v| v.as_secs() >= 1)
+            .map(LocalTime::Timer),
+        _ => None,
+    }
+}
+
+/// Returns the weekdays that precede the given weekdays, e.g. Sunday for Monday.
+fn previous_weekdays(weekdays: Weekdays) -> Weekdays {
+    let bits = weekdays.bits();
+    Weekdays::from_bits(((bits << 1) | (bits >> 6)) & Weekdays::ALL.bits()).unwrap_or_default()
+}
+
+/// Wake up routine that was created with [`wake_up`].
+///
+/// A wake up routine is an [`Alarm`] whose scene, schedule and group are referenced by a
+/// resourcelink, so all resources of the routine can be found and deleted with only the identifier
+/// of the resourcelink.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct WakeUp {
+    resourcelink_id: String,
+    alarm: Alarm,
+}
+
+impl WakeUp {
+    /// Returns the identifier of the resourcelink.
+    pub fn resourcelink_id(&self) -> &str {
+        &self.resourcelink_id
+    }
+
+    /// Returns the alarm of the wake up routine.
+    pub fn alarm(&self) -> &Alarm {
+        &self.alarm
+    }
+
+    /// Deletes the resourcelink, the schedule and the scene of the wake up routine.
+    pub fn delete(self, bridge: &Bridge) -> Result<()> {
+        remove_wake_up(bridge, self.resourcelink_id)
+    }
+}
+
+/// Creates a wake up routine that slowly turns on the lights of a group like a sunrise.
+///
+/// This creates an [`alarm`] that fades the lights from the lowest brightness to full brightness
+/// with a warm white color during `fade`, starting at the given local time. The scene, the
+/// schedules and the group are linked with a resourcelink, which is used by [`remove_wake_up`] to
+/// delete the routine. If a resource cannot be created, the already created resources are deleted
+/// again.
+///
+/// # Examples
+///
+/// Wake up at 7:00 on weekdays with a sunrise of 30 minutes:
+/// ```no_run
+/// use huelib2::recipes;
+/// use std::time::Duration;
+///
+/// # fn main() -> huelib2::Result<()> {
+/// # use huelib2::Bridge;
+/// # use std::net::{IpAddr, Ipv4Addr};
+/// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+/// // let bridge = Bridge::new(...);
+/// let routine = recipes::wake_up(
+///     &bridge,
+///     "1",
+///     "W124/T06:30:00".parse()?,
+///     Duration::from_secs(30 * 60),
+/// )?;
+/// recipes::remove_wake_up(&bridge, routine.resourcelink_id())?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn wake_up<S>(bridge: &Bridge, group: S, time: LocalTime, fade: Duration) -> Result<WakeUp>
+where
+    S: Into<String>,
+{
+    let group = group.into();
+    let lights = bridge.get_group(group.as_str())?.lights;
+    create_wake_up(bridge, group, lights, time, fade)
+}
+
+fn create_wake_up(
+    bridge: &Bridge,
+    group: String,
+    lights: Vec<String>,
+    time: LocalTime,
+    fade: Duration,
+) -> Result<WakeUp> {
+    let target_state = light::StaticStateModifier::new()
+        .with_brightness(254)
+        .with_color_temperature(WAKE_UP_COLOR_TEMPERATURE);
+    let alarm = create_alarm(bridge, time, group.clone(), lights, &target_state, fade)?;
+    let mut links: Vec<Link> = alarm
+        .pre_step_schedule_id
+        .iter()
+        .chain(Some(&alarm.schedule_id))
+        .map(|v| Link {
+            kind: LinkKind::Schedule,
+            id: v.clone(),
+        })
+        .collect();
+    links.push(Link {
+        kind: LinkKind::Scene,
+        id: alarm.scene_id.clone(),
+    });
+    links.push(Link {
+        kind: LinkKind::Group,
+        id: group.clone(),
+    });
+    let creator = resourcelink::Creator::new(format!("Wake up {}", group), WAKE_UP_CLASS_ID, links)
+        .with_description(WAKE_UP_DESCRIPTION.to_owned());
+    match bridge.create_resourcelink(&creator) {
+        Ok(resourcelink_id) => Ok(WakeUp {
+            resourcelink_id,
+            alarm,
+        }),
+        Err(e) => {
+            let _ = alarm.delete(bridge);
+            Err(e)
+        }
+    }
+}
+
+/// Deletes a wake up routine that was created with [`wake_up`].
+///
+/// The linked schedules are deleted first, followed by the linked scenes and the resourcelink.
+/// Linked groups are not deleted. If a resource cannot be deleted, the resourcelink is kept, so
+/// the removal can be retried; resources that were already deleted are skipped then.
+pub fn remove_wake_up<S>(bridge: &Bridge, resourcelink_id: S) -> Result<()>
+where
+    S: Into<String>,
+{
+    let resourcelink_id = resourcelink_id.into();
+    let links = bridge.get_resourcelink(resourcelink_id.as_str())?.links;
+    // Schedules are deleted before scenes, because the bridge locks scenes that are used by
+    // schedules.
+    for link in links.iter().filter(|v| v.kind == LinkKind::Schedule) {
+        ignore_not_found(bridge.delete_schedule(link.id.as_str()))?;
+    }
+    for link in links.iter().filter(|v| v.kind == LinkKind::Scene) {
+        ignore_not_found(bridge.delete_scene(link.id.as_str()))?;
+    }
+    bridge.delete_resourcelink(resourcelink_id)
+}
+
+/// Treats the deletion of a resource that does not exist as success.
+fn ignore_not_found(result: Result<()>) -> Result<()> {
+    match result {
+        Err(e) if e.is_not_found() => Ok(()),
+        v => v,
+    }
+}
+
+/// Lets a light perform breathe cycles for about the given duration.
+///
+/// This starts the `lselect` alert effect and creates a schedule on the bridge that stops the
+/// alert effect, so the function returns immediately. The duration is rounded up to whole seconds
+/// and limited to 15 seconds, after which the bridge stops the alert effect by itself. The schedule
+/// is deleted by the bridge after it ran. Returns the identifier of the schedule.
+///
+/// Use [`Bridge::stop_light_alert`] to stop the breathe cycles earlier.
+///
+/// # Examples
+///
+/// Flash a light for 5 seconds:
+/// ```no_run
+/// use huelib2::recipes;
+/// use std::time::Duration;
+///
+/// # fn main() -> huelib2::Result<()> {
+/// # use huelib2::Bridge;
+/// # use std::net::{IpAddr, Ipv4Addr};
+/// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+/// // let bridge = Bridge::new(...);
+/// recipes::breathe_light(&bridge, "1", Duration::from_secs(5))?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn breathe_light<S>(bridge: &Bridge, id: S, duration: Duration) -> Result<String>
+where
+    S: Into<String>,
+{
+    let id = id.into();
+    let stop = light::StateModifier::new().with_alert(Alert::None);
+    let command = schedule::Command::from_modifier(&stop, id.clone(), bridge.username())?;
+    let start = light::StateModifier::new().with_alert(Alert::LSelect);
+    bridge.set_light_state(id, &start)?;
+    schedule_stop(bridge, command, duration)
+}
+
+/// Lets all lights of a group perform breathe cycles for about the given duration.
+///
+/// See [`breathe_light`] for more information.
+pub fn breathe_group<S>(bridge: &Bridge, id: S, duration: Duration) -> Result<String>
+where
+    S: Into<String>,
+{
+    let id = id.into();
+    let stop = group::StateModifier::new().with_alert(Alert::None);
+    let command = schedule::Command::from_modifier(&stop, id.clone(), bridge.username())?;
+    let start = group::StateModifier::new().with_alert(Alert::LSelect);
+    bridge.set_group_state(id, &start)?;
+    schedule_stop(bridge, command, duration)
+}
+
+/// Creates a schedule that runs the command after the duration and is deleted afterwards.
+fn schedule_stop(
+    bridge: &Bridge,
+    command: schedule::Command,
+    duration: Duration,
+) -> Result<String> {
+    let duration = duration.min(MAX_BREATHE_DURATION);
+    let seconds = (duration.as_secs() + u64::from(duration.subsec_nanos() > 0)).max(1);
+    let creator = schedule::Creator::new(command, LocalTime::Timer(Duration::from_secs(seconds)))
+        .with_description(BREATHE_DESCRIPTION.to_owned())
+        .with_auto_delete(true);
+    bridge.create_schedule(&creator)
+}
+
+/// Fade of a light or group from one state to another over an arbitrary duration.
+///
+/// The transition time of a single command is limited to about 109 minutes, and long transitions
+/// at a low brightness are not smooth. A fade instead sends a command at every step, which
+/// transitions to the interpolated state of the next step. Steps that do not change the state are
+/// skipped, so the light is not sent redundant commands at a low brightness.
+///
+/// The brightness, hue, saturation, color space coordinates and color temperature are
+/// interpolated linearly if they are set in both states. The first step sets the `from` state and
+/// the last step sets the whole `to` state.
+///
+/// # Examples
+///
+/// Fade a group to full brightness over two hours in the background:
+/// ```no_run
+/// use huelib2::recipes::Fade;
+/// use huelib2::resource::{light, StateTarget};
+/// use std::time::Duration;
+///
+/// # fn main() -> huelib2::Result<()> {
+/// # use huelib2::Bridge;
+/// # use std::net::{IpAddr, Ipv4Addr};
+/// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+/// // let bridge = Bridge::new(...);
+/// let from = light::StaticStateModifier::new()
+///     .with_on(true)
+///     .with_brightness(1);
+/// let to = light::StaticStateModifier::new().with_brightness(254);
+/// let fade = Fade::new(from, to, Duration::from_secs(2 * 60 * 60))
+///     .with_step(Duration::from_secs(10))
+///     .start(bridge, StateTarget::Group("1".into()));
+/// // ...
+/// fade.cancel()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Setters)]
+#[setters(prefix = "with_")]
+pub struct Fade {
+    /// State at the start of the fade.
+    #[setters(skip)]
+    pub from: light::StaticStateModifier,
+    /// State at the end of the fade.
+    #[setters(skip)]
+    pub to: light::StaticStateModifier,
+    /// Duration of the fade.
+    #[setters(skip)]
+    pub duration: Duration,
+    /// Time between two steps of the fade. Defaults to 1 second.
+    ///
+    /// The bridge handles about 10 light commands or 1 group command per second, so the step
+    /// should not be shorter than that.
+    pub step: Duration,
+}
+
+impl Fade {
+    /// Creates a new [`Fade`].
+    pub fn new(
+        from: light::StaticStateModifier,
+        to: light::StaticStateModifier,
+        duration: Duration,
+    ) -> Self {
+        Self {
+            from,
+            to,
+            duration,
+            step: Duration::from_secs(1),
+        }
+    }
+
+    /// Runs the fade and blocks until it has finished.
+    pub fn run(&self, bridge: &Bridge, target: &StateTarget) -> Result<()> {
+        let (_sender, receiver) = mpsc::channel();
+        self.run_until(bridge, target, &receiver)
+    }
+
+    /// Runs the fade in a background thread.
+    ///
+    /// The fade is cancelled when [`FadeHandle::cancel`] is called or the handle is dropped.
+    pub fn start(self, bridge: Bridge, target: StateTarget) -> FadeHandle {
+        let (sender, receiver) = mpsc::channel();
+        let thread = thread::spawn(move || self.run_until(&bridge, &target, &receiver));
+        FadeHandle { sender, thread }
+    }
+
+    /// Runs the fade until all steps are sent or a message is received or the sender is dropped.
+    fn run_until(
+        &self,
+        bridge: &Bridge,
+        target: &StateTarget,
+        cancel: &Receiver<()>,
+    ) -> Result<()> {
+        let start = Instant::now();
+        for step in self.plan() {
+            let wait = (start + step.at).saturating_duration_since(Instant::now());
+            match cancel.recv_timeout(wait) {
+                Err(RecvTimeoutError::Timeout) => {}
+                _ => return Ok(()),
+            }
+            let responses = match target {
+                StateTarget::Light(id) => {
+                    bridge.set_light_state(id.as_str(), &light_modifier(&step.state))
+                }
+                StateTarget::Group(id) => {
+                    bridge.set_group_state(id.as_str(), &group_modifier(&step.state))
+                }
+            }?;
+            for response in responses {
+                response.into_result()?;
+            }
+        }
+        let _ =
+            cancel.recv_timeout((start + self.duration).saturating_duration_since(Instant::now()));
+        Ok(())
+    }
+
+    /// Returns the states that are sent, together with the time since the start of the fade.
+    ///
+    /// Every command is sent when the previous state was reached and transitions to the next
+    /// state that differs from it.
+    fn plan(&self) -> Vec<FadeStep> {
+        let count = match self.step.as_nanos() {
+            0 => 1,
+            v => self.duration.as_nanos().div_ceil(v).max(1),
+        };
+        let mut states = vec![(Duration::ZERO, self.from.clone())];
+        let mut last = self.interpolate(0.0);
+        for i in 1..count {
+            let state = self.interpolate(i as f64 / count as f64);
+            if state != last {
+                last = state.clone();
+                states.push((self.duration.mul_f64(i as f64 / count as f64), state));
+            }
+        }
+        states.push((self.duration, self.to.clone()));
+
+        let mut steps = vec![FadeStep {
+            at: Duration::ZERO,
+            state: light::StaticStateModifier {
+                transition_time: Some(0),
+                ..self.from.clone()
+            },
+        }];
+        for window in states.windows(2) {
+            let ((previous, _), (at, state)) = (&window[0], &window[1]);
+            steps.push(FadeStep {
+                at: *previous,
+                state: light::StaticStateModifier {
+                    transition_time: Some(util::transition_time(*at - *previous)),
+                    ..state.clone()
+                },
+            });
+        }
+        steps
+    }
+
+    /// Returns the interpolated values of the attributes that are set in both states.
+    fn interpolate(&self, t: f64) -> light::StaticStateModifier {
+        let lerp = |a: f64, b: f64| a + (b - a) * t;
+        light::StaticStateModifier {
+            brightness: self
+                .from
+                .brightness
+                .zip(self.to.brightness)
+                .map(|(a, b)| lerp(a as f64, b as f64).round() as u8),
+            hue: self
+                .from
+                .hue
+                .zip(self.to.hue)
+                .map(|(a, b)| lerp(a as f64, b as f64).round() as u16),
+            saturation: self
+                .from
+                .saturation
+                .zip(self.to.saturation)
+                .map(|(a, b)| lerp(a as f64, b as f64).round() as u8),
+            color_space_coordinates: self
+                .from
+                .color_space_coordinates
+                .zip(self.to.color_space_coordinates)
+                .map(|(a, b)| {
+                    let round = |v: f64| ((v * 10_000.0).round() / 10_000.0) as f32;
+                    (
+                        round(lerp(a.0 as f64, b.0 as f64)),
+                        round(lerp(a.1 as f64, b.1 as f64)),
+                    )
+                }),
+            color_temperature: self
+                .from
+                .color_temperature
+                .zip(self.to.color_temperature)
+                .map(|(a, b)| lerp(a as f64, b as f64).round() as u16),
+            ..Default::default()
+        }
+    }
+}
+
+/// Handle for a fade that runs in a background thread.
+///
+/// This is returned by [`Fade::start`]. Dropping the handle cancels the fade.
+#[derive(Debug)]
+pub struct FadeHandle {
+    sender: Sender<()>,
+    thread: JoinHandle<Result<()>>,
+}
+
+impl FadeHandle {
+    /// Returns whether the fade has finished.
+    pub fn is_finished(&self) -> bool {
+        self.thread.is_finished()
+    }
+
+    /// Cancels the fade and waits for the background thread to stop.
+    ///
+    /// The lights keep the state of the last step that was sent.
+    pub fn cancel(self) -> Result<()> {
+        let _ = self.sender.send(());
+        self.thread.join().expect("fade thread panicked")
+    }
+
+    /// Waits until the fade has finished.
+    pub fn join(self) -> Result<()> {
+        let result = self.thread.join().expect("fade thread panicked");
+        drop(self.sender);
+        result
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct FadeStep {
+    at: Duration,
+    state: light::StaticStateModifier,
+}
+
+fn light_modifier(state: &light::StaticStateModifier) -> light::StateModifier {
+    light::StateModifier {
+        on: state.on,
+        brightness: state.brightness.map(Adjust::Override),
+        hue: state.hue.map(Adjust::Override),
+        saturation: state.saturation.map(Adjust::Override),
+        color_space_coordinates: state.color_space_coordinates.map(Adjust::Override),
+        color_temperature: state.color_temperature.map(Adjust::Override),
+        effect: state.effect.clone(),
+        transition_time: state.transition_time,
+        ..Default::default()
+    }
+}
+
+fn group_modifier(state: &light::StaticStateModifier) -> group::StateModifier {
+    group::StateModifier {
+        on: state.on,
+        brightness: state.brightness.map(Adjust::Override),
+        hue: state.hue.map(Adjust::Override),
+        saturation: state.saturation.map(Adjust::Override),
+        color_space_coordinates: state.color_space_coordinates.map(Adjust::Override),
+        color_temperature: state.color_temperature.map(Adjust::Override),
+        effect: state.effect.clone(),
+        transition_time: state.transition_time,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::RequestMethod;
+    use serde_json::json;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    #[test]
+    fn create_alarm() {
+        let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "user").with_dry_run();
+        let target_state = light::StaticStateModifier::new().with_brightness(254);
+        let alarm = super::create_alarm(
+            &bridge,
+            "W124/T06:30:00".parse().unwrap(),
+            "1".into(),
+            vec!["2".into()],
+            &target_state,
+            Duration::from_secs(30 * 60),
+        )
+        .unwrap();
+        alarm
+            .set_time(&bridge, "W124/T07:00:00".parse().unwrap())
+            .unwrap();
+
+        let requests = bridge.take_recorded_requests();
+        assert_eq!(requests.len(), 5);
+        assert_eq!(requests[0].path, "scenes");
+        assert_eq!(
+            requests[0].body,
+            Some(json!({
+                "name": "Alarm 1",
+                "type": "LightScene",
+                "lights": ["2"],
+                "lightstates": {"2": {"on": true, "bri": 254}}
+            }))
+        );
+        assert_eq!(
+            requests[1].body,
+            Some(json!({
+                "name": "Alarm 1 start",
+                "description": "huelib2 alarm",
+                "command": {
+                    "address": "/api/user/groups/1/action",
+                    "method": "PUT",
+                    "body": {"on": true, "bri": 1, "transitiontime": 0}
+                },
+                "localtime": "W124/T06:29:50"
+            }))
+        );
+        assert_eq!(
+            requests[2].body,
+            Some(json!({
+                "name": "Alarm 1",
+                "description": "huelib2 alarm",
+                "command": {
+                    "address": "/api/user/groups/1/action",
+                    "method": "PUT",
+                    "body": {"scene": "", "transitiontime": 18000}
+                },
+                "localtime": "W124/T06:30:00"
+            }))
+        );
+        assert_eq!(requests[3].method, RequestMethod::Put);
+        assert_eq!(
+            requests[3].body,
+            Some(json!({"localtime": "W124/T06:59:50", "status": "enabled"}))
+        );
+        assert_eq!(
+            requests[4].body,
+            Some(json!({"localtime": "W124/T07:00:00"}))
+        );
+    }
+
+    #[test]
+    fn pre_step_time() {
+        let time = |s: &str| s.parse::<LocalTime>().unwrap();
+        let cases = vec![
+            ("2020-01-01T06:30:00", Some("2020-01-01T06:29:50")),
+            ("2020-01-01T00:00:05", Some("2019-12-31T23:59:55")),
+            ("W124/T06:30:00", Some("W124/T06:29:50")),
+            // Monday and Wednesday at midnight become Sunday and Tuesday.
+            ("W080/T00:00:00", Some("W033/T23:59:50")),
+            ("PT00:10:00", Some("PT00:09:50")),
+            ("PT00:00:10", None),
+            ("2020-01-01T06:30:00A00:10:00", None),
+            ("R/PT00:10:00", None),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(
+                super::pre_step_time(&time(input)),
+                expected.map(time),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn create_wake_up() {
+        let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "user").with_dry_run();
+        super::create_wake_up(
+            &bridge,
+            "1".into(),
+            vec!["2".into()],
+            "W124/T06:30:00".parse().unwrap(),
+            Duration::from_secs(30 * 60),
+        )
+        .unwrap();
+
+        let requests = bridge.take_recorded_requests();
+        assert_eq!(requests.len(), 4);
+        assert_eq!(requests[0].path, "scenes");
+        assert_eq!(
+            requests[0].body.as_ref().unwrap()["lightstates"],
+            json!({"2": {"on": true, "bri": 254, "ct": 370}})
+        );
+        assert_eq!(requests[1].path, "schedules");
+        assert_eq!(requests[2].path, "schedules");
+        assert_eq!(requests[3].path, "resourcelinks");
+        assert_eq!(
+            requests[3].body,
+            Some(json!({
+                "name": "Wake up 1",
+                "description": "huelib2 wake up",
+                "classid": 1,
+                "links": ["/schedules/", "/schedules/", "/scenes/", "/groups/1"]
+            }))
+        );
+    }
+
+    #[test]
+    fn remove_wake_up() {
+        let server = crate::bridge::test_server::TestServer::start(vec![
+            json!({
+                "name": "Wake up 1",
+                "description": "huelib2 wake up",
+                "owner": "user",
+                "type": "Link",
+                "classid": 1,
+                "recycle": false,
+                "links": ["/schedules/1", "/schedules/2", "/scenes/abc", "/groups/1"]
+            }),
+            json!([{"success": "/schedules/1 deleted"}]),
+            json!([{"error": {
+                "type": 3,
+                "address": "/schedules/2",
+                "description": "resource, /schedules/2, not available"
+            }}]),
+            json!([{"success": "/scenes/abc deleted"}]),
+            json!([{"success": "/resourcelinks/5 deleted"}]),
+        ]);
+        super::remove_wake_up(&server.bridge(), "5").unwrap();
+        let requests: Vec<_> = server
+            .finish()
+            .into_iter()
+            .map(|v| (v.method, v.path))
+            .collect();
+        assert_eq!(
+            requests,
+            vec![
+                (RequestMethod::Get, "resourcelinks/5".to_owned()),
+                (RequestMethod::Delete, "schedules/1".to_owned()),
+                (RequestMethod::Delete, "schedules/2".to_owned()),
+                (RequestMethod::Delete, "scenes/abc".to_owned()),
+                (RequestMethod::Delete, "resourcelinks/5".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn breathe_light() {
+        let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "user").with_dry_run();
+        super::breathe_light(&bridge, "3", Duration::from_millis(4200)).unwrap();
+        super::breathe_group(&bridge, "1", Duration::from_secs(60)).unwrap();
+
+        let requests = bridge.take_recorded_requests();
+        assert_eq!(requests.len(), 4);
+        assert_eq!(requests[0].path, "lights/3/state");
+        assert_eq!(requests[0].body, Some(json!({"alert": "lselect"})));
+        assert_eq!(
+            requests[1].body,
+            Some(json!({
+                "description": "huelib2 breathe",
+                "command": {
+                    "address": "/api/user/lights/3/state",
+                    "method": "PUT",
+                    "body": {"alert": "none"}
+                },
+                "localtime": "PT00:00:05",
+                "autodelete": true
+            }))
+        );
+        assert_eq!(requests[2].path, "groups/1/action");
+        assert_eq!(
+            requests[3].body.as_ref().unwrap()["localtime"],
+            json!("PT00:00:15")
+        );
+    }
+
+    #[test]
+    fn plan_fade() {
+        let from = light::StaticStateModifier::new()
+            .with_on(true)
+            .with_brightness(1);
+        let to = light::StaticStateModifier::new()
+            .with_brightness(3)
+            .with_color_temperature(400);
+        let fade = Fade::new(from, to, Duration::from_secs(40)).with_step(Duration::from_secs(10));
+        let steps: Vec<_> = fade
+            .plan()
+            .into_iter()
+            .map(|v| (v.at.as_secs(), v.state))
+            .collect();
+        let state = |brightness, transition_time| {
+            light::StaticStateModifier::new()
+                .with_brightness(brightness)
+                .with_transition_time(transition_time)
+        };
+        assert_eq!(
+            steps,
+            vec![
+                (0, state(1, 0).with_on(true)),
+                (0, state(2, 100)),
+                (10, state(3, 200)),
+                (30, state(3, 100).with_color_temperature(400)),
+            ]
+        );
+    }
+
+    #[test]
+    fn run_fade() {
+        let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "user").with_dry_run();
+        let from = light::StaticStateModifier::new().with_brightness(100);
+        let to = light::StaticStateModifier::new().with_brightness(200);
+        let fade =
+            Fade::new(from, to, Duration::from_millis(20)).with_step(Duration::from_millis(10));
+        fade.clone()
+            .start(bridge.clone(), StateTarget::Group("1".into()))
+            .join()
+            .unwrap();
+        let requests = bridge.take_recorded_requests();
+        let bodies: Vec<_> = requests.iter().map(|v| v.body.clone().unwrap()).collect();
+        assert!(requests.iter().all(|v| v.path == "groups/1/action"));
+        assert_eq!(
+            bodies,
+            vec![
+                json!({"bri": 100, "transitiontime": 0}),
+                json!({"bri": 150, "transitiontime": 0}),
+                json!({"bri": 200, "transitiontime": 0}),
+            ]
+        );
+
+        let fade = Fade {
+            duration: Duration::from_secs(60),
+            ..fade
+        };
+        fade.start(bridge.clone(), StateTarget::Light("2".into()))
+            .cancel()
+            .unwrap();
+        assert!(bridge.take_recorded_requests().len() < 100);
+    }
+}
@@ -60,9 +60,20 @@ pub struct Config {
     pub timezone: Option<String>,
     /// The current wireless frequency channel used by the bridge.
     ///
-    /// It can take values of 11, 15, 20, 25 or 0 if undefined (factory new).
-    #[serde(rename = "zigbeechannel")]
-    pub zigbee_channel: u8,
+    /// This is `None` if the channel is undefined (factory new).
+    #[serde(
+        rename = "zigbeechannel",
+        deserialize_with = "deserialize_zigbee_channel"
+    )]
+    pub zigbee_channel: Option<ZigbeeChannel>,
+    /// Indicates whether a change of the wireless frequency channel is pending.
+    ///
+    /// While a change is pending, [`zigbee_channel`] still contains the previous channel. Bridges
+    /// that do not report this attribute are treated as having no pending change.
+    ///
+    /// [`zigbee_channel`]: #structfield.zigbee_channel
+    #[serde(rename = "zigbeechannelchangepending", default)]
+    pub zigbee_channel_change_pending: bool,
     /// Uniquely identifies the hardware model of the bridge.
     #[serde(rename = "modelid")]
     pub model_id: String,
@@ -99,6 +110,188 @@ fn deserialize_whitelist<'de, D: Deserializer<'de>>(
     Ok(map.into_iter().map(|(id, user)| user.with_id(id)).collect())
 }
 
+fn deserialize_zigbee_channel<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<ZigbeeChannel>, D::Error> {
+    match u8::deserialize(deserializer)? {
+        0 => Ok(None),
+        v => Ok(Some(ZigbeeChannel::from_number(v))),
+    }
+}
+
+/// Wireless frequency channel of the ZigBee network.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ZigbeeChannel {
+    /// Channel 11.
+    Channel11,
+    /// Channel 15.
+    Channel15,
+    /// Channel 20.
+    Channel20,
+    /// Channel 25.
+    Channel25,
+    /// Channel that is not one of the channels recommended by Philips.
+    Other(u8),
+}
+
+impl ZigbeeChannel {
+    /// Creates a channel from its number.
+    pub fn from_number(number: u8) -> Self {
+        match number {
+            11 => Self::Channel11,
+            15 => Self::Channel15,
+            20 => Self::Channel20,
+            25 => Self::Channel25,
+            v => Self::Other(v),
+        }
+    }
+
+    /// Returns the number of the channel.
+    pub fn number(self) -> u8 {
+        match self {
+            Self::Channel11 => 11,
+            Self::Channel15 => 15,
+            Self::Channel20 => 20,
+            Self::Channel25 => 25,
+            Self::Other(v) => v,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ZigbeeChannel {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u8::deserialize(deserializer).map(Self::from_number)
+    }
+}
+
+impl Serialize for ZigbeeChannel {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.number())
+    }
+}
+
+/// Configuration of a bridge that can be accessed without a registered user.
+///
+/// See [`bridge::get_unauthenticated_config`] for more information.
+///
+/// [`bridge::get_unauthenticated_config`]: crate::bridge::get_unauthenticated_config
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct UnauthenticatedConfig {
+    /// Name of the bridge.
+    pub name: String,
+    /// Software version of the bridge.
+    #[serde(rename = "swversion")]
+    pub software_version: String,
+    /// The version of the Philips Hue API.
+    #[serde(rename = "apiversion")]
+    pub api_version: String,
+    /// MAC address of the bridge.
+    #[serde(rename = "mac")]
+    pub mac_address: String,
+    /// Uniquely identifies the hardware model of the bridge.
+    #[serde(rename = "modelid")]
+    pub model_id: String,
+    /// The unique bridge id.
+    #[serde(rename = "bridgeid")]
+    pub bridge_id: String,
+    /// Indicates if bridge settings are factory new.
+    #[serde(rename = "factorynew")]
+    pub factory_new: bool,
+    /// Identifier of the bridge where a backup was restored.
+    ///
+    /// If no backup was restored from another bridge, this will be `None`.
+    #[serde(rename = "replacesbridgeid")]
+    pub replaces_bridge_id: Option<String>,
+    /// The version of the datastore.
+    #[serde(rename = "datastoreversion")]
+    pub datastore_version: String,
+    /// Name of the starterkit created in the factory.
+    #[serde(rename = "starterkitid")]
+    pub starterkit_id: String,
+}
+
+/// The full state of a bridge, containing all resources and the configuration.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(from = "deserialize::FullStateWrapper")]
+pub struct FullState {
+    /// All lights that are connected to the bridge.
+    pub lights: Vec<resource::Light>,
+    /// All groups.
+    pub groups: Vec<resource::Group>,
+    /// Configuration of the bridge.
+    pub config: Config,
+    /// All schedules.
+    pub schedules: Vec<resource::Schedule>,
+    /// All scenes.
+    pub scenes: Vec<resource::Scene>,
+    /// All rules.
+    pub rules: Vec<resource::Rule>,
+    /// All sensors that are connected to the bridge.
+    pub sensors: Vec<resource::Sensor>,
+    /// All resourcelinks.
+    pub resourcelinks: Vec<resource::Resourcelink>,
+}
+
+impl From<deserialize::FullStateWrapper> for FullState {
+    fn from(value: deserialize::FullStateWrapper) -> Self {
+        Self {
+            lights: value
+                .lights
+                .into_iter()
+                .map(|(id, v)| v.with_id(id))
+                .collect(),
+            groups: value
+                .groups
+                .into_iter()
+                .map(|(id, v)| v.with_id(id))
+                .collect(),
+            config: value.config,
+            schedules: value
+                .schedules
+                .into_iter()
+                .map(|(id, v)| v.with_id(id))
+                .collect(),
+            scenes: value
+                .scenes
+                .into_iter()
+                .map(|(id, v)| v.with_id(id))
+                .collect(),
+            rules: value
+                .rules
+                .into_iter()
+                .map(|(id, v)| v.with_id(id))
+                .collect(),
+            sensors: value
+                .sensors
+                .into_iter()
+                .map(|(id, v)| v.with_id(id))
+                .collect(),
+            resourcelinks: value
+                .resourcelinks
+                .into_iter()
+                .map(|(id, v)| v.with_id(id))
+                .collect(),
+        }
+    }
+}
+
+mod deserialize {
+    use super::*;
+
+    #[derive(Deserialize)]
+    pub(super) struct FullStateWrapper {
+        pub(super) lights: HashMap<String, resource::Light>,
+        pub(super) groups: HashMap<String, resource::Group>,
+        pub(super) config: Config,
+        pub(super) schedules: HashMap<String, resource::Schedule>,
+        pub(super) scenes: HashMap<String, resource::Scene>,
+        pub(super) rules: HashMap<String, resource::Rule>,
+        pub(super) sensors: HashMap<String, resource::Sensor>,
+        #[serde(default)]
+        pub(super) resourcelinks: HashMap<String, resource::Resourcelink>,
+    }
+}
+
 /// Information about software updates.
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
 pub struct SoftwareUpdate {
@@ -122,8 +315,6 @@ pub struct SoftwareUpdate {
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum SoftwareUpdateState {
-    /// System does not know if new updates are available.
-    Unkown,
     /// No updates are available.
     NoUpdates,
     /// Updates are being transferred to the devices.
@@ -134,6 +325,11 @@ pub enum SoftwareUpdateState {
     AllReadyToInstall,
     /// System update is installing.
     Installing,
+    /// System does not know if new updates are available.
+    ///
+    /// States that are not known to this crate are also deserialized as this variant.
+    #[serde(rename = "unknown", other)]
+    Unkown,
 }
 
 /// Configuration for automatically updating.
@@ -255,8 +451,15 @@ pub struct User {
 }
 
 impl User {
-    fn with_id(self, id: String) -> Self {
-        Self { id, ..self }
+    /// Returns the user with its identifier set to `id`.
+    pub fn with_id<S>(self, id: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            id: id.into(),
+            ..self
+        }
     }
 }
 
@@ -301,15 +504,19 @@ pub struct Modifier {
     pub touchlink: Option<bool>,
     /// Sets the wireless frequency channel used by the bridge.
     ///
-    /// It can take values of 11, 15, 20 or 25.
+    /// Lights reconnect on the new channel after a short time. Until then,
+    /// [`Config::zigbee_channel_change_pending`] is set.
     #[serde(skip_serializing_if = "Option::is_none", rename = "zigbeechannel")]
-    pub zigbee_channel: Option<u8>,
+    pub zigbee_channel: Option<ZigbeeChannel>,
     /// Sets the current time of the bridge in UTC.
     #[serde(skip_serializing_if = "Option::is_none", rename = "UTC")]
     pub current_time: Option<NaiveDateTime>,
     /// Sets the timezone of the bridge.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timezone: Option<String>,
+    /// Starts a check for software updates or the installation of updates.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "swupdate2")]
+    pub software_update: Option<SoftwareUpdateModifier>,
 }
 
 impl Modifier {
@@ -319,6 +526,25 @@ impl Modifier {
     }
 }
 
+/// Struct for starting software updates of the bridge and the devices.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Setters)]
+#[setters(strip_option, prefix = "with_")]
+pub struct SoftwareUpdateModifier {
+    /// Starts a search for updates of the bridge and the devices.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "checkforupdate")]
+    pub check_for_update: Option<bool>,
+    /// Installs the available updates.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub install: Option<bool>,
+}
+
+impl SoftwareUpdateModifier {
+    /// Creates a new [`SoftwareUpdateModifier`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
 impl resource::Modifier for Modifier {
     type Id = ();
     fn url_suffix(_id: Self::Id) -> String {
@@ -333,6 +559,129 @@ mod tests {
     use serde_json::json;
     use std::net::Ipv4Addr;
 
+    fn config_json() -> serde_json::Value {
+        json!({
+            "name": "Philips hue",
+            "swupdate2": {
+                "checkforupdate": false,
+                "lastchange": "2020-01-01T10:00:00",
+                "state": "noupdates",
+                "autoinstall": {"updatetime": "T14:00:00", "on": true},
+                "lastinstall": "2020-01-01T09:00:00"
+            },
+            "swversion": "1941132080",
+            "apiversion": "1.41.0",
+            "linkbutton": false,
+            "ipaddress": "192.168.1.2",
+            "mac": "00:17:88:00:00:00",
+            "netmask": "255.255.255.0",
+            "gateway": "192.168.1.1",
+            "dhcp": true,
+            "portalservices": true,
+            "portalconnection": "connected",
+            "portalstate": {
+                "signedon": true,
+                "incoming": false,
+                "outgoing": true,
+                "communication": "disconnected"
+            },
+            "internetservices": {
+                "internet": "connected",
+                "remoteaccess": "connected",
+                "time": "connected",
+                "swupdate": "connected"
+            },
+            "UTC": "2020-01-01T12:00:00",
+            "localtime": "2020-01-01T13:00:00",
+            "timezone": "Europe/Berlin",
+            "zigbeechannel": 15,
+            "modelid": "BSB002",
+            "bridgeid": "001788FFFE000000",
+            "factorynew": false,
+            "replacesbridgeid": null,
+            "datastoreversion": "93",
+            "starterkitid": "",
+            "backup": {"status": "idle", "errorcode": 0},
+            "whitelist": {
+                "user": {
+                    "last use date": "2020-01-01T12:00:00",
+                    "create date": "2019-01-01T12:00:00",
+                    "name": "huelib2#test"
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn deserialize_software_update_state() {
+        let state: SoftwareUpdateState = serde_json::from_value(json!("unknown")).unwrap();
+        assert_eq!(state, SoftwareUpdateState::Unkown);
+        let state: SoftwareUpdateState = serde_json::from_value(json!("checking")).unwrap();
+        assert_eq!(state, SoftwareUpdateState::Unkown);
+        let state: SoftwareUpdateState = serde_json::from_value(json!("noupdates")).unwrap();
+        assert_eq!(state, SoftwareUpdateState::NoUpdates);
+    }
+
+    #[test]
+    fn deserialize_full_state() {
+        let json = json!({
+            "lights": {},
+            "groups": {},
+            "config": config_json(),
+            "schedules": {},
+            "scenes": {},
+            "rules": {},
+            "sensors": {},
+            "resourcelinks": {}
+        });
+        let full_state: FullState = serde_json::from_value(json).unwrap();
+        assert!(full_state.lights.is_empty());
+        assert_eq!(full_state.config.name, "Philips hue");
+        assert_eq!(full_state.config.whitelist.len(), 1);
+        assert_eq!(full_state.config.whitelist[0].id, "user");
+    }
+
+    #[test]
+    fn deserialize_unauthenticated_config() {
+        let json = json!({
+            "name": "Philips hue",
+            "datastoreversion": "93",
+            "swversion": "1941132080",
+            "apiversion": "1.41.0",
+            "mac": "00:17:88:00:00:00",
+            "bridgeid": "001788FFFE000000",
+            "factorynew": false,
+            "replacesbridgeid": null,
+            "modelid": "BSB002",
+            "starterkitid": ""
+        });
+        let config: UnauthenticatedConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(config.bridge_id, "001788FFFE000000");
+        assert_eq!(config.api_version, "1.41.0");
+        assert_eq!(config.replaces_bridge_id, None);
+    }
+
+    #[test]
+    fn deserialize_zigbee_channel() {
+        let config: Config = serde_json::from_value(config_json()).unwrap();
+        assert_eq!(config.zigbee_channel, Some(ZigbeeChannel::Channel15));
+        assert!(!config.zigbee_channel_change_pending);
+
+        let mut json = config_json();
+        json["zigbeechannel"] = json!(0);
+        json["zigbeechannelchangepending"] = json!(true);
+        let config: Config = serde_json::from_value(json).unwrap();
+        assert_eq!(config.zigbee_channel, None);
+        assert!(config.zigbee_channel_change_pending);
+
+        let mut json = config_json();
+        json["zigbeechannel"] = json!(12);
+        let config: Config = serde_json::from_value(json).unwrap();
+        assert_eq!(config.zigbee_channel, Some(ZigbeeChannel::Other(12)));
+        assert_eq!(ZigbeeChannel::from_number(12).number(), 12);
+        assert_eq!(ZigbeeChannel::from_number(25), ZigbeeChannel::Channel25);
+    }
+
     #[test]
     fn serialize_modifier() {
         let modifier = Modifier::new();
@@ -350,12 +699,13 @@ mod tests {
             proxy_address: Some(IpAddr::V4(Ipv4Addr::new(192, 168, 2, 1))),
             linkbutton: Some(false),
             touchlink: Some(false),
-            zigbee_channel: Some(1),
+            zigbee_channel: Some(ZigbeeChannel::Channel20),
             current_time: Some(NaiveDateTime::new(
                 NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
                 NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
             )),
             timezone: Some("Europe/Berlin".into()),
+            software_update: Some(SoftwareUpdateModifier::new().with_check_for_update(true)),
         };
         let modifier_json = serde_json::to_value(modifier).unwrap();
         let expected_json = json!({
@@ -368,9 +718,10 @@ mod tests {
             "proxyaddress": "192.168.2.1",
             "linkbutton": false,
             "touchlink": false,
-            "zigbeechannel": 1,
+            "zigbeechannel": 20,
             "UTC": "2020-01-01T00:00:00",
-            "timezone": "Europe/Berlin"
+            "timezone": "Europe/Berlin",
+            "swupdate2": {"checkforupdate": true}
         });
         assert_eq!(modifier_json, expected_json);
     }
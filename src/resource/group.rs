@@ -1,10 +1,15 @@
-use crate::resource::{self, Adjust, Alert, Effect};
-use crate::Color;
+use crate::resource::{self, capabilities, Adjust, Alert, Effect};
+use crate::units::{Mired, OutOfRangeError};
+use crate::{util, Color};
 use derive_setters::Setters;
 use serde::{ser::SerializeStruct, Deserialize, Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::time::Duration;
+use thiserror::Error as ThisError;
 
 /// A group of lights.
-#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize)]
 pub struct Group {
     /// Identifier of the group.
     #[serde(skip)]
@@ -14,6 +19,9 @@ pub struct Group {
     /// Identifiers of lights that are in this group.
     pub lights: Vec<String>,
     /// Identifiers of sensors that are in this group.
+    ///
+    /// Older firmware versions do not list the sensors of a group, in which case this is empty.
+    #[serde(default)]
     pub sensors: Vec<String>,
     /// Kind of the group.
     #[serde(rename = "type")]
@@ -40,11 +48,40 @@ pub struct Group {
     pub unique_id: Option<String>,
     /// Whether the group is automatically deleted when not referenced anymore.
     pub recycle: Option<bool>,
+    /// Locations of the lights in the group.
+    ///
+    /// Only present for entertainment groups.
+    pub locations: Option<Locations>,
+    /// Streaming status of the group.
+    ///
+    /// Only present for entertainment groups.
+    pub stream: Option<Stream>,
 }
 
 impl Group {
-    pub(crate) fn with_id(self, id: String) -> Self {
-        Self { id, ..self }
+    /// Identifier of the special group that contains all lights of the bridge.
+    ///
+    /// See [`Bridge::set_all_lights_state`] for more information.
+    ///
+    /// [`Bridge::set_all_lights_state`]: crate::Bridge::set_all_lights_state
+    pub const ALL: &'static str = "0";
+
+    /// Returns the group with its identifier set to `id`.
+    pub fn with_id<S>(self, id: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            id: id.into(),
+            ..self
+        }
+    }
+
+    /// Returns the location of a light in the group.
+    ///
+    /// Returns `None` if the group is not an entertainment group or the light has no location.
+    pub fn location(&self, light_id: &str) -> Option<&Location> {
+        self.locations.as_ref()?.get(light_id)
     }
 }
 
@@ -107,10 +144,98 @@ pub enum ImmutableKind {
 }
 
 /// Class of a group.
-pub type Class = String;
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub enum Class {
+    /// Living room.
+    #[serde(rename = "Living room")]
+    LivingRoom,
+    /// Kitchen.
+    Kitchen,
+    /// Dining.
+    Dining,
+    /// Bedroom.
+    Bedroom,
+    /// Kids bedroom.
+    #[serde(rename = "Kids bedroom")]
+    KidsBedroom,
+    /// Bathroom.
+    Bathroom,
+    /// Nursery.
+    Nursery,
+    /// Recreation.
+    Recreation,
+    /// Office.
+    Office,
+    /// Gym.
+    Gym,
+    /// Hallway.
+    Hallway,
+    /// Toilet.
+    Toilet,
+    /// Front door.
+    #[serde(rename = "Front door")]
+    FrontDoor,
+    /// Garage.
+    Garage,
+    /// Terrace.
+    Terrace,
+    /// Garden.
+    Garden,
+    /// Driveway.
+    Driveway,
+    /// Carport.
+    Carport,
+    /// Home.
+    Home,
+    /// Downstairs.
+    Downstairs,
+    /// Upstairs.
+    Upstairs,
+    /// Top floor.
+    #[serde(rename = "Top floor")]
+    TopFloor,
+    /// Attic.
+    Attic,
+    /// Guest room.
+    #[serde(rename = "Guest room")]
+    GuestRoom,
+    /// Staircase.
+    Staircase,
+    /// Lounge.
+    Lounge,
+    /// Man cave.
+    #[serde(rename = "Man cave")]
+    ManCave,
+    /// Computer.
+    Computer,
+    /// Studio.
+    Studio,
+    /// Music.
+    Music,
+    /// TV.
+    #[serde(rename = "TV")]
+    Tv,
+    /// Reading.
+    Reading,
+    /// Balcony.
+    Balcony,
+    /// Porch.
+    Porch,
+    /// Barbecue.
+    Barbecue,
+    /// Pool.
+    Pool,
+    /// Free.
+    Free,
+    /// Other class.
+    ///
+    /// Classes that are not known by this library are also deserialized into this variant.
+    #[serde(other)]
+    Other,
+}
 
 /// State of a group.
-#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Deserialize)]
 pub struct State {
     /// Whether any light in a group is on.
     pub any_on: bool,
@@ -118,8 +243,81 @@ pub struct State {
     pub all_on: bool,
 }
 
+/// Streaming status of an entertainment group.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct Stream {
+    /// Whether a streaming session is active.
+    pub active: bool,
+    /// Username of the user that started the active streaming session.
+    pub owner: Option<String>,
+    /// How the proxy node of the stream is selected.
+    #[serde(rename = "proxymode")]
+    pub proxy_mode: Option<ProxyMode>,
+    /// Path of the light that forwards the stream to the other lights, e.g. `/lights/1`.
+    #[serde(rename = "proxynode")]
+    pub proxy_node: Option<String>,
+}
+
+/// Selection of the proxy node of an entertainment group.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProxyMode {
+    /// The bridge selects the proxy node.
+    Auto,
+    /// The proxy node is selected by the user.
+    Manual,
+    /// Unknown proxy mode.
+    #[serde(other)]
+    Unknown,
+}
+
+/// Struct for starting or stopping a streaming session of an entertainment group.
+///
+/// See [`Bridge::set_group_stream`] for more information.
+///
+/// [`Bridge::set_group_stream`]: crate::Bridge::set_group_stream
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct StreamModifier {
+    /// Whether the streaming session is active.
+    pub active: bool,
+}
+
+impl StreamModifier {
+    /// Creates a new [`StreamModifier`].
+    pub fn new(active: bool) -> Self {
+        Self { active }
+    }
+}
+
+impl resource::Modifier for StreamModifier {
+    type Id = String;
+    fn url_suffix(id: Self::Id) -> String {
+        format!("groups/{}", id)
+    }
+}
+
+impl Serialize for StreamModifier {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct Active {
+            active: bool,
+        }
+        let mut state = serializer.serialize_struct("StreamModifier", 1)?;
+        state.serialize_field(
+            "stream",
+            &Active {
+                active: self.active,
+            },
+        )?;
+        state.end()
+    }
+}
+
 /// Struct for creating a group.
-#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Setters)]
+#[derive(Clone, Debug, PartialEq, Serialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
 pub struct Creator {
     /// Sets the name of the group.
@@ -140,6 +338,12 @@ pub struct Creator {
     /// Sets whether the group is automatically deleted when not referenced anymore.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub recycle: Option<bool>,
+    /// Sets the locations of the lights in an entertainment group.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_location_map"
+    )]
+    pub locations: Option<Locations>,
 }
 
 impl Creator {
@@ -152,6 +356,19 @@ impl Creator {
             kind: None,
             class: None,
             recycle: None,
+            locations: None,
+        }
+    }
+
+    /// Creates a new [`Creator`] for an entertainment group.
+    ///
+    /// The lights of the group are the lights that have a location.
+    pub fn entertainment(name: String, locations: Locations) -> Self {
+        Self {
+            lights: locations.0.keys().cloned().collect(),
+            kind: Some(CreatableKind::Entertainment),
+            locations: Some(locations),
+            ..Self::new(name, Vec::new())
         }
     }
 }
@@ -163,7 +380,7 @@ impl resource::Creator for Creator {
 }
 
 /// Struct for modifying group attributes.
-#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Setters)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
 pub struct AttributeModifier {
     /// Sets the name of the group.
@@ -178,6 +395,17 @@ pub struct AttributeModifier {
     /// Sets the class of the group.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub class: Option<Class>,
+    /// Sets the locations of the lights in an entertainment group.
+    ///
+    /// Unlike [`Bridge::set_entertainment_locations`], this does not change the lights of the
+    /// group and does not validate the locations.
+    ///
+    /// [`Bridge::set_entertainment_locations`]: crate::Bridge::set_entertainment_locations
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_location_map"
+    )]
+    pub locations: Option<Locations>,
 }
 
 impl AttributeModifier {
@@ -201,14 +429,26 @@ pub struct StateModifier {
     /// Turns the lights on or off.
     pub on: Option<bool>,
     /// Sets the brightness of the lights.
+    ///
+    /// The setter also accepts [`Brightness`](crate::units::Brightness).
+    #[setters(into)]
     pub brightness: Option<Adjust<u8>>,
     /// Sets the hue of the lights.
+    ///
+    /// The setter also accepts [`HueDegrees`](crate::units::HueDegrees).
+    #[setters(into)]
     pub hue: Option<Adjust<u16>>,
     /// Sets the saturation of the lights.
+    ///
+    /// The setter also accepts [`Saturation`](crate::units::Saturation).
+    #[setters(into)]
     pub saturation: Option<Adjust<u8>>,
     /// Sets the color space coordinates of the lights.
     pub color_space_coordinates: Option<Adjust<(f32, f32)>>,
     /// Sets the color temperature of the lights.
+    ///
+    /// The setter also accepts [`Mired`](crate::units::Mired) or [`Kelvin`](crate::units::Kelvin).
+    #[setters(into)]
     pub color_temperature: Option<Adjust<u16>>,
     /// Sets the alert effect of the lights.
     pub alert: Option<Alert>,
@@ -242,6 +482,33 @@ impl StateModifier {
         }
         modifier
     }
+
+    /// Convenient method to set the [`transition_time`] field from a duration.
+    ///
+    /// The duration is rounded to the nearest multiple of 100ms and clamped to the maximum
+    /// transition time of about 109 minutes.
+    ///
+    /// [`transition_time`]: Self::transition_time
+    pub fn with_transition_duration(self, duration: Duration) -> Self {
+        Self {
+            transition_time: Some(util::transition_time(duration)),
+            ..self
+        }
+    }
+
+    /// Convenient method to set the [`color_temperature`] field from a color temperature in
+    /// kelvin.
+    ///
+    /// Returns an error if the converted value is outside of the range from 153 to 500 mired.
+    ///
+    /// [`color_temperature`]: Self::color_temperature
+    pub fn with_color_temperature_kelvin(self, kelvin: u32) -> Result<Self, OutOfRangeError> {
+        let mired = Mired::from_kelvin(kelvin)?;
+        Ok(Self {
+            color_temperature: Some(mired.into()),
+            ..self
+        })
+    }
 }
 
 impl resource::Modifier for StateModifier {
@@ -277,6 +544,229 @@ impl Serialize for StateModifier {
     }
 }
 
+/// Position of a light in an entertainment group.
+///
+/// The coordinates range from -1 to 1. The x axis points from left to right, the y axis from back
+/// to front and the z axis from bottom to top.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(try_from = "Vec<f32>", into = "[f32; 3]")]
+pub struct Location {
+    /// Position from left (-1) to right (1).
+    pub x: f32,
+    /// Position from back (-1) to front (1).
+    pub y: f32,
+    /// Position from bottom (-1) to top (1).
+    pub z: f32,
+}
+
+impl Location {
+    /// Creates a new [`Location`].
+    pub fn new(x: f32, y: f32, z: f32) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Returns whether all coordinates are between -1 and 1.
+    pub fn is_valid(&self) -> bool {
+        [self.x, self.y, self.z]
+            .iter()
+            .all(|v| (-1.0..=1.0).contains(v))
+    }
+}
+
+impl TryFrom<Vec<f32>> for Location {
+    type Error = String;
+
+    fn try_from(value: Vec<f32>) -> Result<Self, Self::Error> {
+        match value[..] {
+            // Older bridges only return the x and y coordinates.
+            [x, y] => Ok(Self::new(x, y, 0.0)),
+            [x, y, z] => Ok(Self::new(x, y, z)),
+            _ => Err(format!("invalid number of coordinates: {}", value.len())),
+        }
+    }
+}
+
+impl From<Location> for [f32; 3] {
+    fn from(value: Location) -> Self {
+        [value.x, value.y, value.z]
+    }
+}
+
+/// Locations of the lights in an entertainment group.
+///
+/// Every light in an entertainment group is streamed on its own channel. Setting the locations
+/// also sets the lights of the group to the lights that have a location.
+///
+/// See [`Bridge::set_entertainment_locations`] for more information.
+///
+/// [`Bridge::set_entertainment_locations`]: crate::Bridge::set_entertainment_locations
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+pub struct Locations(BTreeMap<String, Location>);
+
+impl Locations {
+    /// Creates new, empty [`Locations`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the location of a light.
+    pub fn get(&self, light_id: &str) -> Option<&Location> {
+        self.0.get(light_id)
+    }
+
+    /// Sets the location of a light and returns the previous location.
+    pub fn set<S>(&mut self, light_id: S, location: Location) -> Option<Location>
+    where
+        S: Into<String>,
+    {
+        self.0.insert(light_id.into(), location)
+    }
+
+    /// Removes a light and returns its location.
+    pub fn remove(&mut self, light_id: &str) -> Option<Location> {
+        self.0.remove(light_id)
+    }
+
+    /// Returns an iterator over the light identifiers and their locations, ordered by identifier.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Location)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    /// Returns the number of lights.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether there are no lights.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Verifies that all locations are valid and that the lights fit into a stream.
+    pub fn validate(&self, streaming: &capabilities::StreamingInfo) -> Result<(), LocationError> {
+        if let Some((id, location)) = self.0.iter().find(|(_, v)| !v.is_valid()) {
+            return Err(LocationError::OutOfRange {
+                light_id: id.clone(),
+                location: *location,
+            });
+        }
+        if self.0.len() > streaming.channels {
+            return Err(LocationError::TooManyChannels {
+                count: self.0.len(),
+                max: streaming.channels,
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns the changes that are needed to get from these locations to `new`.
+    pub fn diff(&self, new: &Locations) -> Vec<LocationChange> {
+        let mut changes = Vec::new();
+        for (id, old) in &self.0 {
+            match new.0.get(id) {
+                None => changes.push(LocationChange::Removed {
+                    light_id: id.clone(),
+                    location: *old,
+                }),
+                Some(v) if v != old => changes.push(LocationChange::Moved {
+                    light_id: id.clone(),
+                    from: *old,
+                    to: *v,
+                }),
+                Some(_) => {}
+            }
+        }
+        for (id, location) in &new.0 {
+            if !self.0.contains_key(id) {
+                changes.push(LocationChange::Added {
+                    light_id: id.clone(),
+                    location: *location,
+                });
+            }
+        }
+        changes
+    }
+}
+
+impl resource::Modifier for Locations {
+    type Id = String;
+    fn url_suffix(id: Self::Id) -> String {
+        format!("groups/{}", id)
+    }
+}
+
+impl Serialize for Locations {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Locations", 2)?;
+        state.serialize_field("lights", &self.0.keys().collect::<Vec<_>>())?;
+        state.serialize_field("locations", &self.0)?;
+        state.end()
+    }
+}
+
+/// Serializes only the map of locations, without the identifiers of the lights.
+fn serialize_location_map<S>(value: &Option<Locations>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match value {
+        Some(v) => v.0.serialize(serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// A change of the location of a light in an entertainment group.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LocationChange {
+    /// The light is added to the group.
+    Added {
+        /// Identifier of the light.
+        light_id: String,
+        /// Location of the light.
+        location: Location,
+    },
+    /// The light is moved to another location.
+    Moved {
+        /// Identifier of the light.
+        light_id: String,
+        /// Current location of the light.
+        from: Location,
+        /// New location of the light.
+        to: Location,
+    },
+    /// The light is removed from the group.
+    Removed {
+        /// Identifier of the light.
+        light_id: String,
+        /// Current location of the light.
+        location: Location,
+    },
+}
+
+/// Errors that can occur while validating [`Locations`].
+#[derive(Clone, Debug, PartialEq, ThisError)]
+pub enum LocationError {
+    /// Error that occurs when a coordinate is not between -1 and 1.
+    #[error("Location of light {light_id} is out of range")]
+    OutOfRange {
+        /// Identifier of the light.
+        light_id: String,
+        /// Invalid location of the light.
+        location: Location,
+    },
+    /// Error that occurs when there are more lights than channels in a stream.
+    #[error("{count} lights exceed the maximum of {max} channels")]
+    TooManyChannels {
+        /// Number of lights.
+        count: usize,
+        /// Number of channels per stream.
+        max: usize,
+    },
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -297,8 +787,9 @@ mod tests {
             lights: vec!["1".into(), "2".into()],
             sensors: Some(vec!["3".into()]),
             kind: Some(CreatableKind::Room),
-            class: Some("Office".to_string()),
+            class: Some(Class::Office),
             recycle: Some(true),
+            locations: None,
         };
         let creator_json = serde_json::to_value(creator).unwrap();
         let expected_json = json!({
@@ -312,6 +803,21 @@ mod tests {
         assert_eq!(creator_json, expected_json);
     }
 
+    #[test]
+    fn class() {
+        assert_eq!(
+            serde_json::to_value(Class::LivingRoom).unwrap(),
+            json!("Living room")
+        );
+        assert_eq!(serde_json::to_value(Class::Tv).unwrap(), json!("TV"));
+        let class: Class = serde_json::from_value(json!("Kids bedroom")).unwrap();
+        assert_eq!(class, Class::KidsBedroom);
+        let class: Class = serde_json::from_value(json!("Other")).unwrap();
+        assert_eq!(class, Class::Other);
+        let class: Class = serde_json::from_value(json!("Laundry room")).unwrap();
+        assert_eq!(class, Class::Other);
+    }
+
     #[test]
     fn serialize_attribute_modifier() {
         let modifier = AttributeModifier::new();
@@ -323,7 +829,8 @@ mod tests {
             name: Some("test".into()),
             lights: Some(vec!["1".into(), "2".into()]),
             sensors: Some(vec!["3".into()]),
-            class: Some("Office".to_string()),
+            class: Some(Class::Office),
+            locations: None,
         };
         let modifier_json = serde_json::to_value(modifier).unwrap();
         let expected_json = json!({
@@ -377,5 +884,252 @@ mod tests {
             "xy": [0.0, 0.0]
         });
         assert_eq!(modifier_json, expected_json);
+
+        let modifier = StateModifier::new()
+            .with_brightness(Adjust::Decrement(10))
+            .with_hue(Adjust::Increment(100))
+            .with_saturation(Adjust::Increment(5))
+            .with_color_space_coordinates(Adjust::Decrement((0.5, 0.25)))
+            .with_color_temperature(Adjust::Increment(20));
+        let modifier_json = serde_json::to_value(modifier).unwrap();
+        let expected_json = json!({
+            "bri_inc": -10,
+            "hue_inc": 100,
+            "sat_inc": 5,
+            "xy_inc": [-0.5, -0.25],
+            "ct_inc": 20
+        });
+        assert_eq!(modifier_json, expected_json);
+    }
+
+    #[test]
+    fn serialize_state_modifier_adjust() {
+        let values = vec![
+            (
+                StateModifier::new().with_brightness(Adjust::Override(10)),
+                json!({"bri": 10}),
+            ),
+            (
+                StateModifier::new().with_hue(Adjust::Decrement(65535)),
+                json!({"hue_inc": -65535}),
+            ),
+            (
+                StateModifier::new().with_saturation(Adjust::Decrement(254)),
+                json!({"sat_inc": -254}),
+            ),
+            (
+                StateModifier::new().with_color_space_coordinates(Adjust::Override((0.25, 0.75))),
+                json!({"xy": [0.25, 0.75]}),
+            ),
+            (
+                StateModifier::new().with_color_temperature(Adjust::Decrement(347)),
+                json!({"ct_inc": -347}),
+            ),
+            (
+                StateModifier::new().with_scene("AB34EF5".into()),
+                json!({"scene": "AB34EF5"}),
+            ),
+        ];
+        for (modifier, expected_json) in values {
+            assert_eq!(serde_json::to_value(modifier).unwrap(), expected_json);
+        }
+    }
+
+    #[test]
+    fn serialize_entertainment_creator() {
+        let mut locations = Locations::new();
+        locations.set("2", Location::new(0.5, -0.5, 0.0));
+        locations.set("1", Location::new(-1.0, 1.0, 0.25));
+        let creator = Creator::entertainment("test".into(), locations.clone())
+            .with_class(Class::Tv)
+            .with_recycle(false);
+        let creator_json = serde_json::to_value(creator).unwrap();
+        let expected_json = json!({
+            "name": "test",
+            "lights": ["1", "2"],
+            "type": "Entertainment",
+            "class": "TV",
+            "recycle": false,
+            "locations": {
+                "1": [-1.0, 1.0, 0.25],
+                "2": [0.5, -0.5, 0.0]
+            }
+        });
+        assert_eq!(creator_json, expected_json);
+
+        let value: Locations = serde_json::from_value(creator_json["locations"].clone()).unwrap();
+        assert_eq!(value, locations);
+    }
+
+    #[test]
+    fn deserialize_locations() {
+        let json = json!({"1": [0.5, -0.5], "2": [-1.0, 1.0, 0.25]});
+        let locations: Locations = serde_json::from_value(json).unwrap();
+        assert_eq!(locations.get("1"), Some(&Location::new(0.5, -0.5, 0.0)));
+        assert_eq!(locations.get("2"), Some(&Location::new(-1.0, 1.0, 0.25)));
+
+        let json = json!({"1": [0.5]});
+        assert!(serde_json::from_value::<Locations>(json).is_err());
+    }
+
+    #[test]
+    fn serialize_locations() {
+        let mut locations = Locations::new();
+        locations.set("2", Location::new(0.5, -0.5, 0.0));
+        locations.set("1", Location::new(-1.0, 1.0, 0.25));
+        let locations_json = serde_json::to_value(locations).unwrap();
+        let expected_json = json!({
+            "lights": ["1", "2"],
+            "locations": {
+                "1": [-1.0, 1.0, 0.25],
+                "2": [0.5, -0.5, 0.0]
+            }
+        });
+        assert_eq!(locations_json, expected_json);
+    }
+
+    #[test]
+    fn entertainment_group() {
+        let mut locations = Locations::new();
+        locations.set("2", Location::new(0.5, -0.5, 0.0));
+        locations.set("1", Location::new(-1.0, 1.0, 0.25));
+        let creator = Creator::entertainment("tv".into(), locations.clone());
+        let creator_json = serde_json::to_value(creator).unwrap();
+        let expected_json = json!({
+            "name": "tv",
+            "lights": ["1", "2"],
+            "type": "Entertainment",
+            "locations": {
+                "1": [-1.0, 1.0, 0.25],
+                "2": [0.5, -0.5, 0.0]
+            }
+        });
+        assert_eq!(creator_json, expected_json);
+
+        let modifier = AttributeModifier::new().with_locations(locations);
+        let modifier_json = serde_json::to_value(modifier).unwrap();
+        let expected_json = json!({
+            "locations": {
+                "1": [-1.0, 1.0, 0.25],
+                "2": [0.5, -0.5, 0.0]
+            }
+        });
+        assert_eq!(modifier_json, expected_json);
+
+        let group: Group = serde_json::from_value(json!({
+            "name": "tv",
+            "lights": ["1"],
+            "sensors": [],
+            "type": "Entertainment",
+            "class": "TV",
+            "locations": {"1": [0.5, -0.5, 0.0]}
+        }))
+        .unwrap();
+        assert_eq!(group.location("1"), Some(&Location::new(0.5, -0.5, 0.0)));
+        assert_eq!(group.location("2"), None);
+    }
+
+    #[test]
+    fn deserialize_sensors() {
+        let json = json!({
+            "name": "Kitchen",
+            "lights": ["1"],
+            "sensors": ["4", "5"],
+            "type": "Room",
+            "class": "Kitchen"
+        });
+        let group: Group = serde_json::from_value(json).unwrap();
+        assert_eq!(group.sensors, vec!["4", "5"]);
+
+        let json = json!({
+            "name": "Kitchen",
+            "lights": ["1"],
+            "type": "Room",
+            "class": "Kitchen"
+        });
+        let group: Group = serde_json::from_value(json).unwrap();
+        assert!(group.sensors.is_empty());
+    }
+
+    #[test]
+    fn stream() {
+        let json = json!({
+            "proxymode": "auto",
+            "proxynode": "/lights/1",
+            "active": true,
+            "owner": "user"
+        });
+        let stream: Stream = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            stream,
+            Stream {
+                active: true,
+                owner: Some("user".into()),
+                proxy_mode: Some(ProxyMode::Auto),
+                proxy_node: Some("/lights/1".into()),
+            }
+        );
+
+        let json = json!({"active": false, "owner": null});
+        let stream: Stream = serde_json::from_value(json).unwrap();
+        assert!(!stream.active);
+        assert_eq!(stream.owner, None);
+
+        let modifier_json = serde_json::to_value(StreamModifier::new(true)).unwrap();
+        assert_eq!(modifier_json, json!({"stream": {"active": true}}));
+    }
+
+    #[test]
+    fn validate_locations() {
+        let streaming = capabilities::StreamingInfo {
+            available: 1,
+            total: 1,
+            channels: 2,
+        };
+        let mut locations = Locations::new();
+        locations.set("1", Location::new(0.0, 0.0, 0.0));
+        locations.set("2", Location::new(1.0, -1.0, 0.0));
+        assert_eq!(locations.validate(&streaming), Ok(()));
+
+        locations.set("3", Location::new(0.0, 0.0, 0.0));
+        assert_eq!(
+            locations.validate(&streaming),
+            Err(LocationError::TooManyChannels { count: 3, max: 2 })
+        );
+
+        locations.set("3", Location::new(1.5, 0.0, 0.0));
+        assert!(matches!(
+            locations.validate(&streaming),
+            Err(LocationError::OutOfRange { light_id, .. }) if light_id == "3"
+        ));
+    }
+
+    #[test]
+    fn diff_locations() {
+        let mut old = Locations::new();
+        old.set("1", Location::new(0.0, 0.0, 0.0));
+        old.set("2", Location::new(0.5, 0.5, 0.0));
+        let mut new = old.clone();
+        new.remove("1");
+        new.set("2", Location::new(-0.5, 0.5, 0.0));
+        new.set("3", Location::new(1.0, 1.0, 1.0));
+        assert_eq!(
+            old.diff(&new),
+            vec![
+                LocationChange::Removed {
+                    light_id: "1".into(),
+                    location: Location::new(0.0, 0.0, 0.0),
+                },
+                LocationChange::Moved {
+                    light_id: "2".into(),
+                    from: Location::new(0.5, 0.5, 0.0),
+                    to: Location::new(-0.5, 0.5, 0.0),
+                },
+                LocationChange::Added {
+                    light_id: "3".into(),
+                    location: Location::new(1.0, 1.0, 1.0),
+                },
+            ]
+        );
     }
 }
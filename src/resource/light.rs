@@ -1,9 +1,13 @@
 #![allow(clippy::needless_update)]
 
-use crate::resource::{self, Adjust, Alert, ColorMode, Effect};
-use crate::Color;
+use crate::resource::{
+    self, group, Adjust, Alert, ColorMode, DispatchPolicy, Effect, RequestMethod,
+};
+use crate::units::{Brightness, HueDegrees, Kelvin, Mired, OutOfRangeError, Saturation};
+use crate::{response::Modified, util, Bridge, Color};
 use derive_setters::Setters;
 use serde::{ser::SerializeStruct, Deserialize, Serialize, Serializer};
+use std::{collections::HashSet, time::Duration};
 
 /// A light.
 #[derive(Clone, Debug, PartialEq, Deserialize)]
@@ -49,15 +53,41 @@ pub struct Light {
 }
 
 impl Light {
-    pub(crate) fn with_id(self, id: String) -> Self {
-        Self { id, ..self }
+    /// Returns the light with its identifier set to `id`.
+    pub fn with_id<S>(self, id: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            id: id.into(),
+            ..self
+        }
+    }
+
+    /// Returns the dynamic effects that are supported by the light.
+    ///
+    /// Only lights that support colors support the color loop effect. The support is derived from
+    /// the color gamut in the capabilities and the current state of the light.
+    pub fn supported_effects(&self) -> SupportedEffects {
+        #[cfg(not(feature = "old-api"))]
+        let has_gamut = self.capabilities.control.color_gamut.is_some()
+            || self.capabilities.control.color_gamut_type.is_some();
+        #[cfg(feature = "old-api")]
+        let has_gamut = false;
+        let supports_color =
+            has_gamut || self.state.hue.is_some() || self.state.color_space_coordinates.is_some();
+        let mut effects = SupportedEffects::default();
+        if supports_color {
+            effects.0.insert(Effect::Colorloop);
+        }
+        effects
     }
 }
 
 impl resource::Resource for Light {}
 
 /// State of a light.
-#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
 pub struct State {
     /// Whether the light is on.
     pub on: Option<bool>,
@@ -92,6 +122,56 @@ pub struct State {
     pub reachable: bool,
 }
 
+impl State {
+    /// Returns the brightness of the light as [`Brightness`].
+    pub fn brightness_typed(&self) -> Option<Brightness> {
+        self.brightness.map(Brightness::clamped)
+    }
+
+    /// Returns the hue of the light as [`HueDegrees`].
+    pub fn hue_typed(&self) -> Option<HueDegrees> {
+        self.hue.map(HueDegrees::from_raw)
+    }
+
+    /// Returns the saturation of the light as [`Saturation`].
+    pub fn saturation_typed(&self) -> Option<Saturation> {
+        self.saturation.map(Saturation::clamped)
+    }
+
+    /// Returns the color temperature of the light as [`Mired`].
+    pub fn color_temperature_typed(&self) -> Option<Mired> {
+        self.color_temperature.map(Mired::clamped)
+    }
+
+    /// Returns the color temperature of the light as [`Kelvin`].
+    pub fn color_temperature_kelvin(&self) -> Option<Kelvin> {
+        self.color_temperature_typed().map(Kelvin::from)
+    }
+}
+
+/// Set of dynamic effects that are supported by a light.
+///
+/// [`Effect::None`] is supported by every light and therefore not contained in the set.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SupportedEffects(HashSet<Effect>);
+
+impl SupportedEffects {
+    /// Returns whether the effect is supported.
+    pub fn contains(&self, effect: Effect) -> bool {
+        effect == Effect::None || self.0.contains(&effect)
+    }
+
+    /// Returns whether no effect other than [`Effect::None`] is supported.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the supported effects.
+    pub fn iter(&self) -> impl Iterator<Item = Effect> + '_ {
+        self.0.iter().cloned()
+    }
+}
+
 /// Information about software updates of a light.
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
 pub struct SoftwareUpdate {
@@ -104,8 +184,9 @@ pub struct SoftwareUpdate {
 
 /// State of a software update.
 ///
-/// See [this issue] for the reason why this enum is marked as `non_exhaustive`.
+/// States that are not known to this crate are deserialized as [`Unknown`], see [this issue].
 ///
+/// [`Unknown`]: Self::Unknown
 /// [this issue]: https://github.com/yuqio/huelib-rs/issues/1
 #[non_exhaustive]
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Deserialize)]
@@ -123,7 +204,11 @@ pub enum SoftwareUpdateState {
     Transferring,
     /// Device is ready to install new updates.
     ReadyToInstall,
-    // FIXME: Add missing variants for states (https://github.com/yuqio/huelib-rs/issues/1)
+    /// Battery of the device is too low to install updates.
+    BatteryLow,
+    /// State that is not known to this crate.
+    #[serde(other)]
+    Unknown,
 }
 
 /// Configuration of a light.
@@ -221,16 +306,172 @@ impl resource::Modifier for AttributeModifier {
     }
 }
 
+/// Modifier for the light configuration.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Setters)]
+#[setters(strip_option, prefix = "with_")]
+pub struct ConfigModifier {
+    /// Sets the behavior of the light when the power is restored.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub startup: Option<Startup>,
+}
+
+impl ConfigModifier {
+    /// Creates a new [`ConfigModifier`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl resource::Modifier for ConfigModifier {
+    type Id = String;
+    fn url_suffix(id: Self::Id) -> String {
+        format!("lights/{}/config", id)
+    }
+}
+
+/// Behavior of a light when the power is restored.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "mode", rename_all = "lowercase")]
+pub enum Startup {
+    /// Turns the light on with the default bright white color.
+    Safety,
+    /// Restores the state before the power was lost, but only if the power was lost for a short
+    /// time.
+    Powerfail,
+    /// Restores the last state in which the light was on.
+    LastOnState,
+    /// Turns the light on with custom settings.
+    Custom {
+        /// Settings of the light when the power is restored.
+        #[serde(rename = "customsettings")]
+        settings: StartupSettings,
+    },
+}
+
+/// Custom settings of a light when the power is restored.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Setters)]
+#[setters(strip_option, prefix = "with_")]
+pub struct StartupSettings {
+    /// Brightness of the light.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "bri")]
+    pub brightness: Option<u8>,
+    /// Color space coordinates of the light.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "xy")]
+    pub color_space_coordinates: Option<(f32, f32)>,
+    /// Color temperature of the light.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "ct")]
+    pub color_temperature: Option<u16>,
+}
+
+impl StartupSettings {
+    /// Creates new [`StartupSettings`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Convenient method to set the [`color_space_coordinates`] and [`brightness`] fields.
+    ///
+    /// [`color_space_coordinates`]: Self::color_space_coordinates
+    /// [`brightness`]: Self::brightness
+    pub fn with_color(self, value: Color) -> Self {
+        Self {
+            color_space_coordinates: Some(value.space_coordinates),
+            brightness: value.brightness.or(self.brightness),
+            ..self
+        }
+    }
+}
+
+/// A rename of a light that is planned or was applied by [`Bridge::rename_lights_matching`].
+///
+/// [`Bridge::rename_lights_matching`]: crate::Bridge::rename_lights_matching
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Rename {
+    /// Identifier of the light.
+    pub id: String,
+    /// Name of the light before the rename.
+    pub old_name: String,
+    /// Name of the light after the rename.
+    pub new_name: String,
+}
+
+/// How the name of a light is matched by [`Bridge::get_lights_by_name`].
+///
+/// [`Bridge::get_lights_by_name`]: crate::Bridge::get_lights_by_name
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum NameMatch {
+    /// The name is equal to the pattern.
+    Exact,
+    /// The name is equal to the pattern, ignoring case.
+    IgnoreCase,
+    /// The name contains the pattern, ignoring case.
+    Contains,
+}
+
+impl NameMatch {
+    /// Returns whether the name matches the pattern.
+    pub fn matches(self, name: &str, pattern: &str) -> bool {
+        match self {
+            Self::Exact => name == pattern,
+            Self::IgnoreCase => name.to_lowercase() == pattern.to_lowercase(),
+            Self::Contains => name.to_lowercase().contains(&pattern.to_lowercase()),
+        }
+    }
+}
+
+/// Returns the target value if it is set and differs from the current value.
+fn changed<T>(current: &Option<T>, target: &Option<T>) -> Option<T>
+where
+    T: Clone + PartialEq,
+{
+    match target {
+        Some(v) if current.as_ref() != Some(v) => Some(v.clone()),
+        _ => None,
+    }
+}
+
+/// Plans renames for all lights whose name contains `pattern`.
+///
+/// The lights are numbered by their identifier starting at 1 and every `{n}` in `template` is
+/// replaced by that number.
+pub(crate) fn plan_renames<'a, I>(lights: I, pattern: &str, template: &str) -> Vec<Rename>
+where
+    I: IntoIterator<Item = (&'a str, &'a str)>,
+{
+    let mut matching: Vec<(&str, &str)> = lights
+        .into_iter()
+        .filter(|(_, name)| name.contains(pattern))
+        .collect();
+    matching.sort_by(
+        |(a, _), (b, _)| match (a.parse::<u64>(), b.parse::<u64>()) {
+            (Ok(a), Ok(b)) => a.cmp(&b),
+            _ => a.cmp(b),
+        },
+    );
+    matching
+        .into_iter()
+        .enumerate()
+        .map(|(i, (id, name))| Rename {
+            id: id.to_owned(),
+            old_name: name.to_owned(),
+            new_name: template.replace("{n}", &(i + 1).to_string()),
+        })
+        .collect()
+}
+
 /// Static modifier for the light state.
 ///
 /// In comparison to [`StateModifier`], this modifier cannot increment/decrement any attributes or
 /// change the alert effect.
 ///
-/// This modifier is used in [`scene::Modifier`] and [`scene::Creator`].
+/// This modifier is used in [`scene::Modifier`], [`scene::Creator`] and
+/// [`scene::LightStateModifier`], and for the light states of a [`Scene`].
 ///
 /// [`scene::Modifier`]: super::scene::Modifier
 /// [`scene::Creator`]: super::scene::Creator
-#[derive(Clone, Debug, Default, PartialEq, Serialize, Setters)]
+/// [`scene::LightStateModifier`]: super::scene::LightStateModifier
+/// [`Scene`]: super::Scene
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
 pub struct StaticStateModifier {
     /// Turns the light on or off.
@@ -281,6 +522,33 @@ impl StaticStateModifier {
         }
         modifier
     }
+
+    /// Convenient method to set the [`transition_time`] field from a duration.
+    ///
+    /// The duration is rounded to the nearest multiple of 100ms and clamped to the maximum
+    /// transition time of about 109 minutes.
+    ///
+    /// [`transition_time`]: Self::transition_time
+    pub fn with_transition_duration(self, duration: Duration) -> Self {
+        Self {
+            transition_time: Some(util::transition_time(duration)),
+            ..self
+        }
+    }
+
+    /// Convenient method to set the [`color_temperature`] field from a color temperature in
+    /// kelvin.
+    ///
+    /// Returns an error if the converted value is outside of the range from 153 to 500 mired.
+    ///
+    /// [`color_temperature`]: Self::color_temperature
+    pub fn with_color_temperature_kelvin(self, kelvin: u32) -> Result<Self, OutOfRangeError> {
+        let mired = Mired::from_kelvin(kelvin)?;
+        Ok(Self {
+            color_temperature: Some(mired.value()),
+            ..self
+        })
+    }
 }
 
 impl resource::Modifier for StaticStateModifier {
@@ -297,14 +565,26 @@ pub struct StateModifier {
     /// Turns the light on or off.
     pub on: Option<bool>,
     /// Sets the brightness of the light.
+    ///
+    /// The setter also accepts [`Brightness`](crate::units::Brightness).
+    #[setters(into)]
     pub brightness: Option<Adjust<u8>>,
     /// Sets the hue of a light.
+    ///
+    /// The setter also accepts [`HueDegrees`](crate::units::HueDegrees).
+    #[setters(into)]
     pub hue: Option<Adjust<u16>>,
     /// Sets the saturation of a light.
+    ///
+    /// The setter also accepts [`Saturation`](crate::units::Saturation).
+    #[setters(into)]
     pub saturation: Option<Adjust<u8>>,
     /// Sets the color space coordinates of the light.
     pub color_space_coordinates: Option<Adjust<(f32, f32)>>,
     /// Sets the color temperature of a light.
+    ///
+    /// The setter also accepts [`Mired`](crate::units::Mired) or [`Kelvin`](crate::units::Kelvin).
+    #[setters(into)]
     pub color_temperature: Option<Adjust<u16>>,
     /// Sets the alert effect of a light.
     pub alert: Option<Alert>,
@@ -322,6 +602,71 @@ impl StateModifier {
         Self::default()
     }
 
+    /// Creates a modifier that changes the `current` state of a light to the `target` state.
+    ///
+    /// Only attributes that are set in the target state and differ from the current state are
+    /// contained in the modifier, so unchanged attributes are not sent to the bridge again. If the
+    /// color mode of the target state is set, only the color attributes of this color mode are
+    /// compared. If it differs from the color mode of the current state, the color attributes of
+    /// the target color mode are always contained, because the light only switches the color mode
+    /// when they are sent. The alert effect is not compared, as it is not a persistent state of
+    /// the light.
+    ///
+    /// # Examples
+    ///
+    /// Turn on a light without resending its color:
+    /// ```
+    /// use huelib2::resource::{light, Adjust};
+    ///
+    /// let current = light::State {
+    ///     on: Some(false),
+    ///     color_temperature: Some(366),
+    ///     ..Default::default()
+    /// };
+    /// let target = light::State {
+    ///     on: Some(true),
+    ///     ..current.clone()
+    /// };
+    /// let modifier = light::StateModifier::diff(&current, &target);
+    /// assert_eq!(modifier, light::StateModifier::new().with_on(true));
+    /// ```
+    pub fn diff(current: &State, target: &State) -> Self {
+        // `Option::is_none_or` is not available on older compilers.
+        #[allow(clippy::unnecessary_map_or)]
+        let uses_mode = |mode: ColorMode| target.color_mode.map_or(true, |v| v == mode);
+        // Color attributes are compared to an empty state if the color mode changes.
+        let empty = State::default();
+        let color_current =
+            match target.color_mode.is_some() && target.color_mode != current.color_mode {
+                true => &empty,
+                false => current,
+            };
+        let mut modifier = Self {
+            on: changed(&current.on, &target.on),
+            brightness: changed(&current.brightness, &target.brightness).map(Adjust::Override),
+            effect: changed(&current.effect, &target.effect),
+            ..Self::default()
+        };
+        if uses_mode(ColorMode::HueAndSaturation) {
+            modifier.hue = changed(&color_current.hue, &target.hue).map(Adjust::Override);
+            modifier.saturation =
+                changed(&color_current.saturation, &target.saturation).map(Adjust::Override);
+        }
+        if uses_mode(ColorMode::ColorSpaceCoordinates) {
+            modifier.color_space_coordinates = changed(
+                &color_current.color_space_coordinates,
+                &target.color_space_coordinates,
+            )
+            .map(Adjust::Override);
+        }
+        if uses_mode(ColorMode::ColorTemperature) {
+            modifier.color_temperature =
+                changed(&color_current.color_temperature, &target.color_temperature)
+                    .map(Adjust::Override);
+        }
+        modifier
+    }
+
     /// Convenient method to set the [`color_space_coordinates`] and [`brightness`] fields.
     ///
     /// [`color_space_coordinates`]: Self::color_space_coordinates
@@ -336,6 +681,33 @@ impl StateModifier {
         }
         modifier
     }
+
+    /// Convenient method to set the [`transition_time`] field from a duration.
+    ///
+    /// The duration is rounded to the nearest multiple of 100ms and clamped to the maximum
+    /// transition time of about 109 minutes.
+    ///
+    /// [`transition_time`]: Self::transition_time
+    pub fn with_transition_duration(self, duration: Duration) -> Self {
+        Self {
+            transition_time: Some(util::transition_time(duration)),
+            ..self
+        }
+    }
+
+    /// Convenient method to set the [`color_temperature`] field from a color temperature in
+    /// kelvin.
+    ///
+    /// Returns an error if the converted value is outside of the range from 153 to 500 mired.
+    ///
+    /// [`color_temperature`]: Self::color_temperature
+    pub fn with_color_temperature_kelvin(self, kelvin: u32) -> Result<Self, OutOfRangeError> {
+        let mired = Mired::from_kelvin(kelvin)?;
+        Ok(Self {
+            color_temperature: Some(mired.into()),
+            ..self
+        })
+    }
 }
 
 impl resource::Modifier for StateModifier {
@@ -343,6 +715,44 @@ impl resource::Modifier for StateModifier {
     fn url_suffix(id: Self::Id) -> String {
         format!("lights/{}/state", id)
     }
+
+    fn execute_many<I>(
+        &self,
+        bridge: &Bridge,
+        ids: I,
+        policy: DispatchPolicy,
+    ) -> crate::Result<resource::ModifiedMany<Self::Id>>
+    where
+        I: IntoIterator<Item = Self::Id>,
+    {
+        let body = serde_json::to_value(self)?;
+        let ids: Vec<String> = ids.into_iter().collect();
+        let threads = match policy {
+            DispatchPolicy::Sequential => 1,
+            DispatchPolicy::Parallel(v) => v,
+            DispatchPolicy::TemporaryGroup => {
+                let creator = group::Creator::new("huelib2 temporary".into(), ids.clone())
+                    .with_kind(group::CreatableKind::LightGroup);
+                let group_id = bridge.create_group(&creator)?;
+                let responses = bridge.api_request_responses::<_, Modified>(
+                    format!("groups/{}/action", group_id),
+                    RequestMethod::Put,
+                    Some(&body),
+                );
+                bridge.delete_group(group_id)?;
+                let responses = responses?;
+                return Ok(ids
+                    .into_iter()
+                    .map(|id| (id, Ok(responses.clone())))
+                    .collect());
+            }
+        };
+        let targets = ids
+            .into_iter()
+            .map(|id| (id.clone(), Self::url_suffix(id), &body))
+            .collect();
+        Ok(bridge.put_many(targets, threads, Duration::ZERO))
+    }
 }
 
 impl Serialize for StateModifier {
@@ -412,6 +822,129 @@ mod tests {
         assert_eq!(modifier_json, expected_json);
     }
 
+    fn light_json() -> serde_json::Value {
+        json!({
+            "state": {
+                "on": true,
+                "bri": 144,
+                "hue": 13088,
+                "sat": 212,
+                "effect": "none",
+                "xy": [0.5128, 0.4147],
+                "ct": 467,
+                "alert": "none",
+                "colormode": "xy",
+                "mode": "homeautomation",
+                "reachable": true
+            },
+            "swupdate": {"state": "noupdates", "lastinstall": "2020-01-01T10:00:00"},
+            "type": "Extended color light",
+            "name": "Hue color lamp 1",
+            "modelid": "LCT007",
+            "manufacturername": "Signify Netherlands B.V.",
+            "productname": "Hue color lamp",
+            "capabilities": {
+                "certified": true,
+                "control": {
+                    "mindimlevel": 5000,
+                    "maxlumen": 600,
+                    "colorgamuttype": "B",
+                    "colorgamut": [[0.675, 0.322], [0.409, 0.518], [0.167, 0.04]],
+                    "ct": {"min": 153, "max": 500}
+                },
+                "streaming": {"renderer": true, "proxy": false}
+            },
+            "config": {
+                "archetype": "sultanbulb",
+                "function": "mixed",
+                "direction": "omnidirectional",
+                "startup": {"mode": "safety", "configured": true}
+            },
+            "uniqueid": "00:17:88:01:00:bd:c7:b9-0b",
+            "swversion": "5.105.0.21169"
+        })
+    }
+
+    #[test]
+    fn serialize_config_modifier() {
+        let modifier = ConfigModifier::new();
+        let modifier_json = serde_json::to_value(modifier).unwrap();
+        assert_eq!(modifier_json, json!({}));
+
+        let modifier = ConfigModifier::new().with_startup(Startup::LastOnState);
+        let modifier_json = serde_json::to_value(modifier).unwrap();
+        assert_eq!(modifier_json, json!({"startup": {"mode": "lastonstate"}}));
+
+        let settings = StartupSettings::new()
+            .with_brightness(127)
+            .with_color_temperature(366);
+        let modifier = ConfigModifier::new().with_startup(Startup::Custom { settings });
+        let modifier_json = serde_json::to_value(modifier).unwrap();
+        let expected_json = json!({
+            "startup": {
+                "mode": "custom",
+                "customsettings": {"bri": 127, "ct": 366}
+            }
+        });
+        assert_eq!(modifier_json, expected_json);
+    }
+
+    #[test]
+    fn supported_effects() {
+        let light: Light = serde_json::from_value(light_json()).unwrap();
+        let effects = light.supported_effects();
+        assert!(effects.contains(Effect::Colorloop));
+        assert!(effects.contains(Effect::None));
+
+        let mut json = light_json();
+        json["state"] = json!({"on": true, "bri": 1, "alert": "none", "reachable": true});
+        json["capabilities"]["control"] = json!({"mindimlevel": 5000, "maxlumen": 600});
+        let light: Light = serde_json::from_value(json).unwrap();
+        let effects = light.supported_effects();
+        assert!(effects.is_empty());
+        assert!(!effects.contains(Effect::Colorloop));
+        assert!(effects.contains(Effect::None));
+    }
+
+    #[test]
+    fn name_match() {
+        assert!(NameMatch::Exact.matches("Kitchen counter", "Kitchen counter"));
+        assert!(!NameMatch::Exact.matches("Kitchen counter", "kitchen counter"));
+        assert!(NameMatch::IgnoreCase.matches("Kitchen counter", "kitchen COUNTER"));
+        assert!(!NameMatch::IgnoreCase.matches("Kitchen counter", "counter"));
+        assert!(NameMatch::Contains.matches("Kitchen counter", "COUNTER"));
+        assert!(!NameMatch::Contains.matches("Kitchen counter", "Hall"));
+    }
+
+    #[test]
+    fn plan_renames() {
+        let lights = vec![
+            ("10", "Spot"),
+            ("2", "Spot"),
+            ("3", "Desk"),
+            ("1", "Ceiling spot"),
+        ];
+        let renames = super::plan_renames(lights, "pot", "Kitchen spot {n}");
+        let expected = vec![
+            Rename {
+                id: "1".into(),
+                old_name: "Ceiling spot".into(),
+                new_name: "Kitchen spot 1".into(),
+            },
+            Rename {
+                id: "2".into(),
+                old_name: "Spot".into(),
+                new_name: "Kitchen spot 2".into(),
+            },
+            Rename {
+                id: "10".into(),
+                old_name: "Spot".into(),
+                new_name: "Kitchen spot 3".into(),
+            },
+        ];
+        assert_eq!(renames, expected);
+    }
+
     #[test]
     fn serialize_static_state_modifier() {
         let modifier = StaticStateModifier::new();
@@ -494,6 +1027,197 @@ mod tests {
         assert_eq!(modifier_json, expected_json);
     }
 
+    #[test]
+    fn state_units() {
+        let state = State {
+            brightness: Some(0),
+            hue: Some(32768),
+            saturation: Some(127),
+            color_temperature: Some(370),
+            ..Default::default()
+        };
+        assert_eq!(state.brightness_typed(), Some(Brightness::MIN));
+        assert_eq!(state.hue_typed().map(HueDegrees::degrees), Some(180.0));
+        assert_eq!(state.saturation_typed().map(Saturation::value), Some(127));
+        assert_eq!(state.color_temperature_typed().map(Mired::value), Some(370));
+        assert_eq!(
+            state.color_temperature_kelvin().map(Kelvin::value),
+            Some(2703)
+        );
+        assert_eq!(State::default().brightness_typed(), None);
+    }
+
+    #[test]
+    fn diff_state() {
+        let current = State {
+            on: Some(true),
+            brightness: Some(100),
+            hue: Some(1000),
+            saturation: Some(200),
+            color_space_coordinates: Some((0.3, 0.3)),
+            color_temperature: Some(300),
+            color_mode: Some(ColorMode::ColorTemperature),
+            ..Default::default()
+        };
+        assert_eq!(
+            StateModifier::diff(&current, &current),
+            StateModifier::new()
+        );
+
+        let target = State {
+            brightness: Some(254),
+            hue: Some(2000),
+            color_space_coordinates: Some((0.4, 0.4)),
+            color_temperature: Some(400),
+            color_mode: None,
+            ..Default::default()
+        };
+        let expected = StateModifier::new()
+            .with_brightness(Adjust::Override(254))
+            .with_hue(Adjust::Override(2000))
+            .with_color_space_coordinates(Adjust::Override((0.4, 0.4)))
+            .with_color_temperature(Adjust::Override(400));
+        assert_eq!(StateModifier::diff(&current, &target), expected);
+
+        let target = State {
+            color_mode: Some(ColorMode::ColorSpaceCoordinates),
+            ..target
+        };
+        let expected = StateModifier::new()
+            .with_brightness(Adjust::Override(254))
+            .with_color_space_coordinates(Adjust::Override((0.4, 0.4)));
+        assert_eq!(StateModifier::diff(&current, &target), expected);
+
+        let target = State {
+            color_mode: Some(ColorMode::HueAndSaturation),
+            ..current.clone()
+        };
+        let expected = StateModifier::new()
+            .with_hue(Adjust::Override(1000))
+            .with_saturation(Adjust::Override(200));
+        assert_eq!(StateModifier::diff(&current, &target), expected);
+    }
+
+    #[test]
+    fn transition_duration() {
+        let modifier = StateModifier::new().with_transition_duration(Duration::from_millis(1260));
+        assert_eq!(modifier.transition_time, Some(13));
+        let modifier =
+            StaticStateModifier::new().with_transition_duration(Duration::from_secs(86400));
+        assert_eq!(modifier.transition_time, Some(u16::MAX));
+    }
+
+    #[test]
+    fn color_temperature_kelvin() {
+        let modifier = StateModifier::new()
+            .with_color_temperature_kelvin(2700)
+            .unwrap();
+        assert_eq!(modifier.color_temperature, Some(Adjust::Override(370)));
+        let modifier = StaticStateModifier::new()
+            .with_color_temperature_kelvin(6536)
+            .unwrap();
+        assert_eq!(modifier.color_temperature, Some(153));
+        assert!(StateModifier::new()
+            .with_color_temperature_kelvin(10000)
+            .is_err());
+    }
+
+    #[test]
+    fn static_state_modifier_round_trip() {
+        let modifiers = vec![
+            StaticStateModifier::new(),
+            StaticStateModifier::new()
+                .with_on(false)
+                .with_color_space_coordinates((0.3, 0.4))
+                .with_transition_time(10),
+            StaticStateModifier::new()
+                .with_brightness(254)
+                .with_hue(65535)
+                .with_saturation(0)
+                .with_color_temperature(500)
+                .with_effect(Effect::None),
+        ];
+        for modifier in modifiers {
+            let modifier_json = serde_json::to_value(&modifier).unwrap();
+            let value: StaticStateModifier = serde_json::from_value(modifier_json).unwrap();
+            assert_eq!(value, modifier);
+        }
+    }
+
+    #[test]
+    fn serialize_state_modifier_adjust() {
+        let values = vec![
+            (
+                StateModifier::new().with_brightness(Adjust::Override(10)),
+                json!({"bri": 10}),
+            ),
+            (
+                StateModifier::new().with_brightness(Adjust::Decrement(254)),
+                json!({"bri_inc": -254}),
+            ),
+            (
+                StateModifier::new().with_hue(Adjust::Decrement(65535)),
+                json!({"hue_inc": -65535}),
+            ),
+            (
+                StateModifier::new().with_saturation(Adjust::Override(254)),
+                json!({"sat": 254}),
+            ),
+            (
+                StateModifier::new().with_color_space_coordinates(Adjust::Override((0.25, 0.75))),
+                json!({"xy": [0.25, 0.75]}),
+            ),
+            (
+                StateModifier::new().with_color_space_coordinates(Adjust::Increment((0.5, 0.25))),
+                json!({"xy_inc": [0.5, 0.25]}),
+            ),
+            (
+                StateModifier::new().with_color_temperature(Adjust::Decrement(347)),
+                json!({"ct_inc": -347}),
+            ),
+        ];
+        for (modifier, expected_json) in values {
+            assert_eq!(serde_json::to_value(modifier).unwrap(), expected_json);
+        }
+    }
+
+    #[test]
+    fn execute_many() {
+        use crate::resource::Modifier;
+        use std::net::{IpAddr, Ipv4Addr};
+
+        let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "user").with_dry_run();
+        let modifier = StateModifier::new().with_on(true);
+        let ids = vec!["1".to_owned(), "2".to_owned(), "3".to_owned()];
+
+        let results = modifier
+            .execute_many(&bridge, ids.clone(), DispatchPolicy::Parallel(2))
+            .unwrap();
+        assert_eq!(results.len(), 3);
+        assert!(results.values().all(|v| v.is_ok()));
+        let mut paths: Vec<String> = bridge
+            .take_recorded_requests()
+            .into_iter()
+            .map(|v| v.path)
+            .collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec!["lights/1/state", "lights/2/state", "lights/3/state"]
+        );
+
+        let results = modifier
+            .execute_many(&bridge, ids, DispatchPolicy::TemporaryGroup)
+            .unwrap();
+        assert_eq!(results.len(), 3);
+        let requests = bridge.take_recorded_requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0].method, RequestMethod::Post);
+        assert_eq!(requests[0].path, "groups");
+        assert_eq!(requests[1].body, Some(json!({"on": true})));
+        assert_eq!(requests[2].method, RequestMethod::Delete);
+    }
+
     #[test]
     fn serialize_scanner() {
         let scanner = Scanner::new();
@@ -36,7 +36,7 @@ pub mod schedule;
 pub mod sensor;
 
 pub use capabilities::Capabilities;
-pub use config::Config;
+pub use config::{Config, FullState};
 pub use group::Group;
 pub use light::Light;
 pub use resourcelink::Resourcelink;
@@ -49,11 +49,11 @@ use crate::{response::Modified, Bridge, Error, Response};
 use chrono::NaiveDateTime;
 use serde::{de, de::Error as _, Deserialize, Serialize};
 use serde_json::Value as JsonValue;
-use std::fmt;
+use std::{collections::HashMap, fmt, hash::Hash, time::Duration};
 
 /// Alert effect of a light.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
-#[serde(rename_all = "lowercase")]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
 pub enum Alert {
     /// Performs one breathe cycle.
     Select,
@@ -61,20 +61,91 @@ pub enum Alert {
     LSelect,
     /// Disables any alert.
     None,
+    /// Alert effect that is not known to this crate.
+    ///
+    /// Contains the value returned by the bridge, which is sent back unchanged.
+    Unknown(String),
+}
+
+impl Alert {
+    fn from_name(name: String) -> Self {
+        match name.as_str() {
+            "select" => Self::Select,
+            "lselect" => Self::LSelect,
+            "none" => Self::None,
+            _ => Self::Unknown(name),
+        }
+    }
+
+    fn name(&self) -> &str {
+        match self {
+            Self::Select => "select",
+            Self::LSelect => "lselect",
+            Self::None => "none",
+            Self::Unknown(v) => v,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Alert {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::from_name)
+    }
+}
+
+impl Serialize for Alert {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
 }
 
 /// Dynamic effect of a light.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
-#[serde(rename_all = "lowercase")]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
 pub enum Effect {
     /// Cycles through all hues with the current brightness and saturation.
     Colorloop,
     /// Disables any effect.
     None,
+    /// Dynamic effect that is not known to this crate.
+    ///
+    /// Contains the value returned by the bridge, which is sent back unchanged.
+    Unknown(String),
+}
+
+impl Effect {
+    fn from_name(name: String) -> Self {
+        match name.as_str() {
+            "colorloop" => Self::Colorloop,
+            "none" => Self::None,
+            _ => Self::Unknown(name),
+        }
+    }
+
+    fn name(&self) -> &str {
+        match self {
+            Self::Colorloop => "colorloop",
+            Self::None => "none",
+            Self::Unknown(v) => v,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Effect {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::from_name)
+    }
+}
+
+impl Serialize for Effect {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
 }
 
 /// Color mode of a light.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[non_exhaustive]
 pub enum ColorMode {
     /// Uses a color temperatue to set the color of a light.
     #[serde(rename = "ct")]
@@ -85,6 +156,9 @@ pub enum ColorMode {
     /// Uses x and y coordinates in the color space to set the color of a light.
     #[serde(rename = "xy")]
     ColorSpaceCoordinates,
+    /// Color mode that is not known to this crate.
+    #[serde(other)]
+    Unknown,
 }
 
 /// Struct for new resources that were scanned by the bridge.
@@ -202,6 +276,15 @@ pub enum Adjust<T> {
     Decrement(T),
 }
 
+/// A light or a group whose state can be modified.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum StateTarget {
+    /// Light with the given identifier.
+    Light(String),
+    /// Group with the given identifier.
+    Group(String),
+}
+
 /// Represents a HTTP method.
 #[allow(missing_docs)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
@@ -212,6 +295,26 @@ pub enum RequestMethod {
     Delete,
 }
 
+/// Strategy for applying the same modifier to multiple resources.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum DispatchPolicy {
+    /// Sends one request per resource, one after another.
+    Sequential,
+    /// Sends one request per resource, using up to the given number of threads.
+    Parallel(usize),
+    /// Puts all lights into a temporary group, modifies the state of the group and deletes the
+    /// group afterwards.
+    ///
+    /// This only applies to the light state. For other modifiers this behaves like
+    /// [`Sequential`].
+    ///
+    /// [`Sequential`]: Self::Sequential
+    TemporaryGroup,
+}
+
+/// Results of modifying multiple resources, keyed by the identifier of the resource.
+pub type ModifiedMany<K> = HashMap<K, crate::Result<Vec<Response<Modified>>>>;
+
 /// Marker trait for resources.
 pub trait Resource {}
 
@@ -226,10 +329,10 @@ pub trait Creator: Serialize {
         struct CreationInfo {
             id: String,
         }
-        let mut response: Vec<Response<CreationInfo>> = bridge.api_request(
+        let mut response: Vec<Response<CreationInfo>> = bridge.api_request_responses(
             Self::url_suffix(),
             RequestMethod::Post,
-            Some(serde_json::to_value(self)?),
+            Some(&serde_json::to_value(self)?),
         )?;
         match response.pop() {
             Some(v) => Ok(v.into_result()?.id),
@@ -249,13 +352,43 @@ pub trait Modifier: Serialize {
     fn url_suffix(id: Self::Id) -> String;
 
     /// Sends the request to modify the resource.
+    ///
+    /// Errors returned by the Philips Hue API are not converted into an `Err`, because some
+    /// attributes might have been modified successfully. They are returned as [`Response::Error`]
+    /// with the accessed resource attached instead.
     fn execute(&self, bridge: &Bridge, id: Self::Id) -> crate::Result<Vec<Response<Modified>>> {
-        bridge.api_request(
+        bridge.api_request_responses(
             Self::url_suffix(id),
             RequestMethod::Put,
-            Some(serde_json::to_value(self)?),
+            Some(&serde_json::to_value(self)?),
         )
     }
+
+    /// Sends the request to modify multiple resources.
+    ///
+    /// The modifier is serialized once and the same body is sent for every identifier. Returns
+    /// the result for every identifier.
+    fn execute_many<I>(
+        &self,
+        bridge: &Bridge,
+        ids: I,
+        policy: DispatchPolicy,
+    ) -> crate::Result<ModifiedMany<Self::Id>>
+    where
+        I: IntoIterator<Item = Self::Id>,
+        Self::Id: Clone + Eq + Hash + Send,
+    {
+        let body = serde_json::to_value(self)?;
+        let targets = ids
+            .into_iter()
+            .map(|id| (id.clone(), Self::url_suffix(id), &body))
+            .collect();
+        let threads = match policy {
+            DispatchPolicy::Parallel(v) => v,
+            _ => 1,
+        };
+        Ok(bridge.put_many(targets, threads, Duration::ZERO))
+    }
 }
 
 /// Trait for scanning new resources.
@@ -265,10 +398,10 @@ pub trait Scanner: Serialize {
 
     /// Sends the request to scan for new resources.
     fn execute(&self, bridge: &Bridge) -> crate::Result<()> {
-        let responses: Vec<Response<JsonValue>> = bridge.api_request(
+        let responses: Vec<Response<JsonValue>> = bridge.api_request_responses(
             Self::url_suffix(),
             RequestMethod::Post,
-            Some(serde_json::to_value(self)?),
+            Some(&serde_json::to_value(self)?),
         )?;
         for response in responses {
             response.into_result()?;
@@ -283,6 +416,20 @@ mod tests {
     use chrono::{NaiveDate, NaiveTime};
     use serde_json::json;
 
+    #[test]
+    fn deserialize_unknown_variants() {
+        let alert: Alert = serde_json::from_value(json!("lselect")).unwrap();
+        assert_eq!(alert, Alert::LSelect);
+        let alert: Alert = serde_json::from_value(json!("breathe")).unwrap();
+        assert_eq!(alert, Alert::Unknown("breathe".into()));
+        assert_eq!(serde_json::to_value(alert).unwrap(), json!("breathe"));
+        let effect: Effect = serde_json::from_value(json!("sparkle")).unwrap();
+        assert_eq!(effect, Effect::Unknown("sparkle".into()));
+        assert_eq!(serde_json::to_value(effect).unwrap(), json!("sparkle"));
+        let color_mode: ColorMode = serde_json::from_value(json!("gradient")).unwrap();
+        assert_eq!(color_mode, ColorMode::Unknown);
+    }
+
     #[test]
     fn deserialize_last_scan() {
         let json = json!("none");
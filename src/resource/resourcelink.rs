@@ -27,8 +27,15 @@ pub struct Resourcelink {
 }
 
 impl Resourcelink {
-    pub(crate) fn with_id(self, id: String) -> Self {
-        Self { id, ..self }
+    /// Returns the resourcelink with its identifier set to `id`.
+    pub fn with_id<S>(self, id: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            id: id.into(),
+            ..self
+        }
     }
 }
 
@@ -91,7 +98,7 @@ pub enum LinkKind {
 }
 
 impl LinkKind {
-    fn from_str(value: &str) -> Option<Self> {
+    pub(crate) fn from_str(value: &str) -> Option<Self> {
         match value {
             "groups" => Some(Self::Group),
             "lights" => Some(Self::Light),
@@ -104,7 +111,7 @@ impl LinkKind {
         }
     }
 
-    fn as_str(&self) -> &str {
+    pub(crate) fn as_str(&self) -> &str {
         match self {
             Self::Group => "groups",
             Self::Light => "lights",
@@ -1,8 +1,13 @@
+use crate::resource::resourcelink::LinkKind;
+use crate::resource::schedule::Target;
 use crate::{resource, util};
-use chrono::NaiveDateTime;
+use chrono::{DateTime, NaiveDateTime, Utc};
 use derive_setters::Setters;
 use serde::{Deserialize, Serialize};
 use serde_json::{Error as JsonError, Value as JsonValue};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error as ThisError;
 
 /// A rule for resources on a bridge.
 #[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
@@ -12,21 +17,27 @@ pub struct Rule {
     pub id: String,
     /// Name of the rule.
     pub name: String,
-    /// Owner of the rule.
-    #[serde(deserialize_with = "util::deserialize_option_string")]
+    /// Whitelist user that created the rule.
+    #[serde(default, deserialize_with = "util::deserialize_option_string")]
     pub owner: Option<String>,
-    /// When the rule was last triggered.
+    /// When the rule was last triggered in UTC.
+    ///
+    /// Is `None` if the rule was never triggered.
     #[serde(
         rename = "lasttriggered",
+        default,
         deserialize_with = "util::deserialize_option_date_time"
     )]
     pub last_triggered: Option<NaiveDateTime>,
-    /// How often the rule was triggered.
+    /// How often the rule was triggered since the bridge was powered on.
     #[serde(rename = "timestriggered")]
     pub times_triggered: usize,
-    /// When the rule was created.
+    /// When the rule was created in UTC.
     pub created: NaiveDateTime,
     /// Status of the rule.
+    ///
+    /// The status is [`Status::ResourceDeleted`] if a resource that is used by the rule was
+    /// deleted, see [`Rule::is_broken`].
     pub status: Status,
     /// Conditions of the rule.
     pub conditions: Vec<Condition>,
@@ -35,8 +46,46 @@ pub struct Rule {
 }
 
 impl Rule {
-    pub(crate) fn with_id(self, id: String) -> Self {
-        Self { id, ..self }
+    /// Returns the rule with its identifier set to `id`.
+    pub fn with_id<S>(self, id: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            id: id.into(),
+            ..self
+        }
+    }
+
+    /// Returns when the rule was last triggered as UTC date and time.
+    pub fn last_triggered_utc(&self) -> Option<DateTime<Utc>> {
+        self.last_triggered.map(util::utc)
+    }
+
+    /// Returns when the rule was created as UTC date and time.
+    pub fn created_utc(&self) -> DateTime<Utc> {
+        util::utc(self.created)
+    }
+
+    /// Returns whether the rule cannot be triggered anymore, because a resource that is used by
+    /// the rule was deleted.
+    pub fn is_broken(&self) -> bool {
+        self.status == Status::ResourceDeleted
+    }
+
+    /// Returns whether a condition or an action of the rule addresses the given resource.
+    pub fn references(&self, target: &Target) -> bool {
+        self.conditions.iter().any(|v| v.addresses(target))
+            || self.actions.iter().any(|v| v.addresses(target))
+    }
+}
+
+/// Returns whether an address like `/sensors/2/state` points at the given resource.
+fn address_matches(address: &str, body: Option<&JsonValue>, target: &Target) -> bool {
+    let mut parts = address.trim_start_matches('/').split('/');
+    match (parts.next(), parts.next()) {
+        (Some(kind), Some(id)) => target.matches(kind, id, body),
+        _ => false,
     }
 }
 
@@ -70,6 +119,247 @@ pub struct Condition {
     pub value: Option<String>,
 }
 
+impl Condition {
+    /// Returns whether the condition addresses the given resource.
+    pub fn addresses(&self, target: &Target) -> bool {
+        address_matches(&self.address, None, target)
+    }
+
+    /// Creates a new [`Condition`].
+    pub fn new<A>(address: A, operator: ConditionOperator, value: Option<String>) -> Self
+    where
+        A: Into<String>,
+    {
+        Self {
+            address: address.into(),
+            operator,
+            value,
+        }
+    }
+}
+
+/// Address of an attribute of a resource, used as address of a [`Condition`].
+///
+/// # Examples
+///
+/// Create a condition that triggers when the first button of a dimmer switch is released:
+/// ```
+/// use huelib2::resource::rule::{Condition, ConditionAddress, ConditionOperator, SensorField};
+///
+/// let address = ConditionAddress::sensor("12", SensorField::ButtonEvent);
+/// assert_eq!(address.to_string(), "/sensors/12/state/buttonevent");
+/// let condition = Condition::new(address, ConditionOperator::Equals, Some("1002".into()));
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ConditionAddress {
+    /// Attribute of a sensor.
+    Sensor {
+        /// Identifier of the sensor.
+        id: String,
+        /// Attribute of the sensor.
+        field: SensorField,
+    },
+    /// Attribute of a group.
+    Group {
+        /// Identifier of the group.
+        id: String,
+        /// Attribute of the group.
+        field: GroupField,
+    },
+    /// Local time of the bridge (`/config/localtime`).
+    LocalTime,
+}
+
+impl ConditionAddress {
+    /// Creates a new [`ConditionAddress`] for an attribute of a sensor.
+    pub fn sensor<S>(id: S, field: SensorField) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::Sensor {
+            id: id.into(),
+            field,
+        }
+    }
+
+    /// Creates a new [`ConditionAddress`] for an attribute of a group.
+    pub fn group<S>(id: S, field: GroupField) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::Group {
+            id: id.into(),
+            field,
+        }
+    }
+
+    /// Creates a new [`ConditionAddress`] for the local time of the bridge.
+    ///
+    /// This is used with the [`ConditionOperator::In`] and [`ConditionOperator::NotIn`]
+    /// operators to restrict a rule to a time interval.
+    pub fn local_time() -> Self {
+        Self::LocalTime
+    }
+
+    /// Returns the kind of the addressed resource, or `None` for the local time of the bridge.
+    pub fn kind(&self) -> Option<LinkKind> {
+        match self {
+            Self::Sensor { .. } => Some(LinkKind::Sensor),
+            Self::Group { .. } => Some(LinkKind::Group),
+            Self::LocalTime => None,
+        }
+    }
+}
+
+impl fmt::Display for ConditionAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (kind, id, path) = match self {
+            Self::Sensor { id, field } => (LinkKind::Sensor, id, field.path()),
+            Self::Group { id, field } => (LinkKind::Group, id, field.path()),
+            Self::LocalTime => return f.write_str(LOCAL_TIME_ADDRESS),
+        };
+        write!(f, "/{}/{}/{}", kind.as_str(), id, path)
+    }
+}
+
+impl FromStr for ConditionAddress {
+    type Err = ParseConditionAddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == LOCAL_TIME_ADDRESS {
+            return Ok(Self::LocalTime);
+        }
+        let err = || ParseConditionAddressError(s.to_owned());
+        let mut segments = s.strip_prefix('/').ok_or_else(err)?.splitn(3, '/');
+        let kind = segments.next().and_then(LinkKind::from_str);
+        let (id, path) = match (segments.next(), segments.next()) {
+            (Some(id), Some(path)) if !id.is_empty() => (id, path),
+            _ => return Err(err()),
+        };
+        match kind {
+            Some(LinkKind::Sensor) => SensorField::from_path(path).map(|v| Self::sensor(id, v)),
+            Some(LinkKind::Group) => GroupField::from_path(path).map(|v| Self::group(id, v)),
+            _ => None,
+        }
+        .ok_or_else(err)
+    }
+}
+
+impl From<ConditionAddress> for String {
+    fn from(value: ConditionAddress) -> Self {
+        value.to_string()
+    }
+}
+
+/// Address of the local time of the bridge.
+const LOCAL_TIME_ADDRESS: &str = "/config/localtime";
+
+/// Error that can occur when parsing a [`ConditionAddress`].
+#[derive(Clone, Debug, Eq, PartialEq, ThisError)]
+#[error("Invalid condition address: {0}")]
+pub struct ParseConditionAddressError(pub String);
+
+/// Attribute of a sensor that is used in the address of a condition.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum SensorField {
+    /// Last button event of a switch (`state/buttonevent`).
+    ButtonEvent,
+    /// Presence of a presence sensor (`state/presence`).
+    Presence,
+    /// Flag of a generic flag sensor (`state/flag`).
+    Flag,
+    /// Status of a generic status sensor (`state/status`).
+    Status,
+    /// Temperature of a temperature sensor (`state/temperature`).
+    Temperature,
+    /// Light level of a light level sensor (`state/lightlevel`).
+    LightLevel,
+    /// Whether a light level sensor detects darkness (`state/dark`).
+    Dark,
+    /// Whether it's daytime (`state/daylight`).
+    Daylight,
+    /// Last rotary event of a rotary dial (`state/rotaryevent`).
+    RotaryEvent,
+    /// Expected rotation of a rotary dial (`state/expectedrotation`).
+    ExpectedRotation,
+    /// Time the state of the sensor was last updated (`state/lastupdated`).
+    LastUpdated,
+    /// Whether the sensor is on (`config/on`).
+    On,
+    /// Whether the sensor can be reached by the bridge (`config/reachable`).
+    Reachable,
+    /// Battery state of the sensor (`config/battery`).
+    Battery,
+}
+
+impl SensorField {
+    const ALL: [Self; 14] = [
+        Self::ButtonEvent,
+        Self::Presence,
+        Self::Flag,
+        Self::Status,
+        Self::Temperature,
+        Self::LightLevel,
+        Self::Dark,
+        Self::Daylight,
+        Self::RotaryEvent,
+        Self::ExpectedRotation,
+        Self::LastUpdated,
+        Self::On,
+        Self::Reachable,
+        Self::Battery,
+    ];
+
+    fn from_path(path: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|v| v.path() == path)
+    }
+
+    /// Returns the path of the attribute within the sensor.
+    pub fn path(self) -> &'static str {
+        match self {
+            Self::ButtonEvent => "state/buttonevent",
+            Self::Presence => "state/presence",
+            Self::Flag => "state/flag",
+            Self::Status => "state/status",
+            Self::Temperature => "state/temperature",
+            Self::LightLevel => "state/lightlevel",
+            Self::Dark => "state/dark",
+            Self::Daylight => "state/daylight",
+            Self::RotaryEvent => "state/rotaryevent",
+            Self::ExpectedRotation => "state/expectedrotation",
+            Self::LastUpdated => "state/lastupdated",
+            Self::On => "config/on",
+            Self::Reachable => "config/reachable",
+            Self::Battery => "config/battery",
+        }
+    }
+}
+
+/// Attribute of a group that is used in the address of a condition.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum GroupField {
+    /// Whether any light of the group is on (`state/any_on`).
+    AnyOn,
+    /// Whether all lights of the group are on (`state/all_on`).
+    AllOn,
+}
+
+impl GroupField {
+    const ALL: [Self; 2] = [Self::AnyOn, Self::AllOn];
+
+    fn from_path(path: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|v| v.path() == path)
+    }
+
+    /// Returns the path of the attribute within the group.
+    pub fn path(self) -> &'static str {
+        match self {
+            Self::AnyOn => "state/any_on",
+            Self::AllOn => "state/all_on",
+        }
+    }
+}
+
 /// Condition operator of a rule.
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
 pub enum ConditionOperator {
@@ -115,6 +405,13 @@ pub struct Action {
 }
 
 impl Action {
+    /// Returns whether the action addresses the given resource.
+    ///
+    /// An action that recalls a scene on a group addresses both the group and the scene.
+    pub fn addresses(&self, target: &Target) -> bool {
+        address_matches(&self.address, Some(&self.body), target)
+    }
+
     /// Creates a new action from a [`Creator`].
     ///
     /// [`Creator`]: resource::Creator
@@ -294,6 +591,172 @@ mod tests {
         assert_eq!(action_json, expected_json);
     }
 
+    #[test]
+    fn deserialize_rule() {
+        let rule_json = json!({
+            "name": "Wall switch",
+            "owner": "abc",
+            "created": "2020-01-01T12:00:00",
+            "lasttriggered": "none",
+            "timestriggered": 0,
+            "status": "resourcedeleted",
+            "conditions": [
+                {"address": "/sensors/2/state/buttonevent", "operator": "eq", "value": "1002"}
+            ],
+            "actions": [
+                {"address": "/groups/0/action", "method": "PUT", "body": {"on": true}}
+            ]
+        });
+        let rule: Rule = serde_json::from_value(rule_json).unwrap();
+        assert_eq!(rule.owner, Some("abc".into()));
+        assert_eq!(rule.last_triggered, None);
+        assert_eq!(
+            rule.created,
+            chrono::NaiveDate::from_ymd_opt(2020, 1, 1)
+                .unwrap()
+                .and_hms_opt(12, 0, 0)
+                .unwrap()
+        );
+        assert_eq!(rule.created_utc().to_rfc3339(), "2020-01-01T12:00:00+00:00");
+        assert_eq!(rule.status, Status::ResourceDeleted);
+        assert!(rule.is_broken());
+
+        let rule_json = json!({
+            "name": "Motion",
+            "owner": "none",
+            "created": "2020-01-01T12:00:00",
+            "lasttriggered": "2020-01-02T08:30:00",
+            "timestriggered": 3,
+            "status": "enabled",
+            "conditions": [],
+            "actions": []
+        });
+        let rule: Rule = serde_json::from_value(rule_json).unwrap();
+        assert_eq!(rule.owner, None);
+        assert!(rule.last_triggered.is_some());
+        assert_eq!(rule.times_triggered, 3);
+        assert!(!rule.is_broken());
+    }
+
+    #[test]
+    fn rule_references() {
+        let rule_json = json!({
+            "name": "Wall switch",
+            "owner": "abc",
+            "created": "2020-01-01T12:00:00",
+            "lasttriggered": "none",
+            "timestriggered": 0,
+            "status": "enabled",
+            "conditions": [
+                {"address": "/sensors/2/state/buttonevent", "operator": "eq", "value": "1002"},
+                {"address": "/config/localtime", "operator": "in", "value": "T08:00:00/T20:00:00"}
+            ],
+            "actions": [
+                {"address": "/groups/1/action", "method": "PUT", "body": {"scene": "abc"}},
+                {"address": "/lights/4/state", "method": "PUT", "body": {"on": false}}
+            ]
+        });
+        let rule: Rule = serde_json::from_value(rule_json).unwrap();
+        assert!(rule.references(&Target::Sensor("2".into())));
+        assert!(!rule.references(&Target::Sensor("20".into())));
+        assert!(rule.references(&Target::Group("1".into())));
+        assert!(rule.references(&Target::Scene("abc".into())));
+        assert!(rule.references(&Target::Light("4".into())));
+        assert!(!rule.references(&Target::Light("1".into())));
+    }
+
+    #[test]
+    fn set_rules_status() {
+        use crate::Bridge;
+        use std::net::{IpAddr, Ipv4Addr};
+
+        let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "user").with_dry_run();
+        let results = bridge
+            .set_rules_status(vec!["1", "2"], Status::Disabled)
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results.values().all(|v| v.is_ok()));
+        let requests = bridge.take_recorded_requests();
+        let requests: Vec<_> = requests
+            .iter()
+            .map(|v| (v.path.as_str(), v.body.clone()))
+            .collect();
+        assert_eq!(
+            requests,
+            vec![
+                ("rules/1", Some(json!({"status": "disabled"}))),
+                ("rules/2", Some(json!({"status": "disabled"}))),
+            ]
+        );
+
+        let server = crate::bridge::test_server::TestServer::start(vec![
+            json!([{"success": {"/rules/1/status": "enabled"}}]),
+            json!([{"error": {
+                "type": 3,
+                "address": "/rules/2",
+                "description": "resource, /rules/2, not available"
+            }}]),
+        ]);
+        let results = server
+            .bridge()
+            .set_rules_status(vec!["1", "2"], Status::Enabled)
+            .unwrap();
+        assert!(results["1"].is_ok());
+        let error = results["2"].as_ref().unwrap_err();
+        assert!(error.is_not_found());
+        assert_eq!(error.resource().unwrap().endpoint, "rules/2");
+        server.finish();
+    }
+
+    #[test]
+    fn condition_address() {
+        let address = ConditionAddress::sensor("12", SensorField::ButtonEvent);
+        assert_eq!(address.to_string(), "/sensors/12/state/buttonevent");
+        let address = ConditionAddress::group("0", GroupField::AnyOn);
+        assert_eq!(String::from(address), "/groups/0/state/any_on");
+        let address = ConditionAddress::sensor("3", SensorField::Battery);
+        let condition = Condition::new(address, ConditionOperator::LessThan, Some("20".into()));
+        assert_eq!(
+            serde_json::to_value(condition).unwrap(),
+            json!({"address": "/sensors/3/config/battery", "operator": "lt", "value": "20"})
+        );
+        let condition = Condition::new(
+            ConditionAddress::local_time(),
+            ConditionOperator::In,
+            Some("T20:00:00/T08:00:00".into()),
+        );
+        assert_eq!(
+            serde_json::to_value(condition).unwrap(),
+            json!({"address": "/config/localtime", "operator": "in", "value": "T20:00:00/T08:00:00"})
+        );
+    }
+
+    #[test]
+    fn parse_condition_address() {
+        for address in [
+            ConditionAddress::sensor("12", SensorField::ButtonEvent),
+            ConditionAddress::sensor("3", SensorField::Battery),
+            ConditionAddress::group("0", GroupField::AllOn),
+            ConditionAddress::local_time(),
+        ] {
+            assert_eq!(address.to_string().parse(), Ok(address));
+        }
+        for address in [
+            "",
+            "sensors/1/state/flag",
+            "/sensors//state/flag",
+            "/sensors/1/state/any_on",
+            "/groups/1/state/presence",
+            "/scenes/1/state/any_on",
+            "/config/name",
+        ] {
+            assert_eq!(
+                address.parse::<ConditionAddress>(),
+                Err(ParseConditionAddressError(address.to_owned()))
+            );
+        }
+    }
+
     #[test]
     fn serialize_creator() {
         let conditions = vec![Condition {
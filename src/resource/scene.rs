@@ -6,7 +6,7 @@ use serde_repr::Deserialize_repr;
 use std::collections::HashMap;
 
 /// A scene.
-#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize)]
 pub struct Scene {
     /// Identifier of the scene.
     #[serde(skip_deserializing)]
@@ -14,12 +14,17 @@ pub struct Scene {
     /// Name of the scene.
     pub name: String,
     /// Kind of the scene.
-    #[serde(rename = "type")]
+    #[serde(flatten)]
     pub kind: Kind,
-    /// Identifier of the group that the scene is linked to.
-    pub group: Option<String>,
     /// Identifier of the lights that are in this scene.
     pub lights: Option<Vec<String>>,
+    /// States of the lights in the scene, keyed by light identifier.
+    ///
+    /// Only available with an individual scene resource, see [`Bridge::get_scene`].
+    ///
+    /// [`Bridge::get_scene`]: crate::Bridge::get_scene
+    #[serde(rename = "lightstates", default)]
+    pub light_states: Option<HashMap<String, light::StaticStateModifier>>,
     /// Whitelist user that created or modified the content of the scene.
     #[serde(deserialize_with = "util::deserialize_option_string")]
     pub owner: Option<String>,
@@ -47,20 +52,61 @@ pub struct Scene {
 }
 
 impl Scene {
-    pub(crate) fn with_id(self, id: String) -> Self {
-        Self { id, ..self }
+    /// Returns the scene with its identifier set to `id`.
+    pub fn with_id<S>(self, id: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            id: id.into(),
+            ..self
+        }
+    }
+
+    /// Returns the identifier of the group that the scene is linked to.
+    ///
+    /// Returns `None` if the scene is a light scene.
+    pub fn group(&self) -> Option<&str> {
+        match &self.kind {
+            Kind::LightScene => None,
+            Kind::GroupScene { group } => Some(group),
+        }
     }
 }
 
 impl resource::Resource for Scene {}
 
 /// Kind of a scene.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[serde(tag = "type")]
 pub enum Kind {
     /// Represents a scene with lights.
     LightScene,
     /// Represents a scene which links to a specific group.
-    GroupScene,
+    ///
+    /// The lights of the scene are the lights of the group.
+    GroupScene {
+        /// Identifier of the group that the scene is linked to.
+        group: String,
+    },
+}
+
+/// Kind of a scene that is created.
+///
+/// A light scene contains specific lights, a group scene contains the lights of a group.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize)]
+#[serde(tag = "type")]
+pub enum CreatableKind {
+    /// Represents a scene with lights.
+    LightScene {
+        /// Identifiers of the lights in the scene.
+        lights: Vec<String>,
+    },
+    /// Represents a scene which links to a specific group.
+    GroupScene {
+        /// Identifier of the group that the scene is linked to.
+        group: String,
+    },
 }
 
 /// Version of a scene document.
@@ -91,12 +137,10 @@ pub struct Creator {
     /// Sets the name of the scene.
     #[setters(skip)]
     pub name: String,
-    /// Sets the light identifiers of the scene.
+    /// Sets the kind of the scene with its lights or group.
     #[setters(skip)]
-    pub lights: Vec<String>,
-    /// Sets the type of the scene.
-    #[serde(skip_serializing_if = "Option::is_none", rename = "type")]
-    pub kind: Option<Kind>,
+    #[serde(flatten)]
+    pub kind: CreatableKind,
     /// Sets the app data of the scene.
     #[serde(skip_serializing_if = "Option::is_none", rename = "appdata")]
     pub app_data: Option<AppData>,
@@ -107,15 +151,47 @@ pub struct Creator {
 
 impl Creator {
     /// Creates a new [`Creator`].
-    pub fn new(name: String, lights: Vec<String>) -> Self {
+    pub fn new(name: String, kind: CreatableKind) -> Self {
         Self {
             name,
-            lights,
-            kind: None,
+            kind,
             app_data: None,
             light_states: None,
         }
     }
+
+    /// Creates a new [`Creator`] for a scene with the given lights.
+    pub fn light_scene(name: String, lights: Vec<String>) -> Self {
+        Self::new(name, CreatableKind::LightScene { lights })
+    }
+
+    /// Creates a new [`Creator`] for a scene that is linked to a group.
+    pub fn group_scene(name: String, group: String) -> Self {
+        Self::new(name, CreatableKind::GroupScene { group })
+    }
+
+    /// Creates a new [`Creator`] for a copy of a scene with another name.
+    ///
+    /// The copy has the same lights or group, app data and light states. Note that scenes returned
+    /// by [`Bridge::get_all_scenes`] do not contain light states.
+    ///
+    /// [`Bridge::get_all_scenes`]: crate::Bridge::get_all_scenes
+    pub fn from_scene(name: String, scene: &Scene) -> Self {
+        let kind = match &scene.kind {
+            Kind::LightScene => CreatableKind::LightScene {
+                lights: scene.lights.clone().unwrap_or_default(),
+            },
+            Kind::GroupScene { group } => CreatableKind::GroupScene {
+                group: group.clone(),
+            },
+        };
+        let app_data = Some(scene.app_data.clone()).filter(|v| v != &AppData::default());
+        Self {
+            app_data,
+            light_states: scene.light_states.clone(),
+            ..Self::new(name, kind)
+        }
+    }
 }
 
 impl resource::Creator for Creator {
@@ -158,6 +234,33 @@ impl resource::Modifier for Modifier {
     }
 }
 
+/// Struct for modifying the state of a single light in a scene.
+///
+/// The identifier is a tuple of the scene identifier and the light identifier.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct LightStateModifier(pub light::StaticStateModifier);
+
+impl LightStateModifier {
+    /// Creates a new [`LightStateModifier`].
+    pub fn new(state: light::StaticStateModifier) -> Self {
+        Self(state)
+    }
+}
+
+impl From<light::StaticStateModifier> for LightStateModifier {
+    fn from(value: light::StaticStateModifier) -> Self {
+        Self(value)
+    }
+}
+
+impl resource::Modifier for LightStateModifier {
+    type Id = (String, String);
+    fn url_suffix((scene_id, light_id): Self::Id) -> String {
+        format!("scenes/{}/lightstates/{}", scene_id, light_id)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -165,18 +268,18 @@ mod tests {
 
     #[test]
     fn serialize_creator() {
-        let creator = Creator::new("test".into(), vec!["1".into()]);
+        let creator = Creator::light_scene("test".into(), vec!["1".into()]);
         let creator_json = serde_json::to_value(creator).unwrap();
         let expected_json = json!({
             "name": "test",
+            "type": "LightScene",
             "lights": ["1"]
         });
         assert_eq!(creator_json, expected_json);
 
         let creator = Creator {
             name: "test".into(),
-            lights: vec!["1".into()],
-            kind: Some(Kind::GroupScene),
+            kind: CreatableKind::GroupScene { group: "2".into() },
             app_data: Some(AppData {
                 version: Some(2),
                 data: Some("data test".into()),
@@ -186,8 +289,8 @@ mod tests {
         let creator_json = serde_json::to_value(creator).unwrap();
         let expected_json = json!({
             "name": "test",
-            "lights": ["1"],
             "type": "GroupScene",
+            "group": "2",
             "appdata": {
                 "version": 2,
                 "data": "data test"
@@ -195,6 +298,121 @@ mod tests {
             "lightstates": {}
         });
         assert_eq!(creator_json, expected_json);
+
+        let mut light_states = HashMap::new();
+        light_states.insert(
+            "1".to_owned(),
+            light::StaticStateModifier::new()
+                .with_on(true)
+                .with_brightness(10),
+        );
+        let creator = Creator {
+            name: "test".into(),
+            kind: CreatableKind::LightScene {
+                lights: vec!["1".into()],
+            },
+            app_data: Some(AppData {
+                version: Some(1),
+                data: None,
+            }),
+            light_states: Some(light_states),
+        };
+        let creator_json = serde_json::to_value(creator).unwrap();
+        let expected_json = json!({
+            "name": "test",
+            "type": "LightScene",
+            "lights": ["1"],
+            "appdata": {"version": 1},
+            "lightstates": {"1": {"on": true, "bri": 10}}
+        });
+        assert_eq!(creator_json, expected_json);
+    }
+
+    #[test]
+    fn deserialize_light_states() {
+        let scene_json = json!({
+            "name": "Reading",
+            "type": "LightScene",
+            "group": null,
+            "lights": ["1", "2"],
+            "owner": "user",
+            "recycle": false,
+            "locked": false,
+            "appdata": {},
+            "picture": "",
+            "lastupdate": "2020-01-01T12:00:00",
+            "version": 2,
+            "lightstates": {
+                "1": {"on": true, "bri": 254, "ct": 366},
+                "2": {"on": false, "xy": [0.5, 0.25], "effect": "none"}
+            }
+        });
+        let scene: Scene = serde_json::from_value(scene_json).unwrap();
+        assert_eq!(scene.kind, Kind::LightScene);
+        assert_eq!(scene.group(), None);
+        let light_states = scene.light_states.unwrap();
+        assert_eq!(
+            light_states["1"],
+            light::StaticStateModifier::new()
+                .with_on(true)
+                .with_brightness(254)
+                .with_color_temperature(366)
+        );
+        let state_json = serde_json::to_value(&light_states["2"]).unwrap();
+        assert_eq!(
+            state_json,
+            json!({"on": false, "xy": [0.5, 0.25], "effect": "none"})
+        );
+    }
+
+    #[test]
+    fn deserialize_group_scene() {
+        let scene_json = json!({
+            "name": "Relax",
+            "type": "GroupScene",
+            "group": "2",
+            "lights": ["1", "2"],
+            "owner": "user",
+            "recycle": false,
+            "locked": false,
+            "appdata": {},
+            "picture": "",
+            "lastupdate": null,
+            "version": 2
+        });
+        let scene: Scene = serde_json::from_value(scene_json).unwrap();
+        assert_eq!(scene.kind, Kind::GroupScene { group: "2".into() });
+        assert_eq!(scene.group(), Some("2"));
+        assert_eq!(scene.lights, Some(vec!["1".into(), "2".into()]));
+    }
+
+    #[test]
+    fn creator_from_scene() {
+        let scene_json = json!({
+            "name": "Relax",
+            "type": "GroupScene",
+            "group": "2",
+            "lights": ["1"],
+            "owner": "user",
+            "recycle": false,
+            "locked": false,
+            "appdata": {"version": 1, "data": "abc"},
+            "picture": "",
+            "lastupdate": null,
+            "version": 2,
+            "lightstates": {"1": {"on": true, "bri": 100}}
+        });
+        let scene: Scene = serde_json::from_value(scene_json).unwrap();
+        let creator = Creator::from_scene("Relax copy".into(), &scene);
+        let creator_json = serde_json::to_value(creator).unwrap();
+        let expected_json = json!({
+            "name": "Relax copy",
+            "type": "GroupScene",
+            "group": "2",
+            "appdata": {"version": 1, "data": "abc"},
+            "lightstates": {"1": {"on": true, "bri": 100}}
+        });
+        assert_eq!(creator_json, expected_json);
     }
 
     #[test]
@@ -219,4 +437,27 @@ mod tests {
         });
         assert_eq!(modifier_json, expected_json);
     }
+
+    #[test]
+    fn serialize_light_state_modifier() {
+        use resource::Modifier as _;
+
+        let modifier = LightStateModifier::new(
+            light::StaticStateModifier::new()
+                .with_on(true)
+                .with_brightness(200)
+                .with_transition_time(4),
+        );
+        let modifier_json = serde_json::to_value(modifier).unwrap();
+        let expected_json = json!({
+            "on": true,
+            "bri": 200,
+            "transitiontime": 4
+        });
+        assert_eq!(modifier_json, expected_json);
+        assert_eq!(
+            LightStateModifier::url_suffix(("abc".into(), "3".into())),
+            "scenes/abc/lightstates/3"
+        );
+    }
 }
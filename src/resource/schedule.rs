@@ -1,8 +1,15 @@
-use crate::resource;
-use chrono::NaiveDateTime;
+use crate::resource::{self, group, light, sensor};
+use crate::units::OutOfRangeError;
+use crate::util;
+use chrono::{DateTime, NaiveDateTime, NaiveTime, Utc, Weekday};
 use derive_setters::Setters;
 use serde::{Deserialize, Serialize};
 use serde_json::{Error as JsonError, Value as JsonValue};
+use std::fmt;
+use std::ops::{BitOr, BitOrAssign};
+use std::str::FromStr;
+use std::time::Duration;
+use thiserror::Error as ThisError;
 
 /// Schedule of a resource.
 #[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
@@ -18,9 +25,13 @@ pub struct Schedule {
     pub command: Command,
     /// Time when the scheduled event will occur.
     #[serde(rename = "localtime")]
-    pub local_time: String,
+    pub local_time: LocalTime,
     /// UTC time that the timer was started. Only provided for timers.
-    #[serde(rename = "starttime")]
+    #[serde(
+        rename = "starttime",
+        default,
+        deserialize_with = "util::deserialize_option_date_time"
+    )]
     pub start_time: Option<NaiveDateTime>,
     /// Status of the schedule.
     pub status: Status,
@@ -30,8 +41,20 @@ pub struct Schedule {
 }
 
 impl Schedule {
-    pub(crate) fn with_id(self, id: String) -> Self {
-        Self { id, ..self }
+    /// Returns the schedule with its identifier set to `id`.
+    pub fn with_id<S>(self, id: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            id: id.into(),
+            ..self
+        }
+    }
+
+    /// Returns the time that the timer was started as UTC date and time.
+    pub fn start_time_utc(&self) -> Option<DateTime<Utc>> {
+        self.start_time.map(util::utc)
     }
 }
 
@@ -80,6 +103,71 @@ impl Command {
         })
     }
 
+    /// Creates a new command that modifies the state of a light.
+    pub fn light_state<S1, S2>(
+        id: S1,
+        modifier: &light::StateModifier,
+        username: S2,
+    ) -> Result<Self, JsonError>
+    where
+        S1: Into<String>,
+        S2: AsRef<str>,
+    {
+        Self::from_modifier(modifier, id.into(), username)
+    }
+
+    /// Creates a new command that modifies the state of a group.
+    pub fn group_state<S1, S2>(
+        id: S1,
+        modifier: &group::StateModifier,
+        username: S2,
+    ) -> Result<Self, JsonError>
+    where
+        S1: Into<String>,
+        S2: AsRef<str>,
+    {
+        Self::from_modifier(modifier, id.into(), username)
+    }
+
+    /// Creates a new command that recalls a scene on a group.
+    pub fn scene_recall<S1, S2, S3>(
+        group_id: S1,
+        scene_id: S2,
+        username: S3,
+    ) -> Result<Self, JsonError>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: AsRef<str>,
+    {
+        let modifier = group::StateModifier::new().with_scene(scene_id.into());
+        Self::group_state(group_id, &modifier, username)
+    }
+
+    /// Creates a new command that modifies the state of a sensor.
+    pub fn sensor_state<S1, S2>(
+        id: S1,
+        modifier: &sensor::StateModifier,
+        username: S2,
+    ) -> Result<Self, JsonError>
+    where
+        S1: Into<String>,
+        S2: AsRef<str>,
+    {
+        Self::from_modifier(modifier, id.into(), username)
+    }
+
+    /// Returns whether the command addresses the given resource.
+    ///
+    /// A command that recalls a scene on a group addresses both the group and the scene.
+    pub fn addresses(&self, target: &Target) -> bool {
+        let mut parts = self.address.trim_start_matches('/').split('/').skip(2);
+        match (parts.next(), parts.next()) {
+            (Some(kind), Some(id)) => target.matches(kind, id, Some(&self.body)),
+            _ => false,
+        }
+    }
+
     /// Creates a new command from a [`Scanner`].
     ///
     /// [`Scanner`]: resource::Scanner
@@ -96,6 +184,43 @@ impl Command {
     }
 }
 
+/// Resource that is addressed by the command of a schedule or by a rule.
+///
+/// See [`Bridge::get_schedules_for`] and [`Bridge::find_rules_referencing`] for more information.
+///
+/// [`Bridge::get_schedules_for`]: crate::Bridge::get_schedules_for
+/// [`Bridge::find_rules_referencing`]: crate::Bridge::find_rules_referencing
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Target {
+    /// Light with the given identifier.
+    Light(String),
+    /// Group with the given identifier.
+    Group(String),
+    /// Scene with the given identifier.
+    Scene(String),
+    /// Sensor with the given identifier.
+    Sensor(String),
+}
+
+impl Target {
+    /// Returns whether the resource with the given kind (e.g. `lights`) and identifier is the
+    /// target.
+    ///
+    /// A scene is also matched by a request to a group whose body recalls the scene.
+    pub(crate) fn matches(&self, kind: &str, id: &str, body: Option<&JsonValue>) -> bool {
+        match self {
+            Self::Light(v) => kind == "lights" && id == v,
+            Self::Group(v) => kind == "groups" && id == v,
+            Self::Sensor(v) => kind == "sensors" && id == v,
+            Self::Scene(v) => {
+                (kind == "scenes" && id == v)
+                    || (kind == "groups"
+                        && body.and_then(|b| b.get("scene")).and_then(|b| b.as_str()) == Some(v))
+            }
+        }
+    }
+}
+
 /// Request method of an command.
 #[allow(missing_docs)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
@@ -116,6 +241,442 @@ pub enum Status {
     Disabled,
 }
 
+/// Time when the scheduled event of a schedule occurs.
+///
+/// This is converted from and to the time patterns of the Philips Hue API, e.g. `W124/T06:30:00`
+/// for a recurring time. Durations are truncated to whole seconds. Patterns that are not known to
+/// this crate are deserialized as [`Unknown`].
+///
+/// [`Unknown`]: Self::Unknown
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum LocalTime {
+    /// Occurs once at the given time.
+    AbsoluteTime(NaiveDateTime),
+    /// Occurs once at the given time, delayed by a random duration.
+    RandomizedTime {
+        /// Earliest time when the event occurs.
+        time: NaiveDateTime,
+        /// Maximum random delay.
+        randomization: Duration,
+    },
+    /// Occurs at the given time on every selected weekday.
+    RecurringTime {
+        /// Weekdays on which the event occurs.
+        weekdays: Weekdays,
+        /// Time when the event occurs.
+        time: NaiveTime,
+    },
+    /// Occurs at the given time on every selected weekday, delayed by a random duration.
+    RecurringRandomizedTime {
+        /// Weekdays on which the event occurs.
+        weekdays: Weekdays,
+        /// Earliest time when the event occurs.
+        time: NaiveTime,
+        /// Maximum random delay.
+        randomization: Duration,
+    },
+    /// Occurs once after the given duration.
+    Timer(Duration),
+    /// Occurs once after the given duration, delayed by a random duration.
+    RandomizedTimer {
+        /// Duration until the event occurs.
+        duration: Duration,
+        /// Maximum random delay.
+        randomization: Duration,
+    },
+    /// Occurs repeatedly after the given duration.
+    RecurringTimer {
+        /// Number of repetitions, or `None` to repeat forever.
+        count: Option<u8>,
+        /// Duration between the events.
+        duration: Duration,
+    },
+    /// Occurs repeatedly after the given duration, delayed by a random duration.
+    RecurringRandomizedTimer {
+        /// Number of repetitions, or `None` to repeat forever.
+        count: Option<u8>,
+        /// Duration between the events.
+        duration: Duration,
+        /// Maximum random delay.
+        randomization: Duration,
+    },
+    /// Time pattern that is not known to this crate.
+    Unknown(String),
+}
+
+impl LocalTime {
+    /// Creates a local time that occurs once at the given time.
+    pub fn absolute(time: NaiveDateTime) -> Self {
+        Self::AbsoluteTime(time)
+    }
+
+    /// Creates a local time that occurs at the given time on every selected weekday.
+    pub fn recurring(weekdays: Weekdays, time: NaiveTime) -> Self {
+        Self::RecurringTime { weekdays, time }
+    }
+
+    /// Creates a timer that occurs once after the given duration.
+    ///
+    /// Returns an error if the duration is not between 1 second and 23:59:59. The duration is
+    /// truncated to whole seconds.
+    pub fn timer(duration: Duration) -> Result<Self, OutOfRangeError> {
+        Ok(Self::Timer(check_timer_duration(duration)?))
+    }
+
+    /// Creates a timer that occurs `count` times after the given duration, or forever if `count`
+    /// is `None`.
+    ///
+    /// Returns an error if the duration is not between 1 second and 23:59:59 or if the count is
+    /// not between 1 and 99.
+    pub fn recurring_timer(duration: Duration, count: Option<u8>) -> Result<Self, OutOfRangeError> {
+        if let Some(v) = count.filter(|v| !(1..=MAX_TIMER_COUNT).contains(v)) {
+            return Err(OutOfRangeError {
+                min: 1,
+                max: MAX_TIMER_COUNT.into(),
+                value: Some(v.into()),
+            });
+        }
+        Ok(Self::RecurringTimer {
+            count,
+            duration: check_timer_duration(duration)?,
+        })
+    }
+
+    /// Verifies that the values of the local time are in the range that is accepted by the bridge.
+    ///
+    /// Durations have to be between 1 second and 23:59:59, random delays must not exceed
+    /// 23:59:59, recurring times need at least one weekday and recurring timers are repeated 1 to
+    /// 99 times. Unknown patterns are not verified.
+    pub fn validate(&self) -> Result<(), LocalTimeError> {
+        let (weekdays, duration, randomization, count) = match *self {
+            Self::AbsoluteTime(_) | Self::Unknown(_) => (None, None, None, None),
+            Self::RandomizedTime { randomization, .. } => (None, None, Some(randomization), None),
+            Self::RecurringTime { weekdays, .. } => (Some(weekdays), None, None, None),
+            Self::RecurringRandomizedTime {
+                weekdays,
+                randomization,
+                ..
+            } => (Some(weekdays), None, Some(randomization), None),
+            Self::Timer(duration) => (None, Some(duration), None, None),
+            Self::RandomizedTimer {
+                duration,
+                randomization,
+            } => (None, Some(duration), Some(randomization), None),
+            Self::RecurringTimer { count, duration } => (None, Some(duration), None, count),
+            Self::RecurringRandomizedTimer {
+                count,
+                duration,
+                randomization,
+            } => (None, Some(duration), Some(randomization), count),
+        };
+        if weekdays == Some(Weekdays::default()) {
+            return Err(LocalTimeError::NoWeekdays);
+        }
+        if let Some(v) = duration.filter(|v| check_timer_duration(*v).is_err()) {
+            return Err(LocalTimeError::InvalidDuration(v));
+        }
+        if let Some(v) = randomization.filter(|v| v.as_secs() > MAX_TIMER_SECONDS.into()) {
+            return Err(LocalTimeError::InvalidRandomization(v));
+        }
+        if let Some(v) = count.filter(|v| !(1..=MAX_TIMER_COUNT).contains(v)) {
+            return Err(LocalTimeError::InvalidCount(v));
+        }
+        Ok(())
+    }
+
+    /// Returns the local time with a random delay of at most `randomization`.
+    ///
+    /// Replaces the random delay if the local time is already randomized. Unknown patterns are
+    /// returned unchanged.
+    pub fn with_randomization(self, randomization: Duration) -> Self {
+        match self {
+            Self::AbsoluteTime(time) | Self::RandomizedTime { time, .. } => Self::RandomizedTime {
+                time,
+                randomization,
+            },
+            Self::RecurringTime { weekdays, time }
+            | Self::RecurringRandomizedTime { weekdays, time, .. } => {
+                Self::RecurringRandomizedTime {
+                    weekdays,
+                    time,
+                    randomization,
+                }
+            }
+            Self::Timer(duration) | Self::RandomizedTimer { duration, .. } => {
+                Self::RandomizedTimer {
+                    duration,
+                    randomization,
+                }
+            }
+            Self::RecurringTimer { count, duration }
+            | Self::RecurringRandomizedTimer {
+                count, duration, ..
+            } => Self::RecurringRandomizedTimer {
+                count,
+                duration,
+                randomization,
+            },
+            Self::Unknown(_) => self,
+        }
+    }
+}
+
+impl fmt::Display for LocalTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const DATE_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+        const TIME_FORMAT: &str = "%H:%M:%S";
+        let count = |v: &Option<u8>| v.map(|v| format!("{:02}", v)).unwrap_or_default();
+        match self {
+            Self::AbsoluteTime(time) => write!(f, "{}", time.format(DATE_TIME_FORMAT)),
+            Self::RandomizedTime {
+                time,
+                randomization,
+            } => write!(
+                f,
+                "{}A{}",
+                time.format(DATE_TIME_FORMAT),
+                HmsDuration(*randomization)
+            ),
+            Self::RecurringTime { weekdays, time } => {
+                write!(f, "W{}/T{}", weekdays.bits(), time.format(TIME_FORMAT))
+            }
+            Self::RecurringRandomizedTime {
+                weekdays,
+                time,
+                randomization,
+            } => write!(
+                f,
+                "W{}/T{}A{}",
+                weekdays.bits(),
+                time.format(TIME_FORMAT),
+                HmsDuration(*randomization)
+            ),
+            Self::Timer(duration) => write!(f, "PT{}", HmsDuration(*duration)),
+            Self::RandomizedTimer {
+                duration,
+                randomization,
+            } => write!(
+                f,
+                "PT{}A{}",
+                HmsDuration(*duration),
+                HmsDuration(*randomization)
+            ),
+            Self::RecurringTimer { count: c, duration } => {
+                write!(f, "R{}/PT{}", count(c), HmsDuration(*duration))
+            }
+            Self::RecurringRandomizedTimer {
+                count: c,
+                duration,
+                randomization,
+            } => write!(
+                f,
+                "R{}/PT{}A{}",
+                count(c),
+                HmsDuration(*duration),
+                HmsDuration(*randomization)
+            ),
+            Self::Unknown(pattern) => write!(f, "{}", pattern),
+        }
+    }
+}
+
+impl FromStr for LocalTime {
+    type Err = ParseLocalTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseLocalTimeError(s.to_owned());
+        let (pattern, randomization) = match s.split_once('A') {
+            Some((pattern, randomization)) => (
+                pattern,
+                Some(parse_duration(randomization).ok_or_else(err)?),
+            ),
+            None => (s, None),
+        };
+        let local_time = if let Some(rest) = pattern.strip_prefix('W') {
+            let (bits, time) = rest.split_once("/T").ok_or_else(err)?;
+            let weekdays = bits
+                .parse()
+                .ok()
+                .and_then(Weekdays::from_bits)
+                .ok_or_else(err)?;
+            let time = NaiveTime::parse_from_str(time, "%H:%M:%S").map_err(|_| err())?;
+            Self::RecurringTime { weekdays, time }
+        } else if let Some(rest) = pattern.strip_prefix('R') {
+            let (count, duration) = rest.split_once("/PT").ok_or_else(err)?;
+            let count = match count {
+                "" => None,
+                v => Some(v.parse().map_err(|_| err())?),
+            };
+            let duration = parse_duration(duration).ok_or_else(err)?;
+            Self::RecurringTimer { count, duration }
+        } else if let Some(duration) = pattern.strip_prefix("PT") {
+            Self::Timer(parse_duration(duration).ok_or_else(err)?)
+        } else {
+            let time =
+                NaiveDateTime::parse_from_str(pattern, "%Y-%m-%dT%H:%M:%S").map_err(|_| err())?;
+            Self::AbsoluteTime(time)
+        };
+        Ok(match randomization {
+            Some(v) => local_time.with_randomization(v),
+            None => local_time,
+        })
+    }
+}
+
+impl From<String> for LocalTime {
+    fn from(value: String) -> Self {
+        value.parse().unwrap_or(Self::Unknown(value))
+    }
+}
+
+impl From<LocalTime> for String {
+    fn from(value: LocalTime) -> Self {
+        value.to_string()
+    }
+}
+
+/// Maximum duration of a timer in seconds.
+const MAX_TIMER_SECONDS: u32 = 24 * 60 * 60 - 1;
+
+/// Maximum number of repetitions of a recurring timer.
+const MAX_TIMER_COUNT: u8 = 99;
+
+/// Verifies that the duration of a timer is in range and truncates it to whole seconds.
+fn check_timer_duration(duration: Duration) -> Result<Duration, OutOfRangeError> {
+    let seconds = duration.as_secs();
+    if seconds < 1 || seconds > MAX_TIMER_SECONDS.into() {
+        return Err(OutOfRangeError {
+            min: 1,
+            max: MAX_TIMER_SECONDS,
+            value: Some(seconds.min(u32::MAX.into()) as u32),
+        });
+    }
+    Ok(Duration::from_secs(seconds))
+}
+
+/// Error that occurs when the values of a [`LocalTime`] are not accepted by the bridge.
+///
+/// See [`LocalTime::validate`] for more information.
+#[derive(Clone, Debug, Eq, PartialEq, ThisError)]
+pub enum LocalTimeError {
+    /// Error that occurs when a recurring time has no weekdays.
+    #[error("No weekdays selected")]
+    NoWeekdays,
+    /// Error that occurs when the duration of a timer is not between 1 second and 23:59:59.
+    #[error("Duration of {0:?} is not between 1 second and 23:59:59")]
+    InvalidDuration(Duration),
+    /// Error that occurs when a random delay is longer than 23:59:59.
+    #[error("Random delay of {0:?} is longer than 23:59:59")]
+    InvalidRandomization(Duration),
+    /// Error that occurs when the number of repetitions is not between 1 and 99.
+    #[error("Number of repetitions {0} is not between 1 and 99")]
+    InvalidCount(u8),
+}
+
+/// Error that can occur when parsing a [`LocalTime`].
+#[derive(Clone, Debug, Eq, PartialEq, ThisError)]
+#[error("Invalid local time: {0}")]
+pub struct ParseLocalTimeError(pub String);
+
+/// Formats a duration as `hh:mm:ss`.
+struct HmsDuration(Duration);
+
+impl fmt::Display for HmsDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let seconds = self.0.as_secs();
+        write!(
+            f,
+            "{:02}:{:02}:{:02}",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        )
+    }
+}
+
+/// Parses a duration of the format `hh:mm:ss`.
+fn parse_duration(value: &str) -> Option<Duration> {
+    let mut parts = value.split(':').map(|v| v.parse::<u64>().ok());
+    match (parts.next()??, parts.next()??, parts.next()??, parts.next()) {
+        (hours, minutes, seconds, None) if minutes < 60 && seconds < 60 => {
+            Some(Duration::from_secs(hours * 3600 + minutes * 60 + seconds))
+        }
+        _ => None,
+    }
+}
+
+/// Set of weekdays on which a recurring [`LocalTime`] occurs.
+///
+/// This is a bitmask of the format `0MTWTFSS`. Sets can be combined with the `|` operator, e.g.
+/// `Weekdays::SATURDAY | Weekdays::SUNDAY`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Weekdays(u8);
+
+impl Weekdays {
+    /// Monday.
+    pub const MONDAY: Self = Self(0b0100_0000);
+    /// Tuesday.
+    pub const TUESDAY: Self = Self(0b0010_0000);
+    /// Wednesday.
+    pub const WEDNESDAY: Self = Self(0b0001_0000);
+    /// Thursday.
+    pub const THURSDAY: Self = Self(0b0000_1000);
+    /// Friday.
+    pub const FRIDAY: Self = Self(0b0000_0100);
+    /// Saturday.
+    pub const SATURDAY: Self = Self(0b0000_0010);
+    /// Sunday.
+    pub const SUNDAY: Self = Self(0b0000_0001);
+    /// Monday to Friday.
+    pub const WORKDAYS: Self = Self(0b0111_1100);
+    /// Saturday and Sunday.
+    pub const WEEKEND: Self = Self(0b0000_0011);
+    /// Every day of the week.
+    pub const ALL: Self = Self(0b0111_1111);
+
+    /// Creates weekdays from a bitmask of the format `0MTWTFSS`.
+    ///
+    /// Returns `None` if the most significant bit is set.
+    pub fn from_bits(bits: u8) -> Option<Self> {
+        if bits <= Self::ALL.0 {
+            Some(Self(bits))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the bitmask of the format `0MTWTFSS`.
+    pub fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Returns whether all weekdays of `other` are contained in this set.
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl From<Weekday> for Weekdays {
+    fn from(value: Weekday) -> Self {
+        Self(1 << (6 - value.num_days_from_monday()))
+    }
+}
+
+impl BitOr for Weekdays {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Weekdays {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
 /// Struct for creating a schedule.
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
@@ -132,7 +693,7 @@ pub struct Creator {
     /// Sets the local time of the schedule.
     #[serde(rename = "localtime")]
     #[setters(skip)]
-    pub local_time: String,
+    pub local_time: LocalTime,
     /// Sets the status of the schedule.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<Status>,
@@ -146,7 +707,7 @@ pub struct Creator {
 
 impl Creator {
     /// Creates a new [`Creator`].
-    pub fn new(command: Command, local_time: String) -> Self {
+    pub fn new(command: Command, local_time: LocalTime) -> Self {
         Self {
             name: None,
             description: None,
@@ -180,7 +741,7 @@ pub struct Modifier {
     pub command: Option<Command>,
     /// Sets the local time of the schedule.
     #[serde(skip_serializing_if = "Option::is_none", rename = "localtime")]
-    pub local_time: Option<String>,
+    pub local_time: Option<LocalTime>,
     /// Sets the status of the schedule.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<Status>,
@@ -261,6 +822,181 @@ mod tests {
         assert_eq!(command_json, expected_json);
     }
 
+    #[test]
+    fn command_targets() {
+        let modifier = light::StateModifier::new().with_on(true);
+        let command = Command::light_state("1", &modifier, "user").unwrap();
+        assert_eq!(command.address, "/api/user/lights/1/state");
+        assert_eq!(command.request_method, CommandRequestMethod::Put);
+        assert_eq!(command.body, json!({"on": true}));
+
+        let modifier = group::StateModifier::new().with_on(false);
+        let command = Command::group_state("2", &modifier, "user").unwrap();
+        assert_eq!(command.address, "/api/user/groups/2/action");
+        assert_eq!(command.body, json!({"on": false}));
+
+        let command = Command::scene_recall("2", "abc", "user").unwrap();
+        assert_eq!(command.address, "/api/user/groups/2/action");
+        assert_eq!(command.body, json!({"scene": "abc"}));
+
+        let modifier = sensor::StateModifier::Presence(true);
+        let command = Command::sensor_state("3", &modifier, "user").unwrap();
+        assert_eq!(command.address, "/api/user/sensors/3/state");
+        assert_eq!(command.body, json!({"presence": true}));
+    }
+
+    #[test]
+    fn command_addresses() {
+        let modifier = light::StateModifier::new().with_on(true);
+        let command = Command::light_state("1", &modifier, "user").unwrap();
+        assert!(command.addresses(&Target::Light("1".into())));
+        assert!(!command.addresses(&Target::Light("10".into())));
+        assert!(!command.addresses(&Target::Group("1".into())));
+
+        let command = Command::scene_recall("2", "abc", "user").unwrap();
+        assert!(command.addresses(&Target::Group("2".into())));
+        assert!(command.addresses(&Target::Scene("abc".into())));
+        assert!(!command.addresses(&Target::Scene("def".into())));
+
+        let command = Command {
+            address: "/api/user/scenes/abc".into(),
+            request_method: CommandRequestMethod::Delete,
+            body: json!({}),
+        };
+        assert!(command.addresses(&Target::Scene("abc".into())));
+
+        let modifier = sensor::StateModifier::Presence(true);
+        let command = Command::sensor_state("3", &modifier, "user").unwrap();
+        assert!(command.addresses(&Target::Sensor("3".into())));
+
+        let command = Command {
+            address: "/api/user/groups".into(),
+            request_method: CommandRequestMethod::Post,
+            body: json!({}),
+        };
+        assert!(!command.addresses(&Target::Group("1".into())));
+    }
+
+    #[test]
+    fn local_time() {
+        let time = NaiveTime::from_hms_opt(6, 30, 0).unwrap();
+        let date_time = chrono::NaiveDate::from_ymd_opt(2020, 1, 1)
+            .unwrap()
+            .and_time(time);
+        let values = vec![
+            (LocalTime::absolute(date_time), "2020-01-01T06:30:00"),
+            (
+                LocalTime::absolute(date_time).with_randomization(Duration::from_secs(90)),
+                "2020-01-01T06:30:00A00:01:30",
+            ),
+            (
+                LocalTime::recurring(Weekdays::WORKDAYS, time),
+                "W124/T06:30:00",
+            ),
+            (
+                LocalTime::recurring(Weekdays::WEEKEND, time)
+                    .with_randomization(Duration::from_secs(3600)),
+                "W3/T06:30:00A01:00:00",
+            ),
+            (LocalTime::Timer(Duration::from_secs(300)), "PT00:05:00"),
+            (
+                LocalTime::Timer(Duration::from_secs(300))
+                    .with_randomization(Duration::from_secs(5)),
+                "PT00:05:00A00:00:05",
+            ),
+            (
+                LocalTime::RecurringTimer {
+                    count: Some(3),
+                    duration: Duration::from_secs(36_000),
+                },
+                "R03/PT10:00:00",
+            ),
+            (
+                LocalTime::RecurringTimer {
+                    count: None,
+                    duration: Duration::from_secs(60),
+                }
+                .with_randomization(Duration::from_secs(1)),
+                "R/PT00:01:00A00:00:01",
+            ),
+        ];
+        for (local_time, pattern) in values {
+            assert_eq!(local_time.to_string(), pattern);
+            assert_eq!(pattern.parse::<LocalTime>(), Ok(local_time.clone()));
+            assert_eq!(serde_json::to_value(&local_time).unwrap(), json!(pattern));
+            let value: LocalTime = serde_json::from_value(json!(pattern)).unwrap();
+            assert_eq!(value, local_time);
+        }
+
+        for pattern in [
+            "",
+            "W128/T06:30:00",
+            "PT00:60:00",
+            "R1/T00:00:01",
+            "2020-01-01",
+        ] {
+            assert!(pattern.parse::<LocalTime>().is_err(), "{}", pattern);
+            let value: LocalTime = serde_json::from_value(json!(pattern)).unwrap();
+            assert_eq!(value, LocalTime::Unknown(pattern.to_owned()));
+            assert_eq!(serde_json::to_value(&value).unwrap(), json!(pattern));
+        }
+    }
+
+    #[test]
+    fn timer() {
+        let timer = LocalTime::timer(Duration::from_millis(300_500)).unwrap();
+        assert_eq!(timer.to_string(), "PT00:05:00");
+        let timer = LocalTime::recurring_timer(Duration::from_secs(90), Some(5)).unwrap();
+        assert_eq!(timer.to_string(), "R05/PT00:01:30");
+        let timer = LocalTime::recurring_timer(Duration::from_secs(86_399), None).unwrap();
+        assert_eq!(timer.to_string(), "R/PT23:59:59");
+
+        let error = LocalTime::timer(Duration::from_secs(86_400)).unwrap_err();
+        assert_eq!(error.value, Some(86_400));
+        assert!(LocalTime::timer(Duration::from_millis(999)).is_err());
+        assert!(LocalTime::recurring_timer(Duration::from_secs(1), Some(0)).is_err());
+        assert!(LocalTime::recurring_timer(Duration::from_secs(1), Some(100)).is_err());
+    }
+
+    #[test]
+    fn validate_local_time() {
+        let time = NaiveTime::from_hms_opt(6, 30, 0).unwrap();
+        assert_eq!(LocalTime::recurring(Weekdays::ALL, time).validate(), Ok(()));
+        assert_eq!(
+            LocalTime::recurring(Weekdays::default(), time).validate(),
+            Err(LocalTimeError::NoWeekdays)
+        );
+        assert_eq!(
+            LocalTime::Timer(Duration::from_secs(0)).validate(),
+            Err(LocalTimeError::InvalidDuration(Duration::from_secs(0)))
+        );
+        assert_eq!(
+            LocalTime::Timer(Duration::from_secs(60))
+                .with_randomization(Duration::from_secs(86_400))
+                .validate(),
+            Err(LocalTimeError::InvalidRandomization(Duration::from_secs(
+                86_400
+            )))
+        );
+        let timer = LocalTime::RecurringTimer {
+            count: Some(0),
+            duration: Duration::from_secs(60),
+        };
+        assert_eq!(timer.validate(), Err(LocalTimeError::InvalidCount(0)));
+        let timer: LocalTime = "PT24:00:00".parse().unwrap();
+        assert!(timer.validate().is_err());
+    }
+
+    #[test]
+    fn weekdays() {
+        let weekdays = Weekdays::from(Weekday::Mon) | Weekdays::from(Weekday::Sun);
+        assert_eq!(weekdays.bits(), 0b0100_0001);
+        assert!(weekdays.contains(Weekdays::MONDAY));
+        assert!(!weekdays.contains(Weekdays::WEEKEND));
+        assert!(Weekdays::ALL.contains(Weekdays::WORKDAYS | Weekdays::WEEKEND));
+        assert_eq!(Weekdays::from_bits(0b1000_0000), None);
+    }
+
     #[test]
     fn serialize_creator() {
         let command = Command {
@@ -269,7 +1005,7 @@ mod tests {
             body: json!({"on": true}),
         };
 
-        let creator = Creator::new(command.clone(), "2020-01-01T00:00:00".into());
+        let creator = Creator::new(command.clone(), "2020-01-01T00:00:00".parse().unwrap());
         let creator_json = serde_json::to_value(creator).unwrap();
         let expected_json = json!({
             "command": {
@@ -287,7 +1023,7 @@ mod tests {
             name: Some("test".into()),
             description: Some("description test".into()),
             command,
-            local_time: "2020-01-01T00:00:00".into(),
+            local_time: "2020-01-01T00:00:00".parse().unwrap(),
             status: Some(Status::Enabled),
             auto_delete: Some(false),
             recycle: Some(true),
@@ -326,7 +1062,7 @@ mod tests {
                 request_method: CommandRequestMethod::Put,
                 body: json!({"on": true}),
             }),
-            local_time: Some("2020-01-01T00:00:00".into()),
+            local_time: Some("2020-01-01T00:00:00".parse().unwrap()),
             status: Some(Status::Disabled),
             auto_delete: Some(true),
         };
@@ -2,10 +2,16 @@
 
 use crate::{resource, util};
 use derive_setters::Setters;
-use serde::{Deserialize, Serialize};
+use serde::{ser::Error as _, Deserialize, Serialize, Serializer};
+use serde_json::{Error as JsonError, Value as JsonValue};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::time::Duration;
 
 /// A sensor.
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[serde(try_from = "SensorData")]
 pub struct Sensor {
     /// Identifier of the sensor.
     #[serde(skip)]
@@ -34,51 +40,731 @@ pub struct Sensor {
     #[serde(rename = "swversion")]
     pub software_version: Option<String>,
     /// Current state of the sensor.
+    ///
+    /// The variant depends on the type of the sensor.
     pub state: State,
     /// Configuration of the sensor.
+    ///
+    /// The kind of the configuration depends on the type of the sensor.
     pub config: Config,
     /// Whether the group is automatically deleted when not referenced anymore.
     pub recycle: Option<bool>,
 }
 
+/// Sensor as it is returned by the bridge, before the state and the configuration are parsed
+/// according to the type.
+#[derive(Deserialize)]
+struct SensorData {
+    name: String,
+    #[serde(rename = "type")]
+    type_name: String,
+    #[serde(rename = "modelid")]
+    model_id: String,
+    #[serde(rename = "uniqueid")]
+    unique_id: Option<String>,
+    #[serde(rename = "manufacturername")]
+    manufacturer_name: Option<String>,
+    #[serde(rename = "productname")]
+    product_name: Option<String>,
+    #[serde(rename = "diversityid")]
+    diversity_id: Option<String>,
+    #[serde(rename = "swversion")]
+    software_version: Option<String>,
+    state: JsonValue,
+    config: JsonValue,
+    recycle: Option<bool>,
+}
+
+impl TryFrom<SensorData> for Sensor {
+    type Error = JsonError;
+
+    fn try_from(value: SensorData) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: String::new(),
+            state: State::from_type(&value.type_name, value.state),
+            config: Config::from_type(&value.type_name, value.config)?,
+            name: value.name,
+            type_name: value.type_name,
+            model_id: value.model_id,
+            unique_id: value.unique_id,
+            manufacturer_name: value.manufacturer_name,
+            product_name: value.product_name,
+            diversity_id: value.diversity_id,
+            software_version: value.software_version,
+            recycle: value.recycle,
+        })
+    }
+}
+
 impl Sensor {
-    pub(crate) fn with_id(self, id: String) -> Self {
-        Self { id, ..self }
+    /// Returns the sensor with its identifier set to `id`.
+    pub fn with_id<S>(self, id: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            id: id.into(),
+            ..self
+        }
+    }
+
+    /// Returns the kind of the sensor.
+    ///
+    /// Returns `None` if the type of the sensor is unknown.
+    pub fn kind(&self) -> Option<Kind> {
+        Kind::from_type_name(&self.type_name)
+    }
+
+    /// Returns the decoded event of the button that was pressed last, if the sensor is a switch.
+    ///
+    /// The event is decoded according to the type and the model of the switch, so the buttons of
+    /// dimmer switches, Hue taps and Friends of Hue switches are all reported as [`ButtonEvent`].
+    pub fn button_event(&self) -> Option<ButtonEvent> {
+        let value = match &self.state {
+            State::Switch(v) => v.button_event?,
+            _ => return None,
+        };
+        match self.type_name.as_str() {
+            "ZGPSwitch" => ButtonEvent::from_raw_zgp(&self.model_id, value),
+            _ => ButtonEvent::from_raw(value),
+        }
+    }
+
+    /// Returns the MAC address of the physical device of the sensor.
+    ///
+    /// This is the part of the unique identifier before the first `-`, which is shared by all
+    /// sensors of the same device. Returns `None` if this part does not look like a MAC address,
+    /// unless the sensor is a ZLL sensor, so CLIP sensors with arbitrary unique identifiers are
+    /// not grouped.
+    pub fn mac_address(&self) -> Option<&str> {
+        let (mac_address, _) = self.unique_id.as_deref()?.split_once('-')?;
+        match self.type_name.starts_with("ZLL") || is_mac_address(mac_address) {
+            true => Some(mac_address),
+            false => None,
+        }
+    }
+}
+
+/// Returns whether the value consists of six or eight hexadecimal bytes separated by colons.
+fn is_mac_address(value: &str) -> bool {
+    let bytes: Vec<&str> = value.split(':').collect();
+    matches!(bytes.len(), 6 | 8)
+        && bytes
+            .iter()
+            .all(|v| v.len() == 2 && v.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Orders identifiers numerically, falling back to comparing them as strings.
+fn id_order(a: &str, b: &str) -> Ordering {
+    match (a.parse::<u64>(), b.parse::<u64>()) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        _ => a.cmp(b),
     }
 }
 
 impl resource::Resource for Sensor {}
 
+/// Kind of a sensor, as specified by the type name of the sensor.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Kind {
+    /// Motion sensor (`ZLLPresence`).
+    ZllPresence,
+    /// Temperature sensor of a motion sensor (`ZLLTemperature`).
+    ZllTemperature,
+    /// Light level sensor of a motion sensor (`ZLLLightLevel`).
+    ZllLightLevel,
+    /// Switch like the dimmer switch (`ZLLSwitch`).
+    ZllSwitch,
+    /// Battery-less switch like the Hue tap (`ZGPSwitch`).
+    ZgpSwitch,
+    /// Rotary dial like the one of the Hue tap dial switch (`ZLLRelativeRotary`).
+    ZllRelativeRotary,
+    /// Daylight sensor of the bridge (`Daylight`).
+    Daylight,
+    /// Geofence sensor of an app (`Geofence`).
+    Geofence,
+    /// Virtual presence sensor (`CLIPPresence`).
+    ClipPresence,
+    /// Virtual temperature sensor (`CLIPTemperature`).
+    ClipTemperature,
+    /// Virtual light level sensor (`CLIPLightLevel`).
+    ClipLightLevel,
+    /// Virtual switch (`CLIPSwitch`).
+    ClipSwitch,
+    /// Virtual open/close sensor (`CLIPOpenClose`).
+    ClipOpenClose,
+    /// Virtual humidity sensor (`CLIPHumidity`).
+    ClipHumidity,
+    /// Virtual generic status sensor (`CLIPGenericStatus`).
+    ClipGenericStatus,
+    /// Virtual generic flag sensor (`CLIPGenericFlag`).
+    ClipGenericFlag,
+}
+
+impl Kind {
+    /// Returns the kind with the given type name.
+    ///
+    /// Returns `None` if the type name is unknown.
+    pub fn from_type_name(type_name: &str) -> Option<Self> {
+        Some(match type_name {
+            "ZLLPresence" => Self::ZllPresence,
+            "ZLLTemperature" => Self::ZllTemperature,
+            "ZLLLightLevel" => Self::ZllLightLevel,
+            "ZLLSwitch" => Self::ZllSwitch,
+            "ZGPSwitch" => Self::ZgpSwitch,
+            "ZLLRelativeRotary" => Self::ZllRelativeRotary,
+            "Daylight" => Self::Daylight,
+            "Geofence" => Self::Geofence,
+            "CLIPPresence" => Self::ClipPresence,
+            "CLIPTemperature" => Self::ClipTemperature,
+            "CLIPLightLevel" => Self::ClipLightLevel,
+            "CLIPSwitch" => Self::ClipSwitch,
+            "CLIPOpenClose" => Self::ClipOpenClose,
+            "CLIPHumidity" => Self::ClipHumidity,
+            "CLIPGenericStatus" => Self::ClipGenericStatus,
+            "CLIPGenericFlag" => Self::ClipGenericFlag,
+            _ => return None,
+        })
+    }
+
+    /// Returns the type name that is used by the bridge.
+    pub fn type_name(self) -> &'static str {
+        match self {
+            Self::ZllPresence => "ZLLPresence",
+            Self::ZllTemperature => "ZLLTemperature",
+            Self::ZllLightLevel => "ZLLLightLevel",
+            Self::ZllSwitch => "ZLLSwitch",
+            Self::ZgpSwitch => "ZGPSwitch",
+            Self::ZllRelativeRotary => "ZLLRelativeRotary",
+            Self::Daylight => "Daylight",
+            Self::Geofence => "Geofence",
+            Self::ClipPresence => "CLIPPresence",
+            Self::ClipTemperature => "CLIPTemperature",
+            Self::ClipLightLevel => "CLIPLightLevel",
+            Self::ClipSwitch => "CLIPSwitch",
+            Self::ClipOpenClose => "CLIPOpenClose",
+            Self::ClipHumidity => "CLIPHumidity",
+            Self::ClipGenericStatus => "CLIPGenericStatus",
+            Self::ClipGenericFlag => "CLIPGenericFlag",
+        }
+    }
+
+    /// Returns whether the sensor is a virtual sensor, whose state is set with the API.
+    pub fn is_clip(self) -> bool {
+        self.type_name().starts_with("CLIP")
+    }
+}
+
+/// Groups sensors by their physical device.
+///
+/// The keys of the map are the MAC addresses of the devices, see [`Sensor::mac_address`]. Sensors
+/// without a MAC address are ignored.
+pub fn group_by_device(sensors: Vec<Sensor>) -> HashMap<String, Vec<Sensor>> {
+    let mut devices: HashMap<String, Vec<Sensor>> = HashMap::new();
+    for sensor in sensors {
+        if let Some(mac_address) = sensor.mac_address().map(ToOwned::to_owned) {
+            devices.entry(mac_address).or_default().push(sensor);
+        }
+    }
+    devices
+}
+
+/// Returns the name and the battery state in percent of every battery powered device.
+///
+/// The keys of the map are the MAC addresses of the devices, see [`Sensor::mac_address`], so
+/// devices with the same name are reported separately. The name of a motion sensor is the name of
+/// its presence sensor, since this is the name that is shown in the app.
+pub fn battery_levels(sensors: Vec<Sensor>) -> HashMap<String, (String, u8)> {
+    let mut levels = HashMap::new();
+    for (mac_address, mut sensors) in group_by_device(sensors) {
+        sensors.sort_by(|a, b| {
+            (a.type_name != "ZLLPresence")
+                .cmp(&(b.type_name != "ZLLPresence"))
+                .then_with(|| id_order(&a.id, &b.id))
+        });
+        let battery = sensors.iter().filter_map(|v| v.config.battery).min();
+        if let (Some(sensor), Some(battery)) = (sensors.first(), battery) {
+            levels.insert(mac_address, (sensor.name.clone(), battery));
+        }
+    }
+    levels
+}
+
+/// A Hue motion sensor.
+///
+/// The bridge exposes a motion sensor as three sensors, one for the presence, one for the
+/// temperature and one for the light level.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct MotionSensorDevice {
+    /// MAC address of the device.
+    pub mac_address: String,
+    /// Presence sensor of the device.
+    pub presence_sensor: Sensor,
+    /// Temperature sensor of the device.
+    pub temperature_sensor: Option<Sensor>,
+    /// Light level sensor of the device.
+    pub light_level_sensor: Option<Sensor>,
+}
+
+impl MotionSensorDevice {
+    /// Returns the motion sensors that are made up of the given sensors.
+    ///
+    /// Sensors that don't belong to a motion sensor are ignored. The motion sensors are sorted by
+    /// the numeric identifier of their presence sensor.
+    pub fn from_sensors(sensors: Vec<Sensor>) -> Vec<Self> {
+        let mut devices: Vec<_> = group_by_device(sensors)
+            .into_iter()
+            .filter_map(|(mac_address, sensors)| {
+                let mut presence_sensor = None;
+                let mut temperature_sensor = None;
+                let mut light_level_sensor = None;
+                for sensor in sensors {
+                    match sensor.type_name.as_str() {
+                        "ZLLPresence" => presence_sensor = Some(sensor),
+                        "ZLLTemperature" => temperature_sensor = Some(sensor),
+                        "ZLLLightLevel" => light_level_sensor = Some(sensor),
+                        _ => {}
+                    }
+                }
+                Some(Self {
+                    mac_address,
+                    presence_sensor: presence_sensor?,
+                    temperature_sensor,
+                    light_level_sensor,
+                })
+            })
+            .collect();
+        devices.sort_by(|a, b| id_order(&a.presence_sensor.id, &b.presence_sensor.id));
+        devices
+    }
+
+    /// Returns whether presence is detected.
+    pub fn presence(&self) -> Option<bool> {
+        match &self.presence_sensor.state {
+            State::Presence(v) => v.presence,
+            _ => None,
+        }
+    }
+
+    /// Returns the temperature in degrees Celsius.
+    pub fn temperature(&self) -> Option<f32> {
+        match &self.temperature_sensor.as_ref()?.state {
+            State::Temperature(v) => v.celsius(),
+            _ => None,
+        }
+    }
+
+    /// Returns the illuminance in lux.
+    pub fn lux(&self) -> Option<f32> {
+        match &self.light_level_sensor.as_ref()?.state {
+            State::LightLevel(v) => v.lux(),
+            _ => None,
+        }
+    }
+
+    /// Returns the battery state in percent.
+    pub fn battery(&self) -> Option<u8> {
+        self.presence_sensor.config.battery
+    }
+}
+
 /// Current state of a sensor.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Deserialize)]
-pub struct State {
-    /// Whether the sensor is present.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum State {
+    /// State of a presence sensor (`ZLLPresence`, `CLIPPresence`).
+    Presence(PresenceState),
+    /// State of a temperature sensor (`ZLLTemperature`, `CLIPTemperature`).
+    Temperature(TemperatureState),
+    /// State of a light level sensor (`ZLLLightLevel`, `CLIPLightLevel`).
+    LightLevel(LightLevelState),
+    /// State of a switch (`ZLLSwitch`, `ZGPSwitch`, `CLIPSwitch`).
+    Switch(SwitchState),
+    /// State of a rotary dial (`ZLLRelativeRotary`).
+    Rotary(RotaryState),
+    /// State of the daylight sensor of the bridge (`Daylight`).
+    Daylight(DaylightState),
+    /// State of a generic status sensor (`CLIPGenericStatus`).
+    GenericStatus(GenericStatusState),
+    /// State of a generic flag sensor (`CLIPGenericFlag`).
+    GenericFlag(GenericFlagState),
+    /// State of a sensor with an unknown type, or a state that could not be parsed.
+    Other(JsonValue),
+}
+
+impl State {
+    /// Parses the state of a sensor with the given type name.
+    ///
+    /// Falls back to [`State::Other`] if the type is unknown or the state does not match the
+    /// type.
+    pub fn from_type(type_name: &str, value: JsonValue) -> Self {
+        let state = match type_name {
+            "ZLLPresence" | "CLIPPresence" => {
+                serde_json::from_value(value.clone()).map(Self::Presence)
+            }
+            "ZLLTemperature" | "CLIPTemperature" => {
+                serde_json::from_value(value.clone()).map(Self::Temperature)
+            }
+            "ZLLLightLevel" | "CLIPLightLevel" => {
+                serde_json::from_value(value.clone()).map(Self::LightLevel)
+            }
+            "ZLLSwitch" | "ZGPSwitch" | "CLIPSwitch" => {
+                serde_json::from_value(value.clone()).map(Self::Switch)
+            }
+            "ZLLRelativeRotary" => serde_json::from_value(value.clone()).map(Self::Rotary),
+            "Daylight" => serde_json::from_value(value.clone()).map(Self::Daylight),
+            "CLIPGenericStatus" => serde_json::from_value(value.clone()).map(Self::GenericStatus),
+            "CLIPGenericFlag" => serde_json::from_value(value.clone()).map(Self::GenericFlag),
+            _ => return Self::Other(value),
+        };
+        state.unwrap_or(Self::Other(value))
+    }
+
+    /// Returns the time the state was last updated.
+    ///
+    /// Returns `None` for [`State::Other`].
+    pub fn last_updated(&self) -> Option<chrono::NaiveDateTime> {
+        match self {
+            Self::Presence(v) => v.last_updated,
+            Self::Temperature(v) => v.last_updated,
+            Self::LightLevel(v) => v.last_updated,
+            Self::Switch(v) => v.last_updated,
+            Self::Rotary(v) => v.last_updated,
+            Self::Daylight(v) => v.last_updated,
+            Self::GenericStatus(v) => v.last_updated,
+            Self::GenericFlag(v) => v.last_updated,
+            Self::Other(_) => None,
+        }
+    }
+
+    /// Returns the time the state was last updated as UTC date and time.
+    ///
+    /// Returns `None` for [`State::Other`].
+    pub fn last_updated_utc(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.last_updated().map(util::utc)
+    }
+}
+
+/// State of a presence sensor.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Deserialize)]
+pub struct PresenceState {
+    /// Whether presence is detected.
     pub presence: Option<bool>,
-    /// Flag of the sensor.
-    pub flag: Option<bool>,
-    /// The current battery state in percent.
+    /// Time the state was last updated.
     #[serde(
         rename = "lastupdated",
+        default,
         deserialize_with = "util::deserialize_option_date_time"
     )]
     pub last_updated: Option<chrono::NaiveDateTime>,
-    /// Button id that was pressed last.
-    #[serde(rename = "buttonevent")]
-    pub button_event: Option<u32>,
-    /// The temperature in centigrades.
+}
+
+/// State of a temperature sensor.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Deserialize)]
+pub struct TemperatureState {
+    /// The temperature in hundredths of a degree Celsius.
     pub temperature: Option<i32>,
-    /// The light level in centiluxes.
+    /// Time the state was last updated.
+    #[serde(
+        rename = "lastupdated",
+        default,
+        deserialize_with = "util::deserialize_option_date_time"
+    )]
+    pub last_updated: Option<chrono::NaiveDateTime>,
+}
+
+impl TemperatureState {
+    /// Returns the temperature in degrees Celsius.
+    pub fn celsius(&self) -> Option<f32> {
+        self.temperature.map(|v| v as f32 / 100.0)
+    }
+}
+
+/// State of a light level sensor.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Deserialize)]
+pub struct LightLevelState {
+    /// The light level, calculated as `10000 * log10(lux) + 1`.
     #[serde(rename = "lightlevel")]
     pub light_level: Option<u32>,
-    /// Whether it's dark according to the sensor's sensitivity.
+    /// Whether the light level is below the dark threshold of the sensor.
     pub dark: Option<bool>,
-    /// Whether it's daytime according to the sensor's sensitivity.
+    /// Whether the light level is above the dark threshold plus the offset of the sensor.
+    pub daylight: Option<bool>,
+    /// Time the state was last updated.
+    #[serde(
+        rename = "lastupdated",
+        default,
+        deserialize_with = "util::deserialize_option_date_time"
+    )]
+    pub last_updated: Option<chrono::NaiveDateTime>,
+}
+
+impl LightLevelState {
+    /// Returns the illuminance in lux.
+    pub fn lux(&self) -> Option<f32> {
+        self.light_level
+            .map(|v| 10f32.powf((v as f32 - 1.0) / 10000.0))
+    }
+}
+
+/// State of a switch.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Deserialize)]
+pub struct SwitchState {
+    /// Event of the button that was pressed last.
+    #[serde(rename = "buttonevent")]
+    pub button_event: Option<u32>,
+    /// Time the state was last updated.
+    #[serde(
+        rename = "lastupdated",
+        default,
+        deserialize_with = "util::deserialize_option_date_time"
+    )]
+    pub last_updated: Option<chrono::NaiveDateTime>,
+}
+
+impl SwitchState {
+    /// Returns the decoded event of the button that was pressed last.
+    ///
+    /// The event is decoded with the encoding of a `ZLLSwitch`, see [`Sensor::button_event`] for
+    /// other switch types. Returns `None` if no button was pressed yet or the event cannot be
+    /// decoded.
+    pub fn event(&self) -> Option<ButtonEvent> {
+        self.button_event.and_then(ButtonEvent::from_raw)
+    }
+}
+
+/// Decoded button event of a switch like the Hue dimmer switch.
+///
+/// The bridge encodes button events as `button * 1000 + action`, e.g. `1002` is a short release
+/// of the first button. The buttons of the Hue dimmer switches are:
+///
+/// | Button | RWL020, RWL021 | RWL022 |
+/// | ------ | -------------- | ------ |
+/// | 1      | On             | On/Off |
+/// | 2      | Dim up         | Dim up |
+/// | 3      | Dim down       | Dim down |
+/// | 4      | Off            | Hue |
+///
+/// Green power switches (`ZGPSwitch`) use other values, which are decoded by [`from_raw_zgp`].
+///
+/// [`from_raw_zgp`]: Self::from_raw_zgp
+///
+/// # Examples
+///
+/// Use the event as value of a rule condition:
+/// ```
+/// use huelib2::resource::sensor::{ButtonAction, ButtonEvent};
+///
+/// let event = ButtonEvent::new(4, ButtonAction::LongRelease);
+/// assert_eq!(event.value(), 4003);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct ButtonEvent {
+    /// Number of the button, starting at 1.
+    pub button: u8,
+    /// Action of the button.
+    pub action: ButtonAction,
+}
+
+impl ButtonEvent {
+    /// Creates a new [`ButtonEvent`].
+    pub fn new(button: u8, action: ButtonAction) -> Self {
+        Self { button, action }
+    }
+
+    /// Decodes the raw value that is used by the bridge.
+    ///
+    /// Returns `None` if the value is not a known button event.
+    pub fn from_raw(value: u32) -> Option<Self> {
+        let button = value / 1000;
+        if button == 0 || button > u8::MAX as u32 {
+            return None;
+        }
+        let action = match value % 1000 {
+            0 => ButtonAction::InitialPress,
+            1 => ButtonAction::Hold,
+            2 => ButtonAction::ShortRelease,
+            3 => ButtonAction::LongRelease,
+            _ => return None,
+        };
+        Some(Self::new(button as u8, action))
+    }
+
+    /// Decodes the raw value of a green power switch (`ZGPSwitch`) with the given model identifier.
+    ///
+    /// Friends of Hue switches (`FOHSWITCH`) report the values 16 to 19 when button 1 to 4 is
+    /// pressed and the values 20 to 23 when it is released. The Hue tap (`ZGPSWITCH`) only reports
+    /// presses with the values 34, 16, 17 and 18 for button 1 to 4. Returns `None` if the value is
+    /// not a known button event, e.g. when two buttons of a Friends of Hue switch are pressed
+    /// together.
+    pub fn from_raw_zgp(model_id: &str, value: u32) -> Option<Self> {
+        let (button, action) = match (model_id, value) {
+            ("FOHSWITCH", 16..=19) => (value - 15, ButtonAction::InitialPress),
+            ("FOHSWITCH", 20..=23) => (value - 19, ButtonAction::ShortRelease),
+            ("FOHSWITCH", _) => return None,
+            (_, 34) => (1, ButtonAction::InitialPress),
+            (_, 16..=18) => (value - 14, ButtonAction::InitialPress),
+            _ => return None,
+        };
+        Some(Self::new(button as u8, action))
+    }
+
+    /// Returns the raw value that is used by the bridge for a `ZLLSwitch`.
+    pub fn value(self) -> u32 {
+        self.button as u32 * 1000 + self.action as u32
+    }
+}
+
+impl From<ButtonEvent> for u32 {
+    fn from(value: ButtonEvent) -> Self {
+        value.value()
+    }
+}
+
+/// Action of a button.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ButtonAction {
+    /// The button was pressed.
+    InitialPress = 0,
+    /// The button is held down, repeated while the button is held.
+    Hold = 1,
+    /// The button was released after a short press.
+    ShortRelease = 2,
+    /// The button was released after being held down.
+    LongRelease = 3,
+}
+
+/// State of a rotary dial.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Deserialize)]
+pub struct RotaryState {
+    /// Raw value of the last rotary event, 1 when a rotation starts and 2 while it continues.
+    #[serde(rename = "rotaryevent")]
+    pub rotary_event: Option<u8>,
+    /// Expected rotation of the dial in steps, positive when turned clockwise.
+    #[serde(rename = "expectedrotation")]
+    pub expected_rotation: Option<i32>,
+    /// Expected duration of the rotation in milliseconds.
+    #[serde(rename = "expectedeventduration")]
+    pub expected_event_duration: Option<u32>,
+    /// Time the state was last updated.
+    #[serde(
+        rename = "lastupdated",
+        default,
+        deserialize_with = "util::deserialize_option_date_time"
+    )]
+    pub last_updated: Option<chrono::NaiveDateTime>,
+}
+
+impl RotaryState {
+    /// Returns the decoded last rotary event.
+    ///
+    /// Returns `None` if the dial was not turned yet or the event cannot be decoded.
+    pub fn event(&self) -> Option<RotaryEvent> {
+        let action = match self.rotary_event? {
+            1 => RotaryAction::Start,
+            2 => RotaryAction::Repeat,
+            _ => return None,
+        };
+        Some(RotaryEvent {
+            action,
+            rotation: self.expected_rotation?,
+            duration: Duration::from_millis(self.expected_event_duration.unwrap_or(0).into()),
+        })
+    }
+}
+
+/// Decoded event of a rotary dial like the one of the Hue tap dial switch.
+///
+/// # Examples
+///
+/// Dim a group with the dial, like a volume knob:
+/// ```no_run
+/// use huelib2::resource::{group, sensor, Adjust};
+///
+/// # fn main() -> huelib2::Result<()> {
+/// # use huelib2::Bridge;
+/// # use std::net::{IpAddr, Ipv4Addr};
+/// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+/// // let bridge = Bridge::new(...);
+/// if let sensor::State::Rotary(state) = bridge.get_sensor("12")?.state {
+///     if let Some(event) = state.event() {
+///         let steps = event.rotation.unsigned_abs().min(254) as u8;
+///         let brightness = if event.rotation < 0 {
+///             Adjust::Decrement(steps)
+///         } else {
+///             Adjust::Increment(steps)
+///         };
+///         let modifier = group::StateModifier::new()
+///             .with_brightness(brightness)
+///             .with_transition_duration(event.duration);
+///         bridge.set_group_state("1", &modifier)?;
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct RotaryEvent {
+    /// Whether the rotation started or continues.
+    pub action: RotaryAction,
+    /// Expected rotation of the dial in steps, positive when turned clockwise.
+    pub rotation: i32,
+    /// Expected duration of the rotation.
+    pub duration: Duration,
+}
+
+/// Action of a rotary dial.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum RotaryAction {
+    /// The dial started to rotate.
+    Start,
+    /// The dial continues to rotate.
+    Repeat,
+}
+
+/// State of the daylight sensor of the bridge.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Deserialize)]
+pub struct DaylightState {
+    /// Whether it's daytime at the configured location.
     pub daylight: Option<bool>,
-    // TODO: Add missing attributes (https://github.com/yuqio/huelib-rs/issues/2)
+    /// Time the state was last updated.
+    #[serde(
+        rename = "lastupdated",
+        default,
+        deserialize_with = "util::deserialize_option_date_time"
+    )]
+    pub last_updated: Option<chrono::NaiveDateTime>,
+}
+
+/// State of a generic status sensor.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Deserialize)]
+pub struct GenericStatusState {
+    /// Status of the sensor.
+    pub status: Option<i32>,
+    /// Time the state was last updated.
+    #[serde(
+        rename = "lastupdated",
+        default,
+        deserialize_with = "util::deserialize_option_date_time"
+    )]
+    pub last_updated: Option<chrono::NaiveDateTime>,
+}
+
+/// State of a generic flag sensor.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Deserialize)]
+pub struct GenericFlagState {
+    /// Flag of the sensor.
+    pub flag: Option<bool>,
+    /// Time the state was last updated.
+    #[serde(
+        rename = "lastupdated",
+        default,
+        deserialize_with = "util::deserialize_option_date_time"
+    )]
+    pub last_updated: Option<chrono::NaiveDateTime>,
 }
 
 /// Configuration of a sensor.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Deserialize)]
 pub struct Config {
     /// Whether the sensor is on.
     pub on: bool,
@@ -88,6 +774,177 @@ pub struct Config {
     ///
     /// Only for battery powered devices. Not present when not provided on creation (CLIP sensors).
     pub battery: Option<u8>,
+    /// Configuration that is specific to the type of the sensor.
+    #[serde(skip)]
+    pub kind: ConfigKind,
+}
+
+impl Config {
+    /// Parses the configuration of a sensor with the given type name.
+    ///
+    /// The kind of the configuration is [`ConfigKind::Other`] if the type has no specific
+    /// configuration or the configuration does not match the type.
+    pub fn from_type(type_name: &str, value: JsonValue) -> Result<Self, JsonError> {
+        let kind = match type_name {
+            "ZLLPresence" => serde_json::from_value(value.clone()).map(ConfigKind::Presence),
+            "ZLLTemperature" => serde_json::from_value(value.clone()).map(ConfigKind::Temperature),
+            "ZLLLightLevel" => serde_json::from_value(value.clone()).map(ConfigKind::LightLevel),
+            "Daylight" => serde_json::from_value(value.clone()).map(ConfigKind::Daylight),
+            _ => Ok(ConfigKind::Other),
+        };
+        Ok(Self {
+            kind: kind.unwrap_or_default(),
+            ..serde_json::from_value(value)?
+        })
+    }
+}
+
+/// Configuration that is specific to the type of a sensor.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum ConfigKind {
+    /// Configuration of a motion sensor (`ZLLPresence`).
+    Presence(PresenceConfig),
+    /// Configuration of a temperature sensor (`ZLLTemperature`).
+    Temperature(TemperatureConfig),
+    /// Configuration of a light level sensor (`ZLLLightLevel`).
+    LightLevel(LightLevelConfig),
+    /// Configuration of the daylight sensor of the bridge (`Daylight`).
+    Daylight(DaylightConfig),
+    /// Sensor without specific configuration.
+    #[default]
+    Other,
+}
+
+/// Configuration of a motion sensor.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Deserialize)]
+pub struct PresenceConfig {
+    /// Sensitivity of the sensor.
+    pub sensitivity: Option<u8>,
+    /// Maximum sensitivity of the sensor.
+    #[serde(rename = "sensitivitymax")]
+    pub sensitivity_max: Option<u8>,
+    /// Whether the LED of the sensor indicates detected motion.
+    #[serde(rename = "ledindication")]
+    pub led_indication: Option<bool>,
+}
+
+/// Configuration of a temperature sensor.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Deserialize)]
+pub struct TemperatureConfig {
+    /// Whether the LED of the sensor indicates activity.
+    #[serde(rename = "ledindication")]
+    pub led_indication: Option<bool>,
+}
+
+/// Configuration of a light level sensor.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Deserialize)]
+pub struct LightLevelConfig {
+    /// Light level below which it is considered dark.
+    #[serde(rename = "tholddark")]
+    pub threshold_dark: Option<u16>,
+    /// Offset above the dark threshold from which on it is considered daylight.
+    #[serde(rename = "tholdoffset")]
+    pub threshold_offset: Option<u16>,
+    /// Whether the LED of the sensor indicates activity.
+    #[serde(rename = "ledindication")]
+    pub led_indication: Option<bool>,
+}
+
+/// Configuration of the daylight sensor of the bridge.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Deserialize)]
+pub struct DaylightConfig {
+    /// Whether the location of the bridge is configured.
+    pub configured: Option<bool>,
+    /// Offset in minutes to the sunrise.
+    #[serde(rename = "sunriseoffset")]
+    pub sunrise_offset: Option<i8>,
+    /// Offset in minutes to the sunset.
+    #[serde(rename = "sunsetoffset")]
+    pub sunset_offset: Option<i8>,
+}
+
+/// Struct for creating a sensor.
+///
+/// Only CLIP sensors can be created, which are virtual sensors whose state is set with the API.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Setters)]
+#[setters(strip_option, prefix = "with_")]
+pub struct Creator {
+    /// Sets the name of the sensor.
+    #[setters(skip)]
+    pub name: String,
+    /// Sets the type name of the sensor, e.g. `CLIPGenericStatus`.
+    #[setters(skip)]
+    #[serde(rename = "type")]
+    pub type_name: String,
+    /// Sets the model identifier of the sensor.
+    #[serde(rename = "modelid")]
+    pub model_id: String,
+    /// Sets the unique identifier of the sensor.
+    #[serde(rename = "uniqueid")]
+    pub unique_id: String,
+    /// Sets the manufacturer name of the sensor.
+    #[serde(rename = "manufacturername")]
+    pub manufacturer_name: String,
+    /// Sets the software version of the sensor.
+    #[serde(rename = "swversion")]
+    pub software_version: String,
+    /// Sets the initial state of the sensor.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<StateModifier>,
+    /// Sets whether the sensor is automatically deleted when not referenced anymore.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recycle: Option<bool>,
+}
+
+impl Creator {
+    /// Creates a new [`Creator`].
+    ///
+    /// The model identifier defaults to the type name and the unique identifier defaults to the
+    /// name of the sensor.
+    pub fn new<S, T>(name: S, type_name: T) -> Self
+    where
+        S: Into<String>,
+        T: Into<String>,
+    {
+        let name = name.into();
+        let type_name = type_name.into();
+        Self {
+            model_id: type_name.clone(),
+            unique_id: name.clone(),
+            manufacturer_name: "huelib2".to_owned(),
+            software_version: "1.0".to_owned(),
+            name,
+            type_name,
+            state: None,
+            recycle: None,
+        }
+    }
+
+    /// Creates a new [`Creator`] for a `CLIPGenericStatus` sensor.
+    ///
+    /// A generic status sensor holds an integer that can be used as variable by rules.
+    pub fn generic_status<S>(name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::new(name, "CLIPGenericStatus")
+    }
+
+    /// Creates a new [`Creator`] for a `CLIPGenericFlag` sensor.
+    ///
+    /// A generic flag sensor holds a boolean that can be used as variable by rules.
+    pub fn generic_flag<S>(name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::new(name, "CLIPGenericFlag")
+    }
+}
+
+impl resource::Creator for Creator {
+    fn url_suffix() -> String {
+        "sensors".to_owned()
+    }
 }
 
 /// Modifier for sensor attributes.
@@ -114,51 +971,275 @@ impl resource::Modifier for AttributeModifier {
 }
 
 /// Modifier for the sensor state.
+///
+/// Only the state of CLIP sensors can be modified, the state of ZLL sensors is read-only. Each
+/// variant sets the value that can be written for a type of CLIP sensor.
+///
+/// # Examples
+///
+/// Set the flag of a generic flag sensor:
+/// ```
+/// use huelib2::resource::sensor;
+///
+/// let modifier = sensor::StateModifier::Flag(true);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StateModifier {
+    /// Sets the presence of a presence sensor (`CLIPPresence`).
+    Presence(bool),
+    /// Sets the status of a generic status sensor (`CLIPGenericStatus`).
+    Status(i32),
+    /// Sets the flag of a generic flag sensor (`CLIPGenericFlag`).
+    Flag(bool),
+    /// Sets whether an open/close sensor is open (`CLIPOpenClose`).
+    Open(bool),
+    /// Sets the temperature of a temperature sensor in hundredths of a degree Celsius
+    /// (`CLIPTemperature`).
+    Temperature(i32),
+    /// Sets the relative humidity of a humidity sensor in hundredths of a percent
+    /// (`CLIPHumidity`).
+    Humidity(u16),
+    /// Sets the light level of a light level sensor, calculated as `10000 * log10(lux) + 1`
+    /// (`CLIPLightLevel`).
+    LightLevel(u32),
+    /// Sets the button event of a switch (`CLIPSwitch`).
+    ButtonEvent(u32),
+}
+
+impl resource::Modifier for StateModifier {
+    type Id = String;
+    fn url_suffix(id: Self::Id) -> String {
+        format!("sensors/{}/state", id)
+    }
+}
+
+/// Modifier for the sensor configuration.
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
-pub struct StateModifier {
-    /// Sets the presence of the sensor.
+pub struct ConfigModifier {
+    /// Sets whether the sensor is on.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub presence: Option<bool>,
+    pub on: Option<bool>,
 }
 
-impl StateModifier {
-    /// Creates a new [`StateModifier`].
+impl ConfigModifier {
+    /// Creates a new [`ConfigModifier`].
     pub fn new() -> Self {
         Self::default()
     }
 }
 
-impl resource::Modifier for StateModifier {
+impl resource::Modifier for ConfigModifier {
     type Id = String;
     fn url_suffix(id: Self::Id) -> String {
-        format!("sensors/{}/state", id)
+        format!("sensors/{}/config", id)
     }
 }
 
-/// Modifier for the sensor configuration.
+impl SensorConfigModifier for ConfigModifier {}
+
+/// Marker trait for modifiers of the sensor configuration.
+///
+/// Implemented by [`ConfigModifier`], which works for every sensor, and by the modifiers for the
+/// configuration of specific sensor types.
+pub trait SensorConfigModifier: resource::Modifier<Id = String> {}
+
+/// Modifier for the configuration of a motion sensor.
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
-pub struct ConfigModifier {
+pub struct PresenceConfigModifier {
     /// Sets whether the sensor is on.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub on: Option<bool>,
+    /// Sets the sensitivity of the sensor.
+    ///
+    /// The value must not be greater than the maximum sensitivity of the sensor.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sensitivity: Option<u8>,
+    /// Sets whether the LED of the sensor indicates detected motion.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "ledindication")]
+    pub led_indication: Option<bool>,
 }
 
-impl ConfigModifier {
-    /// Creates a new [`ConfigModifier`].
+impl PresenceConfigModifier {
+    /// Creates a new [`PresenceConfigModifier`].
     pub fn new() -> Self {
         Self::default()
     }
 }
 
-impl resource::Modifier for ConfigModifier {
+impl resource::Modifier for PresenceConfigModifier {
     type Id = String;
     fn url_suffix(id: Self::Id) -> String {
         format!("sensors/{}/config", id)
     }
 }
 
+impl SensorConfigModifier for PresenceConfigModifier {}
+
+/// Modifier for the configuration of a temperature sensor.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Setters)]
+#[setters(strip_option, prefix = "with_")]
+pub struct TemperatureConfigModifier {
+    /// Sets whether the sensor is on.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on: Option<bool>,
+    /// Sets whether the LED of the sensor indicates activity.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "ledindication")]
+    pub led_indication: Option<bool>,
+}
+
+impl TemperatureConfigModifier {
+    /// Creates a new [`TemperatureConfigModifier`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl resource::Modifier for TemperatureConfigModifier {
+    type Id = String;
+    fn url_suffix(id: Self::Id) -> String {
+        format!("sensors/{}/config", id)
+    }
+}
+
+impl SensorConfigModifier for TemperatureConfigModifier {}
+
+/// Modifier for the configuration of a light level sensor.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Setters)]
+#[setters(strip_option, prefix = "with_")]
+pub struct LightLevelConfigModifier {
+    /// Sets whether the sensor is on.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on: Option<bool>,
+    /// Sets the light level below which it is considered dark.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "tholddark")]
+    pub threshold_dark: Option<u16>,
+    /// Sets the offset above the dark threshold from which on it is considered daylight.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "tholdoffset")]
+    pub threshold_offset: Option<u16>,
+    /// Sets whether the LED of the sensor indicates activity.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "ledindication")]
+    pub led_indication: Option<bool>,
+}
+
+impl LightLevelConfigModifier {
+    /// Creates a new [`LightLevelConfigModifier`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl resource::Modifier for LightLevelConfigModifier {
+    type Id = String;
+    fn url_suffix(id: Self::Id) -> String {
+        format!("sensors/{}/config", id)
+    }
+}
+
+impl SensorConfigModifier for LightLevelConfigModifier {}
+
+/// Modifier for the configuration of the daylight sensor of the bridge.
+///
+/// # Examples
+///
+/// Set the location of the bridge to Amsterdam and trigger the sunset 30 minutes early:
+/// ```
+/// use huelib2::resource::sensor;
+///
+/// let modifier = sensor::DaylightConfigModifier::new()
+///     .with_coordinates(52.3702, 4.8952)
+///     .with_sunset_offset(-30);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Setters)]
+#[setters(strip_option, prefix = "with_")]
+pub struct DaylightConfigModifier {
+    /// Sets whether the sensor is on.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on: Option<bool>,
+    /// Sets the longitude of the bridge in degrees, ranging from -180 (west) to 180 (east).
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "long",
+        serialize_with = "serialize_longitude"
+    )]
+    pub longitude: Option<f64>,
+    /// Sets the latitude of the bridge in degrees, ranging from -90 (south) to 90 (north).
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "lat",
+        serialize_with = "serialize_latitude"
+    )]
+    pub latitude: Option<f64>,
+    /// Sets the offset in minutes to the sunrise, ranging from -120 to 120.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "sunriseoffset")]
+    pub sunrise_offset: Option<i8>,
+    /// Sets the offset in minutes to the sunset, ranging from -120 to 120.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "sunsetoffset")]
+    pub sunset_offset: Option<i8>,
+}
+
+impl DaylightConfigModifier {
+    /// Creates a new [`DaylightConfigModifier`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the latitude and the longitude of the bridge in degrees.
+    pub fn with_coordinates(self, latitude: f64, longitude: f64) -> Self {
+        self.with_latitude(latitude).with_longitude(longitude)
+    }
+}
+
+/// Serializes a latitude in the format of the bridge, e.g. `052.3702N`.
+fn serialize_latitude<S>(value: &Option<f64>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serialize_coordinate(value, 90.0, ('N', 'S'), serializer)
+}
+
+/// Serializes a longitude in the format of the bridge, e.g. `004.8952E`.
+fn serialize_longitude<S>(value: &Option<f64>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serialize_coordinate(value, 180.0, ('E', 'W'), serializer)
+}
+
+fn serialize_coordinate<S>(
+    value: &Option<f64>,
+    max: f64,
+    (positive, negative): (char, char),
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let value = match value {
+        Some(v) => *v,
+        None => return serializer.serialize_none(),
+    };
+    if !(-max..=max).contains(&value) {
+        return Err(S::Error::custom(format!(
+            "coordinate {} is outside of the range from {} to {}",
+            value, -max, max
+        )));
+    }
+    let direction = if value < 0.0 { negative } else { positive };
+    serializer.serialize_str(&format!("{:08.4}{}", value.abs(), direction))
+}
+
+impl resource::Modifier for DaylightConfigModifier {
+    type Id = String;
+    fn url_suffix(id: Self::Id) -> String {
+        format!("sensors/{}/config", id)
+    }
+}
+
+impl SensorConfigModifier for DaylightConfigModifier {}
+
 /// Scanner for new lights.
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
@@ -186,6 +1267,335 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn construct_sensor() {
+        let sensor = Sensor {
+            id: String::new(),
+            name: "test".into(),
+            type_name: "CLIPPresence".into(),
+            model_id: "PRESENCE".into(),
+            unique_id: None,
+            manufacturer_name: None,
+            product_name: None,
+            diversity_id: None,
+            software_version: None,
+            state: State::Presence(PresenceState {
+                presence: Some(true),
+                ..Default::default()
+            }),
+            config: Config::default(),
+            recycle: None,
+        }
+        .with_id("1");
+        assert_eq!(sensor.id, "1");
+        assert_eq!(
+            sensor.state,
+            State::Presence(PresenceState {
+                presence: Some(true),
+                last_updated: None
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_sensor() {
+        let sensor_json = json!({
+            "name": "Hallway temperature",
+            "type": "ZLLTemperature",
+            "modelid": "SML001",
+            "state": {"temperature": 2134, "lastupdated": "2020-01-01T12:00:00"},
+            "config": {"on": true, "reachable": true, "battery": 100}
+        });
+        let sensor: Sensor = serde_json::from_value(sensor_json).unwrap();
+        assert_eq!(
+            sensor.state,
+            State::Temperature(TemperatureState {
+                temperature: Some(2134),
+                last_updated: Some(
+                    chrono::NaiveDate::from_ymd_opt(2020, 1, 1)
+                        .unwrap()
+                        .and_hms_opt(12, 0, 0)
+                        .unwrap()
+                ),
+            })
+        );
+        assert_eq!(
+            sensor.state.last_updated_utc().unwrap().to_rfc3339(),
+            "2020-01-01T12:00:00+00:00"
+        );
+
+        let state = State::from_type("ZLLSwitch", json!({"buttonevent": 1002}));
+        assert_eq!(
+            state,
+            State::Switch(SwitchState {
+                button_event: Some(1002),
+                last_updated: None
+            })
+        );
+        match state {
+            State::Switch(v) => assert_eq!(
+                v.event(),
+                Some(ButtonEvent::new(1, ButtonAction::ShortRelease))
+            ),
+            _ => unreachable!(),
+        }
+        let state = State::from_type(
+            "ZLLRelativeRotary",
+            json!({
+                "rotaryevent": 2,
+                "expectedrotation": -45,
+                "expectedeventduration": 400,
+                "lastupdated": "2021-05-01T10:00:00"
+            }),
+        );
+        match state {
+            State::Rotary(v) => assert_eq!(
+                v.event(),
+                Some(RotaryEvent {
+                    action: RotaryAction::Repeat,
+                    rotation: -45,
+                    duration: Duration::from_millis(400)
+                })
+            ),
+            _ => unreachable!(),
+        }
+        let state = State::from_type("CLIPGenericStatus", json!({"status": 3}));
+        assert!(matches!(state, State::GenericStatus(v) if v.status == Some(3)));
+        let state = State::from_type("Geofence", json!({"presence": true}));
+        assert_eq!(state, State::Other(json!({"presence": true})));
+        let state = State::from_type("ZLLPresence", json!({"presence": "yes"}));
+        assert_eq!(state, State::Other(json!({"presence": "yes"})));
+    }
+
+    /// Returns a sensor with the given attributes.
+    fn sensor(
+        id: &str,
+        type_name: &str,
+        unique_id: &str,
+        state: JsonValue,
+        battery: Option<u8>,
+    ) -> Sensor {
+        serde_json::from_value::<Sensor>(json!({
+            "name": format!("Sensor {}", id),
+            "type": type_name,
+            "modelid": "SML001",
+            "uniqueid": unique_id,
+            "state": state,
+            "config": {"on": true, "battery": battery}
+        }))
+        .unwrap()
+        .with_id(id)
+    }
+
+    #[test]
+    fn motion_sensor_device() {
+        let sensors = vec![
+            sensor(
+                "5",
+                "ZLLTemperature",
+                "00:17:88:01:02:00:af:1b-02-0402",
+                json!({"temperature": 2150}),
+                Some(90),
+            ),
+            sensor(
+                "4",
+                "ZLLPresence",
+                "00:17:88:01:02:00:af:1b-02-0406",
+                json!({"presence": true}),
+                Some(90),
+            ),
+            sensor(
+                "6",
+                "ZLLLightLevel",
+                "00:17:88:01:02:00:af:1b-02-0400",
+                json!({"lightlevel": 10001}),
+                Some(90),
+            ),
+            sensor(
+                "7",
+                "ZLLSwitch",
+                "00:17:88:01:10:5c:2e:7a-02-fc00",
+                json!({"buttonevent": 1002}),
+                Some(90),
+            ),
+            sensor("8", "CLIPGenericFlag", "flag", json!({"flag": true}), None),
+            sensor(
+                "9",
+                "CLIPPresence",
+                "my-app-presence-1",
+                json!({"presence": false}),
+                None,
+            ),
+            sensor(
+                "10",
+                "CLIPPresence",
+                "my-app-presence-2",
+                json!({"presence": false}),
+                None,
+            ),
+            sensor(
+                "12",
+                "ZLLPresence",
+                "00:17:88:01:02:00:b0:2c-02-0406",
+                json!({"presence": false}),
+                Some(50),
+            ),
+        ];
+        assert_eq!(sensors[0].mac_address(), Some("00:17:88:01:02:00:af:1b"));
+        assert_eq!(sensors[4].mac_address(), None);
+        assert_eq!(sensors[5].mac_address(), None);
+        assert_eq!(group_by_device(sensors.clone()).len(), 3);
+
+        let devices = MotionSensorDevice::from_sensors(sensors);
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[1].presence_sensor.id, "12");
+        let device = &devices[0];
+        assert_eq!(device.mac_address, "00:17:88:01:02:00:af:1b");
+        assert_eq!(device.presence_sensor.id, "4");
+        assert_eq!(device.presence(), Some(true));
+        assert_eq!(device.temperature(), Some(21.5));
+        assert_eq!(device.lux(), Some(10.0));
+        assert_eq!(device.battery(), Some(90));
+    }
+
+    #[test]
+    fn battery_levels() {
+        let sensors = vec![
+            sensor(
+                "2",
+                "ZLLTemperature",
+                "00:17:88:01:02:00:af:1b-02-0402",
+                json!({}),
+                Some(40),
+            ),
+            sensor(
+                "3",
+                "ZLLPresence",
+                "00:17:88:01:02:00:af:1b-02-0406",
+                json!({}),
+                Some(40),
+            ),
+            sensor(
+                "4",
+                "ZLLSwitch",
+                "00:17:88:01:10:5c:2e:7a-02-fc00",
+                json!({}),
+                Some(85),
+            ),
+            // A second switch with the same name is reported separately.
+            Sensor {
+                name: "Sensor 4".into(),
+                ..sensor(
+                    "6",
+                    "ZLLSwitch",
+                    "00:17:88:01:10:5c:2e:7b-02-fc00",
+                    json!({}),
+                    Some(60),
+                )
+            },
+            sensor(
+                "5",
+                "ZGPSwitch",
+                "00:00:00:00:00:43:51:6e-f2",
+                json!({}),
+                None,
+            ),
+        ];
+        let levels = super::battery_levels(sensors);
+        let mut expected = HashMap::new();
+        expected.insert(
+            "00:17:88:01:02:00:af:1b".to_owned(),
+            ("Sensor 3".to_owned(), 40),
+        );
+        expected.insert(
+            "00:17:88:01:10:5c:2e:7a".to_owned(),
+            ("Sensor 4".to_owned(), 85),
+        );
+        expected.insert(
+            "00:17:88:01:10:5c:2e:7b".to_owned(),
+            ("Sensor 4".to_owned(), 60),
+        );
+        assert_eq!(levels, expected);
+    }
+
+    #[test]
+    fn kind() {
+        assert_eq!(Kind::from_type_name("ZLLSwitch"), Some(Kind::ZllSwitch));
+        assert_eq!(Kind::from_type_name("Unknown"), None);
+        assert_eq!(Kind::ClipGenericFlag.type_name(), "CLIPGenericFlag");
+        assert!(Kind::ClipGenericFlag.is_clip());
+        assert!(!Kind::ZllPresence.is_clip());
+    }
+
+    #[test]
+    fn button_event() {
+        let event = ButtonEvent::from_raw(2001).unwrap();
+        assert_eq!(event, ButtonEvent::new(2, ButtonAction::Hold));
+        assert_eq!(event.value(), 2001);
+        assert_eq!(
+            u32::from(ButtonEvent::new(4, ButtonAction::LongRelease)),
+            4003
+        );
+        assert_eq!(ButtonEvent::from_raw(1004), None);
+        assert_eq!(ButtonEvent::from_raw(2), None);
+
+        assert_eq!(
+            ButtonEvent::from_raw_zgp("ZGPSWITCH", 34),
+            Some(ButtonEvent::new(1, ButtonAction::InitialPress))
+        );
+        assert_eq!(
+            ButtonEvent::from_raw_zgp("ZGPSWITCH", 18),
+            Some(ButtonEvent::new(4, ButtonAction::InitialPress))
+        );
+        assert_eq!(
+            ButtonEvent::from_raw_zgp("FOHSWITCH", 16),
+            Some(ButtonEvent::new(1, ButtonAction::InitialPress))
+        );
+        assert_eq!(
+            ButtonEvent::from_raw_zgp("FOHSWITCH", 22),
+            Some(ButtonEvent::new(3, ButtonAction::ShortRelease))
+        );
+        assert_eq!(ButtonEvent::from_raw_zgp("FOHSWITCH", 98), None);
+        assert_eq!(ButtonEvent::from_raw_zgp("ZGPSWITCH", 20), None);
+
+        let tap: Sensor = serde_json::from_value(json!({
+            "name": "Tap",
+            "type": "ZGPSwitch",
+            "modelid": "ZGPSWITCH",
+            "state": {"buttonevent": 17},
+            "config": {"on": true}
+        }))
+        .unwrap();
+        assert_eq!(
+            tap.button_event(),
+            Some(ButtonEvent::new(3, ButtonAction::InitialPress))
+        );
+    }
+
+    #[test]
+    fn serialize_creator() {
+        let creator = Creator::generic_status("Mode")
+            .with_state(StateModifier::Status(0))
+            .with_recycle(true);
+        let creator_json = serde_json::to_value(creator).unwrap();
+        let expected_json = json!({
+            "name": "Mode",
+            "type": "CLIPGenericStatus",
+            "modelid": "CLIPGenericStatus",
+            "uniqueid": "Mode",
+            "manufacturername": "huelib2",
+            "swversion": "1.0",
+            "state": {"status": 0},
+            "recycle": true
+        });
+        assert_eq!(creator_json, expected_json);
+
+        let creator = Creator::generic_flag("Away").with_unique_id("away-flag".into());
+        let creator_json = serde_json::to_value(creator).unwrap();
+        assert_eq!(creator_json["type"], "CLIPGenericFlag");
+        assert_eq!(creator_json["uniqueid"], "away-flag");
+    }
+
     #[test]
     fn serialize_attribute_modifier() {
         let modifier = AttributeModifier::new();
@@ -203,17 +1613,40 @@ mod tests {
 
     #[test]
     fn serialize_state_modifier() {
-        let modifier = StateModifier::new();
+        let modifier = StateModifier::Presence(true);
         let modifier_json = serde_json::to_value(modifier).unwrap();
-        let expected_json = json!({});
+        let expected_json = json!({"presence": true});
         assert_eq!(modifier_json, expected_json);
 
-        let modifier = StateModifier {
-            presence: Some(true),
-        };
+        let modifier = StateModifier::Status(-1);
         let modifier_json = serde_json::to_value(modifier).unwrap();
-        let expected_json = json!({"presence": true});
+        let expected_json = json!({"status": -1});
         assert_eq!(modifier_json, expected_json);
+
+        let modifier = StateModifier::Flag(false);
+        let modifier_json = serde_json::to_value(modifier).unwrap();
+        let expected_json = json!({"flag": false});
+        assert_eq!(modifier_json, expected_json);
+
+        let modifiers = vec![
+            (StateModifier::Open(true), json!({"open": true})),
+            (
+                StateModifier::Temperature(-250),
+                json!({"temperature": -250}),
+            ),
+            (StateModifier::Humidity(4550), json!({"humidity": 4550})),
+            (
+                StateModifier::LightLevel(10001),
+                json!({"lightlevel": 10001}),
+            ),
+            (
+                StateModifier::ButtonEvent(1002),
+                json!({"buttonevent": 1002}),
+            ),
+        ];
+        for (modifier, expected_json) in modifiers {
+            assert_eq!(serde_json::to_value(modifier).unwrap(), expected_json);
+        }
     }
 
     #[test]
@@ -227,6 +1660,72 @@ mod tests {
         let modifier_json = serde_json::to_value(modifier).unwrap();
         let expected_json = json!({"on": true});
         assert_eq!(modifier_json, expected_json);
+
+        let modifier = PresenceConfigModifier::new()
+            .with_sensitivity(2)
+            .with_led_indication(false);
+        let modifier_json = serde_json::to_value(modifier).unwrap();
+        let expected_json = json!({"sensitivity": 2, "ledindication": false});
+        assert_eq!(modifier_json, expected_json);
+
+        let modifier = LightLevelConfigModifier::new()
+            .with_threshold_dark(16000)
+            .with_threshold_offset(7000);
+        let modifier_json = serde_json::to_value(modifier).unwrap();
+        let expected_json = json!({"tholddark": 16000, "tholdoffset": 7000});
+        assert_eq!(modifier_json, expected_json);
+
+        let modifier = DaylightConfigModifier::new()
+            .with_coordinates(52.3702, 4.8952)
+            .with_sunset_offset(-30);
+        let modifier_json = serde_json::to_value(modifier).unwrap();
+        let expected_json = json!({"long": "004.8952E", "lat": "052.3702N", "sunsetoffset": -30});
+        assert_eq!(modifier_json, expected_json);
+
+        let modifier = DaylightConfigModifier::new().with_coordinates(-33.8688, -151.2093);
+        let modifier_json = serde_json::to_value(modifier).unwrap();
+        let expected_json = json!({"long": "151.2093W", "lat": "033.8688S"});
+        assert_eq!(modifier_json, expected_json);
+
+        let modifier = DaylightConfigModifier::new().with_latitude(91.0);
+        assert!(serde_json::to_value(modifier).is_err());
+    }
+
+    #[test]
+    fn deserialize_config() {
+        let config_json = json!({
+            "on": true,
+            "battery": 80,
+            "reachable": true,
+            "sensitivity": 2,
+            "sensitivitymax": 2,
+            "ledindication": false
+        });
+        let config = Config::from_type("ZLLPresence", config_json.clone()).unwrap();
+        assert_eq!(config.battery, Some(80));
+        assert_eq!(
+            config.kind,
+            ConfigKind::Presence(PresenceConfig {
+                sensitivity: Some(2),
+                sensitivity_max: Some(2),
+                led_indication: Some(false)
+            })
+        );
+        let config = Config::from_type("CLIPPresence", config_json).unwrap();
+        assert_eq!(config.kind, ConfigKind::Other);
+
+        let config_json = json!({"on": true, "configured": true, "sunriseoffset": 30});
+        let config = Config::from_type("Daylight", config_json).unwrap();
+        assert_eq!(
+            config.kind,
+            ConfigKind::Daylight(DaylightConfig {
+                configured: Some(true),
+                sunrise_offset: Some(30),
+                sunset_offset: None
+            })
+        );
+
+        assert!(Config::from_type("ZLLPresence", json!({"reachable": true})).is_err());
     }
 
     #[test]
@@ -1,6 +1,6 @@
+use crate::resource::RequestMethod;
 use serde::{de, Deserialize};
 use serde_json::Value as JsonValue;
-use serde_repr::Deserialize_repr;
 use std::fmt;
 use thiserror::Error as ThisError;
 
@@ -24,6 +24,16 @@ impl<T> Response<T> {
     }
 }
 
+impl<T> Response<T> {
+    /// Attaches the resource that was accessed by the request to an error response.
+    pub(crate) fn with_resource(self, resource: Option<&ResourceContext>) -> Self {
+        match self {
+            Self::Error(e) => Self::Error(e.with_resource(resource)),
+            v => v,
+        }
+    }
+}
+
 impl<T: fmt::Display> fmt::Display for Response<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -48,54 +58,221 @@ pub struct Error {
     pub address: String,
     /// Description of the error.
     pub description: String,
+    /// Resource that was accessed by the request that failed.
+    ///
+    /// This is `None` if the request targeted the whole bridge or if the error was not returned
+    /// for a request sent by this crate.
+    #[serde(skip)]
+    pub resource: Option<Box<ResourceContext>>,
+}
+
+impl Error {
+    /// Sets the resource of the error, unless it is already set.
+    pub(crate) fn with_resource(self, resource: Option<&ResourceContext>) -> Self {
+        Self {
+            resource: self.resource.or_else(|| resource.cloned().map(Box::new)),
+            ..self
+        }
+    }
+}
+
+/// Resource that was accessed by a request to the Philips Hue API.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ResourceContext {
+    /// Kind of the resource, e.g. `lights`.
+    pub kind: String,
+    /// Identifier of the resource, if a single resource was accessed.
+    pub id: Option<String>,
+    /// HTTP method of the request.
+    pub method: RequestMethod,
+    /// Path of the request relative to the API URL of the user, e.g. `lights/1/state`.
+    pub endpoint: String,
+}
+
+impl ResourceContext {
+    /// Creates the context of a request.
+    ///
+    /// Returns `None` if the request targets the whole bridge. The configuration has no
+    /// identifier.
+    pub(crate) fn new(method: RequestMethod, url_suffix: &str) -> Option<Self> {
+        let mut segments = url_suffix.split('/');
+        let kind = match segments.next() {
+            Some(v) if !v.is_empty() => v.to_owned(),
+            _ => return None,
+        };
+        let id = match kind.as_str() {
+            "config" => None,
+            _ => segments.next().map(ToOwned::to_owned),
+        };
+        Some(Self {
+            kind,
+            id,
+            method,
+            endpoint: url_suffix.to_owned(),
+        })
+    }
+}
+
+impl fmt::Display for ResourceContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({:?} {})", self.kind, self.method, self.endpoint)
+    }
 }
 
 /// Kind of an error from a response.
-#[allow(missing_docs)]
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize_repr)]
+///
+/// The kind is identified by the numeric error type returned by the Philips Hue API. Error types
+/// that are not known by this crate are deserialized as [`UnkownError`].
+///
+/// [`UnkownError`]: Self::UnkownError
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 #[repr(u16)]
 pub enum ErrorKind {
+    /// The user is not authorized to access the resource. (`1`)
     UnauthorizedUser = 1,
+    /// The body of the request contains invalid JSON. (`2`)
     BodyContainsInvalidJson = 2,
+    /// The resource does not exist. (`3`)
     ResourceNotAvailable = 3,
+    /// The HTTP method is not available for the resource. (`4`)
     MethodNotAvailableForResource = 4,
+    /// Required parameters are missing in the body of the request. (`5`)
     MissingParametersInBody = 5,
+    /// A parameter in the body is not available for the resource. (`6`)
     ParameterNotAvailable = 6,
+    /// A parameter in the body has an invalid value. (`7`)
     InvalidValueForParameter = 7,
+    /// A parameter in the body is read-only. (`8`)
     ParameterIsNotModifiable = 8,
+    /// A list in the body contains too many items. (`11`)
     TooManyItemsInList = 11,
+    /// The bridge must be connected to the portal. (`12`)
     PortalConnectionRequired = 12,
+    /// The link button of the bridge was not pressed. (`101`)
     LinkButtonNotPressed = 101,
+    /// DHCP cannot be disabled on this bridge. (`110`)
     DhcpCannotBeDisabled = 110,
+    /// The update state cannot be set to the given value. (`111`)
     InvalidUpdateState = 111,
+    /// The state of the device cannot be modified because it is turned off. (`201`)
     DeviceIsSetToOff = 201,
+    /// The list of lights to commission is full. (`203`)
     CommissionableLightListIsFull = 203,
+    /// No more groups can be created. (`301`)
     GroupTableIsFull = 301,
+    /// Groups of this type cannot be modified or deleted. (`305`)
     UpdateOrDeleteGroupOfThisTypeNotAllowed = 305,
+    /// The light is already part of another room. (`306`)
     LightAlreadyUsedInAnotherRoom = 306,
+    /// No more scenes can be created. (`402`)
     SceneCouldNotBeCreatedBufferIsFull = 402,
+    /// The scene could not be deleted. (`403`)
     SceneCouldNotBeRemoved = 403,
+    /// The scene could not be created because the group has no lights. (`404`)
     SceneCouldNotBeCreatedGroupIsEmpty = 404,
+    /// Sensors of this type cannot be created. (`501`)
     NotAllowedToCreateSensorType = 501,
+    /// No more sensors can be created. (`502`)
     SensorListIsFull = 502,
+    /// The list of sensors to commission is full. (`503`)
     CommissionableSensorListIsFull = 503,
+    /// No more rules can be created. (`601`)
     RuleEngineFull = 601,
+    /// A condition of the rule is invalid. (`607`)
     ConditionError = 607,
+    /// An action of the rule or schedule is invalid. (`608`)
     ActionError = 608,
+    /// The rule could not be activated. (`609`)
     UnableToActivate = 609,
+    /// No more schedules can be created. (`701`)
     ScheduleListIsFull = 701,
+    /// The time zone of the schedule is invalid. (`702`)
     ScheduleTimezoneNotValid = 702,
+    /// The time and the local time of a schedule cannot be set at the same time. (`703`)
     ScheduleCannotSetTimeAndLocalTime = 703,
+    /// The schedule could not be created. (`704`)
     CannotCreateSchedule = 704,
+    /// The schedule cannot be enabled because its time is in the past. (`705`)
     CannotEnableScheduleTimeInPast = 705,
+    /// The command of the schedule is invalid. (`706`)
     CommandError = 706,
+    /// The model of the source is invalid. (`801`)
     SourceModelInvalid = 801,
+    /// The source is factory new. (`802`)
     SourceFactoryNew = 802,
+    /// The state is invalid. (`803`)
     InvalidState = 803,
+    /// An internal error occurred on the bridge. (`901`)
     InternalError = 901,
+    /// An error type that is not known by this crate.
     UnkownError,
 }
 
+impl ErrorKind {
+    /// Returns the error kind for the numeric error type.
+    ///
+    /// Returns [`UnkownError`] if the error type is not known by this crate.
+    ///
+    /// [`UnkownError`]: Self::UnkownError
+    pub fn from_code(code: u16) -> Self {
+        match code {
+            1 => Self::UnauthorizedUser,
+            2 => Self::BodyContainsInvalidJson,
+            3 => Self::ResourceNotAvailable,
+            4 => Self::MethodNotAvailableForResource,
+            5 => Self::MissingParametersInBody,
+            6 => Self::ParameterNotAvailable,
+            7 => Self::InvalidValueForParameter,
+            8 => Self::ParameterIsNotModifiable,
+            11 => Self::TooManyItemsInList,
+            12 => Self::PortalConnectionRequired,
+            101 => Self::LinkButtonNotPressed,
+            110 => Self::DhcpCannotBeDisabled,
+            111 => Self::InvalidUpdateState,
+            201 => Self::DeviceIsSetToOff,
+            203 => Self::CommissionableLightListIsFull,
+            301 => Self::GroupTableIsFull,
+            305 => Self::UpdateOrDeleteGroupOfThisTypeNotAllowed,
+            306 => Self::LightAlreadyUsedInAnotherRoom,
+            402 => Self::SceneCouldNotBeCreatedBufferIsFull,
+            403 => Self::SceneCouldNotBeRemoved,
+            404 => Self::SceneCouldNotBeCreatedGroupIsEmpty,
+            501 => Self::NotAllowedToCreateSensorType,
+            502 => Self::SensorListIsFull,
+            503 => Self::CommissionableSensorListIsFull,
+            601 => Self::RuleEngineFull,
+            607 => Self::ConditionError,
+            608 => Self::ActionError,
+            609 => Self::UnableToActivate,
+            701 => Self::ScheduleListIsFull,
+            702 => Self::ScheduleTimezoneNotValid,
+            703 => Self::ScheduleCannotSetTimeAndLocalTime,
+            704 => Self::CannotCreateSchedule,
+            705 => Self::CannotEnableScheduleTimeInPast,
+            706 => Self::CommandError,
+            801 => Self::SourceModelInvalid,
+            802 => Self::SourceFactoryNew,
+            803 => Self::InvalidState,
+            901 => Self::InternalError,
+            _ => Self::UnkownError,
+        }
+    }
+
+    /// Returns the numeric error type, or `None` if the error type is not known by this crate.
+    pub fn code(self) -> Option<u16> {
+        match self {
+            Self::UnkownError => None,
+            v => Some(v as u16),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ErrorKind {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u16::deserialize(deserializer).map(Self::from_code)
+    }
+}
+
 /// A response type that is used when modifying a resource.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Modified {
@@ -169,10 +346,35 @@ mod tests {
             kind: ErrorKind::UnauthorizedUser,
             address: "/address/123".to_owned(),
             description: "description test".to_owned(),
+            resource: None,
         };
         assert_eq!(response, Response::Error(error));
     }
 
+    #[test]
+    fn deserialize_error_kind() {
+        let kind: ErrorKind = serde_json::from_value(json!(101)).unwrap();
+        assert_eq!(kind, ErrorKind::LinkButtonNotPressed);
+        assert_eq!(kind.code(), Some(101));
+
+        let kind: ErrorKind = serde_json::from_value(json!(1234)).unwrap();
+        assert_eq!(kind, ErrorKind::UnkownError);
+        assert_eq!(kind.code(), None);
+    }
+
+    #[test]
+    fn resource_context() {
+        let context = ResourceContext::new(RequestMethod::Put, "lights/1/state").unwrap();
+        assert_eq!(context.kind, "lights");
+        assert_eq!(context.id.as_deref(), Some("1"));
+        assert_eq!(context.endpoint, "lights/1/state");
+        assert_eq!(context.to_string(), "lights (Put lights/1/state)");
+
+        let context = ResourceContext::new(RequestMethod::Get, "config/whitelist").unwrap();
+        assert_eq!(context.id, None);
+        assert_eq!(ResourceContext::new(RequestMethod::Get, ""), None);
+    }
+
     #[test]
     fn deserialize_response_modifier() {
         let json = json!({
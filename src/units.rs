@@ -0,0 +1,343 @@
+use crate::resource::Adjust;
+use std::convert::TryFrom;
+use thiserror::Error as ThisError;
+
+/// Brightness of a light, ranging from 1 (minimum) to 254 (maximum).
+///
+/// # Examples
+///
+/// Set the brightness of a light to 50%:
+/// ```
+/// use huelib2::{resource::light, units::Brightness};
+///
+/// let modifier = light::StateModifier::new().with_brightness(Brightness::from_percent(50.0));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Brightness(u8);
+
+impl Brightness {
+    /// Minimum brightness.
+    pub const MIN: Self = Self(1);
+    /// Maximum brightness.
+    pub const MAX: Self = Self(254);
+
+    /// Creates a new brightness.
+    pub fn new(value: u8) -> Result<Self, OutOfRangeError> {
+        check_range(value, Self::MIN.0, Self::MAX.0).map(Self)
+    }
+
+    /// Creates a new brightness, clamping the value into the valid range.
+    pub(crate) fn clamped(value: u8) -> Self {
+        Self(value.clamp(Self::MIN.0, Self::MAX.0))
+    }
+
+    /// Creates a new brightness from a percentage, which is clamped between 0 and 100.
+    pub fn from_percent(value: f32) -> Self {
+        Self(scale_percent(value, Self::MIN.0 as f32, Self::MAX.0 as f32) as u8)
+    }
+
+    /// Returns the raw value that is used by the bridge.
+    pub fn value(self) -> u8 {
+        self.0
+    }
+}
+
+/// Saturation of a light, ranging from 0 (white) to 254 (most saturated).
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Saturation(u8);
+
+impl Saturation {
+    /// Minimum saturation.
+    pub const MIN: Self = Self(0);
+    /// Maximum saturation.
+    pub const MAX: Self = Self(254);
+
+    /// Creates a new saturation.
+    pub fn new(value: u8) -> Result<Self, OutOfRangeError> {
+        check_range(value, Self::MIN.0, Self::MAX.0).map(Self)
+    }
+
+    /// Creates a new saturation, clamping the value into the valid range.
+    pub(crate) fn clamped(value: u8) -> Self {
+        Self(value.clamp(Self::MIN.0, Self::MAX.0))
+    }
+
+    /// Creates a new saturation from a percentage, which is clamped between 0 and 100.
+    pub fn from_percent(value: f32) -> Self {
+        Self(scale_percent(value, Self::MIN.0 as f32, Self::MAX.0 as f32) as u8)
+    }
+
+    /// Returns the raw value that is used by the bridge.
+    pub fn value(self) -> u8 {
+        self.0
+    }
+}
+
+/// Hue of a light in degrees on the color wheel.
+///
+/// The bridge represents the hue as value from 0 to 65535, where both 0 and 65535 are red, 21845
+/// is green and 43690 is blue.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct HueDegrees(f32);
+
+impl HueDegrees {
+    /// Creates a new hue from degrees.
+    ///
+    /// The value is wrapped into the range from 0 to 360, e.g. -90° becomes 270°. Returns an
+    /// error if the value is not finite.
+    pub fn new(value: f32) -> Result<Self, OutOfRangeError> {
+        if !value.is_finite() {
+            return Err(OutOfRangeError {
+                min: 0,
+                max: 360,
+                value: None,
+            });
+        }
+        Ok(Self(value.rem_euclid(360.0)))
+    }
+
+    /// Creates a new hue from the raw value that is used by the bridge.
+    pub fn from_raw(value: u16) -> Self {
+        Self(value as f32 / 65536.0 * 360.0)
+    }
+
+    /// Returns the hue in degrees.
+    pub fn degrees(self) -> f32 {
+        self.0
+    }
+
+    /// Returns the raw value that is used by the bridge.
+    pub fn value(self) -> u16 {
+        ((self.0 / 360.0 * 65536.0).round() as u32 % 65536) as u16
+    }
+}
+
+/// Color temperature in mired (micro reciprocal degree), ranging from 153 (6500K) to 500
+/// (2000K).
+///
+/// This is the unit that is used by the bridge. See [`Kelvin`] for the color temperature in
+/// kelvin.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Mired(u16);
+
+impl Mired {
+    /// Minimum color temperature in mired (coldest).
+    pub const MIN: Self = Self(153);
+    /// Maximum color temperature in mired (warmest).
+    pub const MAX: Self = Self(500);
+
+    /// Creates a new color temperature in mired.
+    pub fn new(value: u16) -> Result<Self, OutOfRangeError> {
+        check_range(value, Self::MIN.0, Self::MAX.0).map(Self)
+    }
+
+    /// Creates a new color temperature in mired, clamping the value into the valid range.
+    pub(crate) fn clamped(value: u16) -> Self {
+        Self(value.clamp(Self::MIN.0, Self::MAX.0))
+    }
+
+    /// Creates a new color temperature in mired from a color temperature in kelvin.
+    ///
+    /// Returns an error if the converted value is outside of the range from 153 to 500 mired.
+    pub fn from_kelvin(kelvin: u32) -> Result<Self, OutOfRangeError> {
+        let mired = (1_000_000.0 / kelvin as f64).round().min(u32::MAX as f64) as u32;
+        if mired < Self::MIN.0 as u32 || mired > Self::MAX.0 as u32 {
+            return Err(OutOfRangeError {
+                min: Self::MIN.0 as u32,
+                max: Self::MAX.0 as u32,
+                value: Some(mired),
+            });
+        }
+        Ok(Self(mired as u16))
+    }
+
+    /// Returns the raw value that is used by the bridge.
+    pub fn value(self) -> u16 {
+        self.0
+    }
+}
+
+/// Color temperature in kelvin, ranging from 2000 to 6536.
+///
+/// # Examples
+///
+/// Set the color temperature of a light to 2700K:
+/// ```
+/// # fn main() -> Result<(), huelib2::units::OutOfRangeError> {
+/// use huelib2::{resource::light, units::Kelvin};
+///
+/// let modifier = light::StateModifier::new().with_color_temperature(Kelvin::new(2700)?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Kelvin(u16);
+
+impl Kelvin {
+    /// Minimum color temperature in kelvin (warmest).
+    pub const MIN: Self = Self(2000);
+    /// Maximum color temperature in kelvin (coldest).
+    pub const MAX: Self = Self(6536);
+
+    /// Creates a new color temperature in kelvin.
+    pub fn new(value: u16) -> Result<Self, OutOfRangeError> {
+        check_range(value, Self::MIN.0, Self::MAX.0).map(Self)
+    }
+
+    /// Returns the color temperature in kelvin.
+    pub fn value(self) -> u16 {
+        self.0
+    }
+}
+
+impl From<Kelvin> for Mired {
+    fn from(value: Kelvin) -> Self {
+        let mired = reciprocal(value.0).clamp(Self::MIN.0, Self::MAX.0);
+        Self(mired)
+    }
+}
+
+impl From<Mired> for Kelvin {
+    fn from(value: Mired) -> Self {
+        let kelvin = reciprocal(value.0).clamp(Self::MIN.0, Self::MAX.0);
+        Self(kelvin)
+    }
+}
+
+/// Implements conversions between a unit and its raw value.
+macro_rules! impl_raw_conversions {
+    ($unit:ty, $raw:ty) => {
+        impl TryFrom<$raw> for $unit {
+            type Error = OutOfRangeError;
+
+            fn try_from(value: $raw) -> Result<Self, Self::Error> {
+                Self::new(value)
+            }
+        }
+
+        impl From<$unit> for $raw {
+            fn from(value: $unit) -> Self {
+                value.value()
+            }
+        }
+
+        impl From<$unit> for Adjust<$raw> {
+            fn from(value: $unit) -> Self {
+                Adjust::Override(value.value())
+            }
+        }
+    };
+}
+
+impl_raw_conversions!(Brightness, u8);
+impl_raw_conversions!(Saturation, u8);
+impl_raw_conversions!(Mired, u16);
+
+impl From<HueDegrees> for u16 {
+    fn from(value: HueDegrees) -> Self {
+        value.value()
+    }
+}
+
+impl From<HueDegrees> for Adjust<u16> {
+    fn from(value: HueDegrees) -> Self {
+        Adjust::Override(value.value())
+    }
+}
+
+impl From<Kelvin> for Adjust<u16> {
+    fn from(value: Kelvin) -> Self {
+        Mired::from(value).into()
+    }
+}
+
+/// Error that occurs when a value is outside of the range of a unit.
+#[derive(Clone, Debug, Eq, PartialEq, ThisError)]
+#[error("Value is outside of the range from {min} to {max}")]
+pub struct OutOfRangeError {
+    /// Minimum value of the range.
+    pub min: u32,
+    /// Maximum value of the range.
+    pub max: u32,
+    /// Value that is outside of the range, if it is an integer.
+    pub value: Option<u32>,
+}
+
+fn check_range<T>(value: T, min: T, max: T) -> Result<T, OutOfRangeError>
+where
+    T: Copy + PartialOrd + Into<u32>,
+{
+    if value < min || value > max {
+        return Err(OutOfRangeError {
+            min: min.into(),
+            max: max.into(),
+            value: Some(value.into()),
+        });
+    }
+    Ok(value)
+}
+
+fn scale_percent(value: f32, min: f32, max: f32) -> f32 {
+    let value = if value.is_nan() { 0.0 } else { value };
+    (min + value.clamp(0.0, 100.0) / 100.0 * (max - min)).round()
+}
+
+/// Converts between kelvin and mired.
+fn reciprocal(value: u16) -> u16 {
+    (1_000_000.0 / value as f32).round().min(u16::MAX as f32) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn brightness() {
+        assert_eq!(
+            Brightness::new(0),
+            Err(OutOfRangeError {
+                min: 1,
+                max: 254,
+                value: Some(0)
+            })
+        );
+        assert_eq!(Brightness::new(254).unwrap().value(), 254);
+        assert!(Brightness::try_from(255).is_err());
+        assert_eq!(Brightness::from_percent(0.0), Brightness::MIN);
+        assert_eq!(Brightness::from_percent(150.0), Brightness::MAX);
+        assert_eq!(Saturation::from_percent(50.0).value(), 127);
+        assert_eq!(Adjust::from(Brightness::MAX), Adjust::Override(254));
+    }
+
+    #[test]
+    fn hue_degrees() {
+        assert_eq!(HueDegrees::new(-90.0).unwrap().degrees(), 270.0);
+        assert_eq!(HueDegrees::new(120.0).unwrap().value(), 21845);
+        assert_eq!(HueDegrees::new(0.0).unwrap().value(), 0);
+        assert_eq!(HueDegrees::new(359.999).unwrap().value(), 0);
+        assert!(HueDegrees::new(f32::NAN).is_err());
+        assert_eq!(HueDegrees::from_raw(43690).value(), 43690);
+    }
+
+    #[test]
+    fn color_temperature() {
+        assert_eq!(Mired::from(Kelvin::new(2700).unwrap()).value(), 370);
+        assert_eq!(Mired::from(Kelvin::MIN), Mired::MAX);
+        assert_eq!(Mired::from(Kelvin::MAX), Mired::MIN);
+        assert_eq!(Kelvin::from(Mired::MIN), Kelvin::MAX);
+        assert_eq!(Kelvin::from(Mired::MAX), Kelvin::MIN);
+        assert!(Mired::new(100).is_err());
+        assert_eq!(Mired::from_kelvin(2700).unwrap().value(), 370);
+        assert_eq!(Mired::from_kelvin(6536).unwrap(), Mired::MIN);
+        assert_eq!(
+            Mired::from_kelvin(1000),
+            Err(OutOfRangeError {
+                min: 153,
+                max: 500,
+                value: Some(1000)
+            })
+        );
+        assert!(Mired::from_kelvin(0).is_err());
+        assert_eq!(Adjust::from(Kelvin::MIN), Adjust::Override(500));
+    }
+}
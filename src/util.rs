@@ -1,5 +1,6 @@
-use chrono::{NaiveDateTime, NaiveTime};
+use chrono::{DateTime, NaiveDateTime, NaiveTime, TimeZone, Utc};
 use serde::de::{Deserialize, Deserializer, Error};
+use std::time::Duration;
 
 pub(crate) fn deserialize_option_string<'de, D: Deserializer<'de>>(
     deserializer: D,
@@ -23,6 +24,11 @@ pub(crate) fn deserialize_option_date_time<'de, D: Deserializer<'de>>(
     })
 }
 
+/// Interprets a date and time returned by the bridge as UTC.
+pub(crate) fn utc(value: NaiveDateTime) -> DateTime<Utc> {
+    Utc.from_utc_datetime(&value)
+}
+
 pub(crate) fn deserialize_option_time<'de, D: Deserializer<'de>>(
     deserializer: D,
 ) -> Result<Option<NaiveTime>, D::Error> {
@@ -33,6 +39,59 @@ pub(crate) fn deserialize_option_time<'de, D: Deserializer<'de>>(
     })
 }
 
+/// Converts a duration to a transition time in multiples of 100ms.
+///
+/// The duration is rounded to the nearest multiple of 100ms and clamped to the maximum transition
+/// time of about 109 minutes.
+pub(crate) fn transition_time(duration: Duration) -> u16 {
+    ((duration.as_millis() + 50) / 100).min(u16::MAX as u128) as u16
+}
+
+/// Returns the names that are similar to `name`, ignoring case, ordered by similarity.
+///
+/// A name is similar if one name contains the other or if they differ in at most two characters.
+pub(crate) fn near_matches<'a, I>(name: &str, candidates: I) -> Vec<String>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let name = name.to_lowercase();
+    let mut matches: Vec<(usize, &str)> = candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let lowercase = candidate.to_lowercase();
+            let distance = edit_distance(&name, &lowercase);
+            if distance <= 2 || lowercase.contains(&name) || name.contains(&lowercase) {
+                Some((distance, candidate))
+            } else {
+                None
+            }
+        })
+        .collect();
+    matches.sort_unstable();
+    matches.dedup();
+    matches.into_iter().map(|(_, v)| v.to_owned()).collect()
+}
+
+/// Returns the number of inserted, removed or replaced characters to get from `a` to `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let current = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                previous
+            } else {
+                1 + previous.min(current).min(row[j])
+            };
+            previous = current;
+        }
+    }
+    row[b.len()]
+}
+
 macro_rules! custom_serialize {
     ($serializer:expr, $struct_name:expr; $($k:ident => ($($v:tt)*),)*) => {
         let mut len = 0;
@@ -79,6 +138,7 @@ macro_rules! custom_serialize {
 mod tests {
     use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
     use serde_json::json;
+    use std::time::Duration;
 
     #[test]
     fn deserialize_option_string() {
@@ -112,6 +172,14 @@ mod tests {
         assert_eq!(value, Some(NaiveDateTime::new(date, time)));
     }
 
+    #[test]
+    fn utc() {
+        let date = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+        let time = NaiveTime::from_hms_opt(1, 30, 0).unwrap();
+        let value = super::utc(NaiveDateTime::new(date, time));
+        assert_eq!(value.to_rfc3339(), "2020-01-01T01:30:00+00:00");
+    }
+
     #[test]
     fn deserialize_option_time() {
         let json = json!("none");
@@ -126,4 +194,30 @@ mod tests {
         let value = super::deserialize_option_time(json).unwrap();
         assert_eq!(value, Some(NaiveTime::from_hms_opt(2, 0, 20).unwrap()));
     }
+
+    #[test]
+    fn transition_time() {
+        assert_eq!(super::transition_time(Duration::from_millis(0)), 0);
+        assert_eq!(super::transition_time(Duration::from_millis(49)), 0);
+        assert_eq!(super::transition_time(Duration::from_millis(150)), 2);
+        assert_eq!(super::transition_time(Duration::from_secs(3)), 30);
+        assert_eq!(super::transition_time(Duration::from_secs(86400)), u16::MAX);
+    }
+
+    #[test]
+    fn near_matches() {
+        assert_eq!(super::edit_distance("kitchen", "kitchen"), 0);
+        assert_eq!(super::edit_distance("kitchen", "kichen"), 1);
+        assert_eq!(super::edit_distance("", "abc"), 3);
+        let names = ["Kitchen", "Living room", "Kids room", "Office"];
+        assert_eq!(
+            super::near_matches("kitchn", names.iter().copied()),
+            vec!["Kitchen"]
+        );
+        assert_eq!(
+            super::near_matches("room", names.iter().copied()),
+            vec!["Kids room", "Living room"]
+        );
+        assert!(super::near_matches("garage", names.iter().copied()).is_empty());
+    }
 }